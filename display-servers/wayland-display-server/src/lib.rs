@@ -0,0 +1,63 @@
+//! Skeleton of a Wayland display server.
+//!
+//! It doesn't talk to a compositor yet, the actions it is asked to execute are queued until
+//! the protocol is in place. It only uses the `DisplayServer` trait, to show which parts of the
+//! core still assume X: window handles and the modifier and button masks of
+//! `utils::modmask_lookup` are xlib types.
+//!
+//! This is experimental, `leftwm` itself can't be built on top of it until it shows windows.
+use futures::future;
+use leftwm_core::{Config, DisplayAction, DisplayEvent, DisplayServer};
+use std::future::Future;
+use std::pin::Pin;
+
+#[derive(Default)]
+pub struct WaylandDisplayServer {
+    // Events received from the compositor, handed to the core on the next read.
+    events: Vec<DisplayEvent>,
+    // Actions waiting to be sent to the compositor.
+    pending: Vec<DisplayAction>,
+}
+
+impl DisplayServer for WaylandDisplayServer {
+    fn new(_: &impl Config) -> Self {
+        Self::default()
+    }
+
+    fn get_next_events(&mut self) -> Vec<DisplayEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn execute_action(&mut self, act: DisplayAction) -> Option<DisplayEvent> {
+        self.pending.push(act);
+        None
+    }
+
+    // Without a compositor connection nothing ever becomes readable.
+    fn wait_readable(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(future::pending())
+    }
+
+    fn flush(&self) {}
+
+    fn generate_verify_focus_event(&self) -> Option<DisplayEvent> {
+        None
+    }
+}
+
+#[cfg(test)]
+impl leftwm_core::display_servers::handler_tests::RecordingDisplayServer for WaylandDisplayServer {
+    fn take_actions(&mut self) -> Vec<DisplayAction> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handlers_work_behind_the_display_server_trait() {
+        leftwm_core::display_servers::handler_tests::run_all::<WaylandDisplayServer>();
+    }
+}
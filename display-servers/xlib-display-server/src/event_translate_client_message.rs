@@ -1,5 +1,8 @@
 use super::{DisplayEvent, XWrap};
-use leftwm_core::{models::WindowChange, Command};
+use leftwm_core::{
+    models::{WindowChange, WindowState},
+    Command,
+};
 use std::convert::TryFrom;
 use std::os::raw::c_long;
 
@@ -57,30 +60,15 @@ pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<Displa
         return None;
     }
 
-    //if the client is trying to toggle fullscreen without changing the window state, change it too
-    if event.message_type == xw.atoms.NetWMState
-        && (event.data.get_long(1) == xw.atoms.NetWMStateFullscreen as c_long
-            || event.data.get_long(2) == xw.atoms.NetWMStateFullscreen as c_long)
-    {
-        let set_fullscreen = event.data.get_long(0) == 1;
-        let toggle_fullscreen = event.data.get_long(0) == 2;
-        let mut states = xw.get_window_states_atoms(event.window);
-        //determine what to change the state to
-        let fullscreen = if toggle_fullscreen {
-            !states.contains(&xw.atoms.NetWMStateFullscreen)
-        } else {
-            set_fullscreen
-        };
-        //update the list of states
-        if fullscreen {
-            states.push(xw.atoms.NetWMStateFullscreen);
-        } else {
-            states.retain(|x| x != &xw.atoms.NetWMStateFullscreen);
+    //if the client is trying to toggle fullscreen or ask for attention without changing the
+    //window state, change it too
+    if event.message_type == xw.atoms.NetWMState {
+        for atom in [
+            xw.atoms.NetWMStateFullscreen,
+            xw.atoms.NetWMStateDemandsAttention,
+        ] {
+            apply_state_message(xw, &event, atom);
         }
-        states.sort_unstable();
-        states.dedup();
-        //set the windows state
-        xw.set_window_states_atoms(event.window, &states);
     }
 
     //update the window states
@@ -88,9 +76,37 @@ pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<Displa
         let handle = event.window.into();
         let mut change = WindowChange::new(handle);
         let states = xw.get_window_states(event.window);
+        change.skip_focus_cycle = Some(states.contains(&WindowState::SkipTaskbar));
         change.states = Some(states);
+        change.demands_attention = Some(xw.get_window_demands_attention(event.window));
         return Some(DisplayEvent::WindowChange(change));
     }
 
     None
 }
+
+/// Adds, removes or toggles a state atom of the window as asked for by a `_NET_WM_STATE` message.
+fn apply_state_message(xw: &XWrap, event: &xlib::XClientMessageEvent, atom: xlib::Atom) {
+    if event.data.get_long(1) != atom as c_long && event.data.get_long(2) != atom as c_long {
+        return;
+    }
+    let set_state = event.data.get_long(0) == 1;
+    let toggle_state = event.data.get_long(0) == 2;
+    let mut states = xw.get_window_states_atoms(event.window);
+    //determine what to change the state to
+    let enabled = if toggle_state {
+        !states.contains(&atom)
+    } else {
+        set_state
+    };
+    //update the list of states
+    if enabled {
+        states.push(atom);
+    } else {
+        states.retain(|x| x != &atom);
+    }
+    states.sort_unstable();
+    states.dedup();
+    //set the windows state
+    xw.set_window_states_atoms(event.window, &states);
+}
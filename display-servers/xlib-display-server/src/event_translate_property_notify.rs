@@ -1,5 +1,5 @@
 use super::{DisplayEvent, XWrap};
-use leftwm_core::models::{WindowChange, WindowType, Xyhw};
+use leftwm_core::models::{WindowChange, WindowState, WindowType, Xyhw};
 
 use x11_dl::xlib;
 
@@ -36,6 +36,7 @@ pub fn from_event(xw: &XWrap, event: xlib::XPropertyEvent) -> Option<DisplayEven
             .map(|hints| build_change_hints(event, hints))
             .map(DisplayEvent::WindowChange),
         xlib::XA_WM_NAME => Some(update_title(xw, event.window)),
+        xlib::XA_WM_CLASS => Some(update_class(xw, event.window)),
         _ => {
             if event.atom == xw.atoms.NetWMName {
                 return Some(update_title(xw, event.window));
@@ -50,11 +51,19 @@ pub fn from_event(xw: &XWrap, event: xlib::XPropertyEvent) -> Option<DisplayEven
                 }
             }
 
+            if event.atom == xw.atoms.MotifWMHints {
+                let mut change = WindowChange::new(event.window.into());
+                change.no_border = Some(xw.get_window_wants_no_border(event.window));
+                return Some(DisplayEvent::WindowChange(change));
+            }
+
             if event.atom == xw.atoms.NetWMState {
                 let handle = event.window.into();
                 let mut change = WindowChange::new(handle);
                 let states = xw.get_window_states(event.window);
+                change.skip_focus_cycle = Some(states.contains(&WindowState::SkipTaskbar));
                 change.states = Some(states);
+                change.demands_attention = Some(xw.get_window_demands_attention(event.window));
                 return Some(DisplayEvent::WindowChange(change));
             }
 
@@ -105,9 +114,10 @@ fn build_change_for_size_hints(xw: &XWrap, window: xlib::Window) -> Option<Windo
     let handle = window.into();
     let mut change = WindowChange::new(handle);
     let hint = xw.get_hint_sizing_as_xyhw(window)?;
+    change.aspect = Some(xw.get_hint_aspect(window));
     if hint.x.is_none() && hint.y.is_none() && hint.w.is_none() && hint.h.is_none() {
-        //junk hint; change change anything
-        return None;
+        //junk hint; only update the aspect
+        return Some(change);
     }
     let mut xyhw = Xyhw::default();
     hint.update(&mut xyhw);
@@ -115,6 +125,18 @@ fn build_change_for_size_hints(xw: &XWrap, window: xlib::Window) -> Option<Windo
     Some(change)
 }
 
+fn update_class(xw: &XWrap, window: xlib::Window) -> DisplayEvent {
+    let (res_name, res_class) = match xw.get_window_class(window) {
+        Some((res_name, res_class)) => (Some(res_name), Some(res_class)),
+        None => (None, None),
+    };
+    let handle = window.into();
+    let mut change = WindowChange::new(handle);
+    change.res_name = Some(res_name);
+    change.res_class = Some(res_class);
+    DisplayEvent::WindowChange(change)
+}
+
 fn update_title(xw: &XWrap, window: xlib::Window) -> DisplayEvent {
     let title = xw.get_window_name(window);
     let handle = window.into();
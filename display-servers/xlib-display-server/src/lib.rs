@@ -106,6 +106,7 @@ impl DisplayServer for XlibDisplayServer {
             DisplayAction::ReadyToResizeWindow(h) => from_ready_to_resize_window(xw, h),
             DisplayAction::SetCurrentTags(t) => from_set_current_tags(xw, t),
             DisplayAction::SetWindowTag(h, t) => from_set_window_tag(xw, h, t),
+            DisplayAction::SetOpacity(h, o) => from_set_opacity(xw, h, o),
             DisplayAction::ConfigureXlibWindow(w) => from_configure_xlib_window(xw, &w),
 
             DisplayAction::WindowTakeFocus {
@@ -152,8 +153,11 @@ impl XlibDisplayServer {
                     events.push(e);
                 });
             } else {
-                for wsc in &workspaces {
-                    let mut screen = Screen::from(wsc);
+                // Geometry left on `auto` is taken from the screen at the same position.
+                let screens = self.xw.get_screens();
+                for (index, wsc) in workspaces.iter().enumerate() {
+                    let detected = screens.get(index).or_else(|| screens.first());
+                    let mut screen = Screen::from_workspace_config(wsc, detected.map(|s| &s.bbox));
                     screen.root = self.root.into();
                     let e = DisplayEvent::ScreenCreate(screen);
                     events.push(e);
@@ -319,6 +323,12 @@ fn from_set_window_tag(
     None
 }
 
+fn from_set_opacity(xw: &mut XWrap, handle: WindowHandle, opacity: f32) -> Option<DisplayEvent> {
+    let window = handle.xlib_handle()?;
+    xw.set_window_opacity(window, opacity);
+    None
+}
+
 fn from_configure_xlib_window(xw: &mut XWrap, window: &Window) -> Option<DisplayEvent> {
     xw.configure_window(window);
     None
@@ -18,6 +18,8 @@ pub struct XAtom {
     pub NetWMState: xlib::Atom,
     pub NetWMAction: xlib::Atom,
     pub NetWMPid: xlib::Atom,
+    pub NetStartupId: xlib::Atom,
+    pub MotifWMHints: xlib::Atom,
 
     pub NetWMActionMove: xlib::Atom,
     pub NetWMActionResize: xlib::Atom,
@@ -61,6 +63,7 @@ pub struct XAtom {
     pub NetWMDesktop: xlib::Atom,
     pub NetWMStrutPartial: xlib::Atom, //net version - Reserve Screen Space
     pub NetWMStrut: xlib::Atom,        //old version
+    pub NetWMWindowOpacity: xlib::Atom,
 
     pub UTF8String: xlib::Atom,
 }
@@ -129,6 +132,8 @@ impl XAtom {
             a if a == self.NetWMState => "_NET_WM_STATE",
             a if a == self.NetWMAction => "_NET_WM_ALLOWED_ACTIONS",
             a if a == self.NetWMPid => "_NET_WM_PID",
+            a if a == self.NetStartupId => "_NET_STARTUP_ID",
+            a if a == self.MotifWMHints => "_MOTIF_WM_HINTS",
 
             a if a == self.NetWMStateModal => "NetWMStateModal",
             a if a == self.NetWMStateSticky => "NetWMStateSticky",
@@ -164,6 +169,7 @@ impl XAtom {
             a if a == self.NetWMDesktop => "_NET_WM_DESKTOP",
             a if a == self.NetWMStrutPartial => "_NET_WM_STRUT_PARTIAL",
             a if a == self.NetWMStrut => "_NET_WM_STRUT",
+            a if a == self.NetWMWindowOpacity => "_NET_WM_WINDOW_OPACITY",
 
             a if a == self.UTF8String => "UTF8_STRING",
             _ => "(UNKNOWN)",
@@ -181,6 +187,8 @@ impl XAtom {
             NetSupported: from(xlib, dpy, "_NET_SUPPORTED"),
             NetWMName: from(xlib, dpy, "_NET_WM_NAME"),
             NetWMPid: from(xlib, dpy, "_NET_WM_PID"),
+            NetStartupId: from(xlib, dpy, "_NET_STARTUP_ID"),
+            MotifWMHints: from(xlib, dpy, "_MOTIF_WM_HINTS"),
 
             NetWMState: from(xlib, dpy, "_NET_WM_STATE"),
             NetWMStateModal: from(xlib, dpy, "_NET_WM_STATE_MODAL"),
@@ -226,6 +234,7 @@ impl XAtom {
             NetWMDesktop: from(xlib, dpy, "_NET_WM_DESKTOP"),
             NetWMStrutPartial: from(xlib, dpy, "_NET_WM_STRUT_PARTIAL"),
             NetWMStrut: from(xlib, dpy, "_NET_WM_STRUT"),
+            NetWMWindowOpacity: from(xlib, dpy, "_NET_WM_WINDOW_OPACITY"),
 
             UTF8String: from(xlib, dpy, "UTF8_STRING"),
         }
@@ -127,6 +127,23 @@ impl XWrap {
         self.root
     }
 
+    /// Returns the aspect ratio (width, height) a window asks to keep in its `WM_NORMAL_HINTS`.
+    /// Only fixed aspect ratios, where the min and max aspect are the same, are returned.
+    #[must_use]
+    pub fn get_hint_aspect(&self, window: xlib::Window) -> Option<(u32, u32)> {
+        let size = self.get_hint_sizing(window)?;
+        if (size.flags & xlib::PAspect) == 0 {
+            return None;
+        }
+        let (min, max) = (size.min_aspect, size.max_aspect);
+        if i64::from(min.x) * i64::from(max.y) != i64::from(max.x) * i64::from(min.y) {
+            return None;
+        }
+        let width = u32::try_from(min.x).ok().filter(|&w| w > 0)?;
+        let height = u32::try_from(min.y).ok().filter(|&h| h > 0)?;
+        Some((width, height))
+    }
+
     /// Returns the `WM_SIZE_HINTS`/`WM_NORMAL_HINTS` of a window as a `XyhwChange`.
     #[must_use]
     pub fn get_hint_sizing_as_xyhw(&self, window: xlib::Window) -> Option<XyhwChange> {
@@ -169,12 +186,6 @@ impl XWrap {
                 xyhw.x = Some(size.x);
                 xyhw.y = Some(size.y);
             }
-            // TODO: support min/max aspect
-            // if size.flags & xlib::PAspect != 0 {
-            //     //c->mina = (float)size.min_aspect.y / size.min_aspect.x;
-            //     //c->maxa = (float)size.max_aspect.x / size.max_aspect.y;
-            // }
-
             return Some(xyhw);
         }
         None
@@ -419,11 +430,43 @@ impl XWrap {
         }
     }
 
+    /// Returns a windows `_NET_STARTUP_ID`.
+    #[must_use]
+    pub fn get_window_startup_id(&self, window: xlib::Window) -> Option<String> {
+        self.get_text_prop(window, self.atoms.NetStartupId).ok()
+    }
+
+    /// Returns true if a window has `_NET_WM_STATE_DEMANDS_ATTENTION` set.
+    #[must_use]
+    pub fn get_window_demands_attention(&self, window: xlib::Window) -> bool {
+        self.get_window_states_atoms(window)
+            .contains(&self.atoms.NetWMStateDemandsAttention)
+    }
+
+    /// Returns true if a window asks for no decorations through `_MOTIF_WM_HINTS`.
+    #[must_use]
+    pub fn get_window_wants_no_border(&self, window: xlib::Window) -> bool {
+        // The hints are: flags, functions, decorations, input mode and status.
+        const MWM_HINTS_DECORATIONS: c_long = 1 << 1;
+        let (prop_return, nitems_return) =
+            match self.get_property(window, self.atoms.MotifWMHints, self.atoms.MotifWMHints) {
+                Ok(prop) => prop,
+                Err(_) => return false,
+            };
+        unsafe {
+            #[allow(clippy::cast_ptr_alignment)]
+            let hints = slice::from_raw_parts(prop_return.cast::<c_long>(), nitems_return as usize);
+            hints.len() >= 3 && hints[0] & MWM_HINTS_DECORATIONS != 0 && hints[2] == 0
+        }
+    }
+
     /// Returns the states of a window.
     #[must_use]
     pub fn get_window_states(&self, window: xlib::Window) -> Vec<WindowState> {
         self.get_window_states_atoms(window)
             .iter()
+            // Attention is kept in its own flag.
+            .filter(|a| *a != &self.atoms.NetWMStateDemandsAttention)
             .map(|a| match a {
                 x if x == &self.atoms.NetWMStateModal => WindowState::Modal,
                 x if x == &self.atoms.NetWMStateSticky => WindowState::Sticky,
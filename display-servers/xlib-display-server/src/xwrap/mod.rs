@@ -11,7 +11,7 @@ use super::xcursor::XCursor;
 use super::{utils, Screen, Window, WindowHandle};
 use leftwm_core::config::Config;
 use leftwm_core::models::{FocusBehaviour, Mode};
-use leftwm_core::utils::modmask_lookup::ModMask;
+use leftwm_core::utils::modmask_lookup::{Button, ModMask};
 use std::ffi::CString;
 use std::os::raw::{c_char, c_double, c_int, c_long, c_short, c_ulong};
 use std::sync::Arc;
@@ -113,6 +113,8 @@ pub struct XWrap {
     pub mode: Mode,
     pub focus_behaviour: FocusBehaviour,
     pub mouse_key_mask: ModMask,
+    // The modifiers and buttons of the mousebinds, grabbed on the root window.
+    pub mousebinds: Vec<(ModMask, Button)>,
     pub mode_origin: (i32, i32),
     _task_guard: oneshot::Receiver<()>,
     pub task_notify: Arc<Notify>,
@@ -234,6 +236,7 @@ impl XWrap {
             mode: Mode::Normal,
             focus_behaviour: FocusBehaviour::Sloppy,
             mouse_key_mask: 0,
+            mousebinds: vec![],
             mode_origin: (0, 0),
             _task_guard,
             task_notify,
@@ -268,6 +271,7 @@ impl XWrap {
     ) {
         self.focus_behaviour = config.focus_behaviour();
         self.mouse_key_mask = utils::modmask_lookup::into_modmask(&config.mousekey());
+        self.load_mousebinds(config);
         self.load_colors(config, focused, Some(windows));
         self.tag_labels = config.create_list_of_tag_labels();
     }
@@ -297,6 +301,7 @@ impl XWrap {
         }
 
         self.subscribe_to_event(root, ROOT_EVENT_MASK);
+        self.load_mousebinds(config);
 
         // EWMH compliance.
         unsafe {
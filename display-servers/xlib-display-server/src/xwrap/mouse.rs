@@ -2,6 +2,7 @@
 use super::{XlibError, MOUSEMASK};
 use crate::xwrap::BUTTONMASK;
 use crate::XWrap;
+use leftwm_core::Config;
 use std::os::raw::{c_int, c_uint, c_ulong};
 use x11_dl::xlib;
 
@@ -19,6 +20,19 @@ impl XWrap {
         self.grab_buttons(handle, xlib::Button3, self.mouse_key_mask | xlib::ShiftMask);
     }
 
+    /// Grabs the buttons of the mousebinds on the root window, so they work over any window.
+    pub fn load_mousebinds(&mut self, config: &impl Config) {
+        self.mousebinds = config
+            .mousebinds()
+            .iter()
+            .map(|bind| (bind.modmask, bind.button))
+            .collect();
+        self.ungrab_buttons(self.root);
+        for &(modmask, button) in &self.mousebinds {
+            self.grab_buttons(self.root, button, modmask);
+        }
+    }
+
     /// Grabs the button with the modifier for a window.
     // `XGrabButton`: https://tronche.com/gui/x/xlib/input/XGrabButton.html
     pub fn grab_buttons(&self, window: xlib::Window, button: u32, modifiers: u32) {
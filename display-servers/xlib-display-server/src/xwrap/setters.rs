@@ -189,6 +189,17 @@ impl XWrap {
         self.replace_property_long(window, self.atoms.NetWMDesktop, xlib::XA_CARDINAL, &indexes);
     }
 
+    /// Sets the `_NET_WM_WINDOW_OPACITY` of a window, which is applied by compositors.
+    pub fn set_window_opacity(&self, window: xlib::Window, opacity: f32) {
+        let opacity = (f64::from(opacity.clamp(0.0, 1.0)) * f64::from(u32::MAX)) as c_long;
+        self.replace_property_long(
+            window,
+            self.atoms.NetWMWindowOpacity,
+            xlib::XA_CARDINAL,
+            &[opacity],
+        );
+    }
+
     /// Sets the atom states of a window.
     pub fn set_window_states_atoms(&self, window: xlib::Window, states: &[xlib::Atom]) {
         let data: Vec<c_long> = states.iter().map(|x| *x as c_long).collect();
@@ -209,6 +220,16 @@ impl XWrap {
         }
     }
 
+    /// Removes `_NET_WM_STATE_DEMANDS_ATTENTION` from a window.
+    pub fn clear_window_demands_attention(&self, window: xlib::Window) {
+        let mut states = self.get_window_states_atoms(window);
+        let count = states.len();
+        states.retain(|x| x != &self.atoms.NetWMStateDemandsAttention);
+        if states.len() != count {
+            self.set_window_states_atoms(window, &states);
+        }
+    }
+
     /// Sets the `XWMHints` of a window.
     pub fn set_wmhints(&self, window: xlib::Window, wmh: &mut xlib::XWMHints) {
         unsafe { (self.xlib.XSetWMHints)(self.display, window, wmh) };
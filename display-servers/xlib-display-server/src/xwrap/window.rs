@@ -4,7 +4,7 @@ use super::{
     ROOT_EVENT_MASK, WITHDRAWN_STATE,
 };
 use crate::XWrap;
-use leftwm_core::models::{WindowChange, WindowType, Xyhw, XyhwChange};
+use leftwm_core::models::{WindowChange, WindowState, WindowType, Xyhw, XyhwChange};
 use leftwm_core::DisplayEvent;
 use std::os::raw::{c_long, c_ulong};
 use x11_dl::xlib;
@@ -12,11 +12,9 @@ use x11_dl::xlib;
 impl XWrap {
     /// Sets up a window before we manage it.
     pub fn setup_window(&self, window: xlib::Window) -> Option<DisplayEvent> {
-        // Check that the window isn't requesting to be unmanaged
+        // Windows requesting to be unmanaged are flagged and left alone by the manager.
         let attrs = match self.get_window_attrs(window) {
-            Ok(attr) if attr.override_redirect == 0 && !self.managed_windows.contains(&window) => {
-                attr
-            }
+            Ok(attr) if !self.managed_windows.contains(&window) => attr,
             _ => return None,
         };
         let handle = window.into();
@@ -25,12 +23,15 @@ impl XWrap {
         let legacy_name = self.get_window_legacy_name(window);
         let class = self.get_window_class(window);
         let pid = self.get_window_pid(window);
+        let startup_id = self.get_window_startup_id(window);
+        let no_border = self.get_window_wants_no_border(window);
         let r#type = self.get_window_type(window);
         let states = self.get_window_states(window);
         let actions = self.get_window_actions_atoms(window);
         let mut can_resize = actions.contains(&self.atoms.NetWMActionResize);
         let trans = self.get_transient_for(window);
         let sizing_hint = self.get_hint_sizing_as_xyhw(window);
+        let aspect = self.get_hint_aspect(window);
         let wm_hint = self.get_wmhints(window);
 
         // Build the new window, and fill in info about it.
@@ -40,7 +41,12 @@ impl XWrap {
             w.res_class = Some(res_class);
         }
         w.legacy_name = legacy_name;
+        w.override_redirect = attrs.override_redirect != 0;
+        w.startup_id = startup_id;
+        w.no_border = no_border;
+        w.demands_attention = self.get_window_demands_attention(window);
         w.r#type = r#type.clone();
+        w.skip_focus_cycle = states.contains(&WindowState::SkipTaskbar);
         w.set_states(states);
         if let Some(trans) = trans {
             w.transient = Some(trans.into());
@@ -76,6 +82,7 @@ impl XWrap {
             hint.update(&mut requested);
         }
         w.requested = Some(requested);
+        w.aspect = aspect;
         w.can_resize = can_resize;
         if let Some(hint) = wm_hint {
             w.never_focus = hint.flags & xlib::InputHint != 0 && hint.input == 0;
@@ -261,6 +268,7 @@ impl XWrap {
             self.focused_window = handle;
             self.grab_mouse_clicks(handle, true);
             self.set_window_urgency(handle, false);
+            self.clear_window_demands_attention(handle);
             self.set_window_border_color(handle, self.colors.active);
             self.focus(handle, window.never_focus);
             self.sync();
@@ -1,14 +1,19 @@
 pub use crate::handlers::command_handler::ReleaseScratchPadOption;
 use crate::{
     layouts::Layout,
-    models::{ScratchPadName, TagId, WindowHandle},
+    models::{FocusDirection, ScratchPadName, Side, TagId, WindowHandle},
 };
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Command {
     CloseWindow,
+    CloseAllOnTag,
     SwapScreens,
+    SwapMonitorTags {
+        a: usize,
+        b: usize,
+    },
     SoftReload,
     HardReload,
     AttachScratchPad {
@@ -26,21 +31,51 @@ pub enum Command {
         scratchpad: ScratchPadName,
     },
     ToggleScratchPad(ScratchPadName),
+    SaveScratchPadGeometry {
+        name: ScratchPadName,
+    },
     ToggleFullScreen,
     ToggleSticky,
+    /// Makes the focused window fill its workspace over the layout, or restores it.
+    ToggleMaximize,
     GoToTag {
         tag: TagId,
         swap: bool,
     },
-    ReturnToLastTag,
+    /// Goes to the previously focused tag, without `swap` it stays on the focused tag like
+    /// `GoToTag` with the focused tag does.
+    ReturnToLastTag {
+        swap: bool,
+    },
+    MoveTag {
+        from: TagId,
+        to: TagId,
+    },
+    /// Exchanges the windows of two tags, the tags stay where they are.
+    SwapTagContents {
+        a: TagId,
+        b: TagId,
+    },
     FloatingToTile,
     TileToFloating,
     ToggleFloating,
+    TileAllFloating,
+    /// Floats the focused window and keeps it out of the layout, or tiles it again.
+    ToggleIgnoreInLayout,
     MoveWindowUp,
     MoveWindowDown,
     MoveWindowTop {
         swap: bool,
     },
+    /// Makes the focused window the main window, shifting the others down.
+    PromoteToMaster,
+    SwapWindowDirection {
+        dir: FocusDirection,
+    },
+    /// Splits the space of the focused window and its neighbour in a direction evenly among them.
+    SplitFocusedWith {
+        dir: FocusDirection,
+    },
     FocusNextTag,
     FocusPreviousTag,
     FocusWindow(String),
@@ -49,12 +84,41 @@ pub enum Command {
     FocusWindowTop {
         swap: bool,
     },
+    FocusWindowDirection {
+        dir: FocusDirection,
+    },
+    FocusBySize {
+        largest: bool,
+    },
+    FocusUrgent,
+    FocusNextOfClass,
+    /// Cycles the focus through the tiled windows of the focused workspace, skipping floating ones.
+    FocusNextTiled,
+    FocusPrevTiled,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
     SendWindowToTag {
         window: Option<WindowHandle>,
         tag: TagId,
     },
+    /// Sends the focused window to a tag and goes there with it.
+    /// With `swap`, sending it to the focused tag goes to the previous tag like `GoToTag`.
+    MoveWindowToTagAndFollow {
+        tag: TagId,
+        swap: bool,
+    },
+    ToggleWindowTag {
+        tag: TagId,
+    },
+    SetWindowTags {
+        tags: Vec<TagId>,
+    },
+    MarkWindow {
+        key: char,
+    },
+    GotoMark {
+        key: char,
+    },
     MoveWindowToNextTag {
         follow: bool,
     },
@@ -64,14 +128,52 @@ pub enum Command {
     MoveWindowToLastWorkspace,
     MoveWindowToNextWorkspace,
     MoveWindowToPreviousWorkspace,
+    MoveWindowToMonitor {
+        dir: FocusDirection,
+    },
     NextLayout,
     PreviousLayout,
     SetLayout(Layout),
     RotateTag,
+    ToggleStackVisibility,
+    /// Places the main window of `MainAndVertStack` on a side of the focused tag,
+    /// or makes it follow the flips of the tag again without a side.
+    SetMainPosition {
+        side: Option<Side>,
+    },
     IncreaseMainWidth(i8),
     DecreaseMainWidth(i8),
+    /// Changes the main width of the focused tag by a percentage, keeping it between 10 and 90.
+    ChangeMainWidth {
+        delta: i32,
+    },
+    /// Sets the main width of the focused tag back to the default of its layout.
+    ResetMainWidth,
+    IncreaseBorder(i32),
+    DecreaseBorder(i32),
+    IncreaseGridColumns,
+    DecreaseGridColumns,
+    /// Sets the number of columns of the `Grid` layout on the focused tag.
+    SetGridColumns(usize),
+    ToggleOverview,
+    /// Switches to the next `InsertBehavior` for new windows.
+    CycleInsertBehavior,
+    /// Shows the windows of the overlay above the focused workspace, or hides them.
+    ToggleOverlay,
+    /// Moves the focused window onto the overlay, or back to the focused tag.
+    ToggleWindowOverlay,
     SetMarginMultiplier(f32),
+    ToggleGaps,
     SendWorkspaceToTag(usize, usize),
     CloseAllOtherWindows,
+    QueryFocusedWindow,
+    DumpLayout,
+    Execute(String),
+    Spawn {
+        command: String,
+        tag: Option<TagId>,
+    },
+    /// Runs the commands in order, stopping at the first one that fails.
+    Chain(Vec<Command>),
     Other(String),
 }
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Where new floating windows are placed on their workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingPlacement {
+    /// In the middle of the workspace.
+    Center,
+    /// Offset from the last window placed on the workspace, so they don't cover each other.
+    Cascade,
+    /// Under the mouse cursor.
+    Cursor,
+}
+
+// Deriving needs `#[default]`, which is newer than the supported Rust version.
+#[allow(clippy::derivable_impls)]
+impl Default for FloatingPlacement {
+    fn default() -> Self {
+        Self::Center
+    }
+}
@@ -8,6 +8,19 @@ pub enum InsertBehavior {
     AfterCurrent,
 }
 
+impl InsertBehavior {
+    /// The behavior after this one, wrapping around after `BeforeCurrent`.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::AfterCurrent,
+            Self::AfterCurrent => Self::BeforeCurrent,
+            Self::BeforeCurrent => Self::Top,
+        }
+    }
+}
+
 impl Default for InsertBehavior {
     fn default() -> Self {
         InsertBehavior::Bottom
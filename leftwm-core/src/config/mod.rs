@@ -1,13 +1,23 @@
+mod floating_placement;
 mod insert_behavior;
+mod mousebind;
+mod tile_drag;
 mod workspace_config;
 
+use std::collections::HashMap;
+
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
 pub use crate::models::ScratchPad;
-pub use crate::models::{FocusBehaviour, Gutter, Margins, Size};
+pub use crate::models::{
+    EmptyTagFocus, FocusAfterLastClose, FocusBehaviour, Gutter, Margins, Size,
+};
 use crate::models::{LayoutMode, Manager, Window, WindowType};
 use crate::state::State;
+pub use floating_placement::FloatingPlacement;
 pub use insert_behavior::InsertBehavior;
+pub use mousebind::Mousebind;
+pub use tile_drag::DisableTileDrag;
 pub use workspace_config::Workspace;
 
 pub trait Config {
@@ -19,18 +29,60 @@ pub trait Config {
 
     fn mousekey(&self) -> Vec<String>;
 
+    /// Mouse buttons which run a command when pressed with their modifiers.
+    fn mousebinds(&self) -> Vec<Mousebind>;
+
     fn create_list_of_scratchpads(&self) -> Vec<ScratchPad>;
 
+    /// Label of the hidden tag scratchpads are kept on while hidden.
+    fn scratchpad_tag_label(&self) -> String;
+
+    /// Label of the hidden tag holding the windows of the overlay.
+    fn overlay_tag_label(&self) -> String;
+
     fn layouts(&self) -> Vec<Layout>;
 
     fn layout_mode(&self) -> LayoutMode;
 
+    /// Layouts tags start with, keyed by tag label.
+    fn default_layouts(&self) -> Option<HashMap<String, Layout>>;
+
+    /// Number of tiled windows on a tag before new windows float, keyed by tag label.
+    fn max_tiled_windows(&self) -> Option<HashMap<String, usize>>;
+
+    /// Shell commands run when a tag gets the focus, keyed by tag label.
+    fn on_tag_focus_cmd(&self) -> Option<HashMap<String, String>>;
+
+    /// Shell commands run when a tag loses the focus, keyed by tag label.
+    fn on_tag_unfocus_cmd(&self) -> Option<HashMap<String, String>>;
+
+    /// Number of window classes whose last tag is remembered to open new windows on.
+    fn remember_app_tags(&self) -> Option<usize>;
+
+    /// Whether child windows float or tile, keyed by `WM_CLASS`.
+    fn transient_float(&self) -> Option<HashMap<String, bool>>;
+
+    /// Tags go back to their configured layout once their last window is closed.
+    fn reset_layout_on_empty(&self) -> bool;
+
     fn insert_behavior(&self) -> InsertBehavior;
 
+    /// Where new floating windows are placed.
+    fn floating_placement(&self) -> FloatingPlacement;
+
     fn single_window_border(&self) -> bool;
 
     fn focus_new_windows(&self) -> bool;
 
+    /// New windows don't take the focus from another window, unless they demand attention.
+    fn prevent_focus_stealing(&self) -> bool;
+
+    /// What happens to the focus when switching to a tag without windows.
+    fn focus_on_empty_tag(&self) -> EmptyTagFocus;
+
+    /// What gets focused after the last window of a workspace has been closed.
+    fn focus_after_last_close(&self) -> FocusAfterLastClose;
+
     fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
     where
         SERVER: DisplayServer,
@@ -40,6 +92,8 @@ pub trait Config {
     fn default_width(&self) -> i32;
     fn default_height(&self) -> i32;
     fn border_width(&self) -> i32;
+    fn floating_border_width(&self) -> i32;
+    fn tiled_border_width(&self) -> i32;
     fn margin(&self) -> Margins;
     fn workspace_margin(&self) -> Option<Margins>;
     fn gutter(&self) -> Option<Vec<Gutter>>;
@@ -48,11 +102,23 @@ pub trait Config {
     fn focused_border_color(&self) -> String;
     fn background_color(&self) -> String;
     fn on_new_window_cmd(&self) -> Option<String>;
+    fn on_reload_cmd(&self) -> Option<String>;
     fn get_list_of_gutters(&self) -> Vec<Gutter>;
     fn max_window_width(&self) -> Option<Size>;
-    fn disable_tile_drag(&self) -> bool;
+    fn disable_tile_drag(&self) -> DisableTileDrag;
     fn disable_window_snap(&self) -> bool;
+    /// Floating windows can't be moved or resized beyond the usable area of their workspace.
+    fn constrain_floating_to_workspace(&self) -> bool;
     fn sloppy_mouse_follows_focus(&self) -> bool;
+    fn follow_modal_dialogs(&self) -> bool;
+    fn float_on_cursor_monitor(&self) -> bool;
+    /// New windows float when the focused window does, unless a rule or the workspace decides.
+    fn inherit_floating_from_focused(&self) -> bool;
+    fn keep_fullscreen_on_new_window(&self) -> bool;
+    /// The space reserved by docks is kept free of windows, false for auto-hiding bars.
+    fn respect_dock_struts(&self) -> bool;
+    /// Docks are stacked above dialogs and floating windows instead of below all windows.
+    fn docks_on_top(&self) -> bool;
 
     /// Attempt to write current state to a file.
     ///
@@ -62,7 +128,9 @@ pub trait Config {
     fn save_state(&self, state: &State);
 
     /// Load saved state if it exists.
-    fn load_state(&self, state: &mut State);
+    ///
+    /// Returns true if a saved state was restored.
+    fn load_state(&self, state: &mut State) -> bool;
 
     /// Handle window placement based on `WM_CLASS`
     fn setup_predefined_window(&self, state: &mut State, window: &mut Window) -> bool;
@@ -70,8 +138,12 @@ pub trait Config {
     fn load_window(&self, window: &mut Window) {
         if window.r#type == WindowType::Normal {
             window.margin = self.margin();
-            window.border = self.border_width();
             window.must_float = self.always_float();
+            window.border = if window.floating() {
+                self.floating_border_width()
+            } else {
+                self.tiled_border_width()
+            };
         } else {
             window.margin = Margins::new(0);
             window.border = 0;
@@ -79,22 +151,53 @@ pub trait Config {
     }
 }
 
-#[cfg(test)]
-pub(crate) mod tests {
+#[cfg(any(test, feature = "test-suite"))]
+pub mod tests {
+    #[allow(clippy::wildcard_imports)]
     use super::*;
+    #[cfg(test)]
     use crate::models::Screen;
     use crate::models::Window;
+    #[cfg(test)]
     use crate::models::WindowHandle;
 
-    #[allow(clippy::module_name_repetitions)]
+    #[allow(clippy::module_name_repetitions, clippy::struct_excessive_bools)]
     #[derive(Default)]
     pub struct TestConfig {
         pub tags: Vec<String>,
         pub layouts: Vec<Layout>,
+        pub layout_mode: LayoutMode,
+        pub default_layouts: Option<HashMap<String, Layout>>,
+        pub max_tiled_windows: Option<HashMap<String, usize>>,
+        pub on_tag_focus_cmd: Option<HashMap<String, String>>,
+        pub on_tag_unfocus_cmd: Option<HashMap<String, String>>,
+        pub remember_app_tags: Option<usize>,
+        pub transient_float: Option<HashMap<String, bool>>,
+        pub reset_layout_on_empty: bool,
         pub workspaces: Option<Vec<Workspace>>,
         pub insert_behavior: InsertBehavior,
+        pub floating_placement: FloatingPlacement,
         pub border_width: i32,
+        pub floating_border_width: Option<i32>,
+        pub tiled_border_width: Option<i32>,
         pub single_window_border: bool,
+        pub follow_modal_dialogs: bool,
+        pub float_on_cursor_monitor: bool,
+        pub inherit_floating_from_focused: bool,
+        pub keep_fullscreen_on_new_window: bool,
+        // Inverted so the derived default respects dock struts.
+        pub ignore_dock_struts: bool,
+        pub docks_on_top: bool,
+        pub prevent_focus_stealing: bool,
+        pub constrain_floating_to_workspace: bool,
+        pub focus_on_empty_tag: EmptyTagFocus,
+        pub focus_after_last_close: FocusAfterLastClose,
+        pub always_float: Option<bool>,
+        pub disable_tile_drag: DisableTileDrag,
+        pub scratchpad_tag_label: Option<String>,
+        pub overlay_tag_label: Option<String>,
+        pub on_reload_cmd: Option<String>,
+        pub mousebinds: Vec<Mousebind>,
     }
 
     impl Config for TestConfig {
@@ -110,19 +213,56 @@ pub(crate) mod tests {
         fn mousekey(&self) -> Vec<String> {
             vec!["Mod4".to_owned()]
         }
+        fn mousebinds(&self) -> Vec<Mousebind> {
+            self.mousebinds.clone()
+        }
         fn create_list_of_scratchpads(&self) -> Vec<ScratchPad> {
             vec![]
         }
+        fn scratchpad_tag_label(&self) -> String {
+            self.scratchpad_tag_label
+                .clone()
+                .unwrap_or_else(|| "NSP".to_string())
+        }
+        fn overlay_tag_label(&self) -> String {
+            self.overlay_tag_label
+                .clone()
+                .unwrap_or_else(|| "OVERLAY".to_string())
+        }
         fn layouts(&self) -> Vec<Layout> {
             self.layouts.clone()
         }
         fn layout_mode(&self) -> LayoutMode {
-            LayoutMode::Workspace
+            self.layout_mode
+        }
+        fn default_layouts(&self) -> Option<HashMap<String, Layout>> {
+            self.default_layouts.clone()
+        }
+        fn max_tiled_windows(&self) -> Option<HashMap<String, usize>> {
+            self.max_tiled_windows.clone()
+        }
+        fn on_tag_focus_cmd(&self) -> Option<HashMap<String, String>> {
+            self.on_tag_focus_cmd.clone()
+        }
+        fn on_tag_unfocus_cmd(&self) -> Option<HashMap<String, String>> {
+            self.on_tag_unfocus_cmd.clone()
+        }
+        fn remember_app_tags(&self) -> Option<usize> {
+            self.remember_app_tags
+        }
+        fn transient_float(&self) -> Option<HashMap<String, bool>> {
+            self.transient_float.clone()
+        }
+        fn reset_layout_on_empty(&self) -> bool {
+            self.reset_layout_on_empty
         }
 
         fn insert_behavior(&self) -> InsertBehavior {
             self.insert_behavior
         }
+        fn floating_placement(&self) -> FloatingPlacement {
+            self.floating_placement
+        }
 
         fn single_window_border(&self) -> bool {
             self.single_window_border
@@ -131,6 +271,15 @@ pub(crate) mod tests {
         fn focus_new_windows(&self) -> bool {
             false
         }
+        fn prevent_focus_stealing(&self) -> bool {
+            self.prevent_focus_stealing
+        }
+        fn focus_on_empty_tag(&self) -> EmptyTagFocus {
+            self.focus_on_empty_tag
+        }
+        fn focus_after_last_close(&self) -> FocusAfterLastClose {
+            self.focus_after_last_close
+        }
         fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
         where
             SERVER: DisplayServer,
@@ -144,7 +293,7 @@ pub(crate) mod tests {
             }
         }
         fn always_float(&self) -> bool {
-            false
+            self.always_float.unwrap_or(false)
         }
         fn default_width(&self) -> i32 {
             1000
@@ -155,6 +304,12 @@ pub(crate) mod tests {
         fn border_width(&self) -> i32 {
             self.border_width
         }
+        fn floating_border_width(&self) -> i32 {
+            self.floating_border_width.unwrap_or(self.border_width)
+        }
+        fn tiled_border_width(&self) -> i32 {
+            self.tiled_border_width.unwrap_or(self.border_width)
+        }
         fn margin(&self) -> Margins {
             Margins::new(0)
         }
@@ -179,22 +334,28 @@ pub(crate) mod tests {
         fn on_new_window_cmd(&self) -> Option<String> {
             None
         }
+        fn on_reload_cmd(&self) -> Option<String> {
+            self.on_reload_cmd.clone()
+        }
         fn get_list_of_gutters(&self) -> Vec<Gutter> {
             Default::default()
         }
         fn max_window_width(&self) -> Option<Size> {
             None
         }
-        fn disable_tile_drag(&self) -> bool {
-            false
+        fn disable_tile_drag(&self) -> DisableTileDrag {
+            self.disable_tile_drag.clone()
         }
         fn disable_window_snap(&self) -> bool {
             false
         }
+        fn constrain_floating_to_workspace(&self) -> bool {
+            self.constrain_floating_to_workspace
+        }
         fn save_state(&self, _state: &State) {
             unimplemented!()
         }
-        fn load_state(&self, _state: &mut State) {
+        fn load_state(&self, _state: &mut State) -> bool {
             unimplemented!()
         }
         fn setup_predefined_window(&self, _: &mut State, window: &mut Window) -> bool {
@@ -208,6 +369,24 @@ pub(crate) mod tests {
         fn sloppy_mouse_follows_focus(&self) -> bool {
             true
         }
+        fn follow_modal_dialogs(&self) -> bool {
+            self.follow_modal_dialogs
+        }
+        fn float_on_cursor_monitor(&self) -> bool {
+            self.float_on_cursor_monitor
+        }
+        fn inherit_floating_from_focused(&self) -> bool {
+            self.inherit_floating_from_focused
+        }
+        fn keep_fullscreen_on_new_window(&self) -> bool {
+            self.keep_fullscreen_on_new_window
+        }
+        fn respect_dock_struts(&self) -> bool {
+            !self.ignore_dock_struts
+        }
+        fn docks_on_top(&self) -> bool {
+            self.docks_on_top
+        }
     }
 
     #[test]
@@ -0,0 +1,11 @@
+use crate::utils::modmask_lookup::{Button, ModMask};
+use crate::Command;
+use serde::{Deserialize, Serialize};
+
+/// A mouse button which runs a command when pressed with the modifiers held.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Mousebind {
+    pub modmask: ModMask,
+    pub button: Button,
+    pub command: Command,
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::layouts::Layout;
+
+/// Where dragging tiled windows around is disabled.
+/// Either `true`/`false` for every layout, or a list of layouts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum DisableTileDrag {
+    All(bool),
+    Layouts(Vec<Layout>),
+}
+
+impl DisableTileDrag {
+    /// Returns true if tiled windows can't be dragged in the given layout.
+    #[must_use]
+    pub fn is_disabled_for(&self, layout: &Layout) -> bool {
+        match self {
+            Self::All(disabled) => *disabled,
+            Self::Layouts(layouts) => layouts.iter().any(|l| l.is_same_kind(layout)),
+        }
+    }
+}
+
+impl Default for DisableTileDrag {
+    fn default() -> Self {
+        Self::All(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_be_a_bool_or_a_list_of_layouts() {
+        let all: DisableTileDrag = serde_json::from_str("true").unwrap();
+        assert_eq!(all, DisableTileDrag::All(true));
+        let layouts: DisableTileDrag = serde_json::from_str(r#"["Monocle"]"#).unwrap();
+        assert_eq!(layouts, DisableTileDrag::Layouts(vec![Layout::Monocle]));
+    }
+}
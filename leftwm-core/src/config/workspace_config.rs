@@ -3,13 +3,127 @@ use serde::{Deserialize, Serialize};
 use crate::layouts::Layout;
 use crate::models::Size;
 
+/// A workspace defined in the config.
+///
+/// Any of `x`, `y`, `height` and `width` may be set to `"auto"` (or left out),
+/// in which case it is taken from the detected screen geometry.
 #[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq)]
 pub struct Workspace {
-    pub x: i32,
-    pub y: i32,
-    pub height: i32,
-    pub width: i32,
+    #[serde(default, with = "auto_geometry")]
+    pub x: Option<i32>,
+    #[serde(default, with = "auto_geometry")]
+    pub y: Option<i32>,
+    #[serde(default, with = "auto_geometry")]
+    pub height: Option<i32>,
+    #[serde(default, with = "auto_geometry")]
+    pub width: Option<i32>,
     pub id: Option<i32>,
     pub max_window_width: Option<Size>,
     pub layouts: Option<Vec<Layout>>,
+    /// Never place new windows on this workspace, even when the cursor is over it.
+    #[serde(default)]
+    pub no_new_windows: bool,
+    /// Overrides the global `always_float` for new windows on this workspace.
+    #[serde(default)]
+    pub always_float: Option<bool>,
+    /// The fractional scale of the output, e.g. `1.5` on a high resolution screen.
+    #[serde(default)]
+    pub scale: Option<f64>,
+    /// The tag shown on this workspace when it is created, counting from 1.
+    #[serde(default)]
+    pub default_tag: Option<usize>,
+}
+
+impl Workspace {
+    /// The configured scale, `None` if it is left out or isn't a positive number.
+    #[must_use]
+    pub fn valid_scale(&self) -> Option<f64> {
+        self.scale.filter(|scale| scale.is_finite() && *scale > 0.0)
+    }
+}
+
+/// (De)serializes a geometry value which is either a number or `"auto"`.
+mod auto_geometry {
+    use serde::de::{Error, Unexpected};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const AUTO: &str = "auto";
+
+    // The signature is dictated by `#[serde(with)]`.
+    #[allow(clippy::ref_option, clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: Serializer>(value: &Option<i32>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_i32(*value),
+            None => serializer.serialize_str(AUTO),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<i32>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Geometry {
+            Value(i32),
+            Keyword(String),
+        }
+
+        match Geometry::deserialize(deserializer)? {
+            Geometry::Value(value) => Ok(Some(value)),
+            Geometry::Keyword(keyword) if keyword == AUTO => Ok(None),
+            Geometry::Keyword(keyword) => Err(D::Error::invalid_value(
+                Unexpected::Str(&keyword),
+                &"a number or \"auto\"",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_can_be_auto_or_omitted() {
+        let workspace: Workspace =
+            serde_json::from_str(r#"{ "x": 1720, "y": "auto", "width": 1720 }"#).unwrap();
+        assert_eq!(workspace.x, Some(1720));
+        assert_eq!(workspace.y, None);
+        assert_eq!(workspace.height, None);
+        assert_eq!(workspace.width, Some(1720));
+    }
+
+    #[test]
+    fn geometry_rejects_unknown_keywords() {
+        assert!(serde_json::from_str::<Workspace>(r#"{ "x": "left" }"#).is_err());
+    }
+
+    #[test]
+    fn auto_geometry_survives_a_round_trip() {
+        let workspace = Workspace {
+            x: Some(0),
+            width: Some(1720),
+            ..Workspace::default()
+        };
+        let json = serde_json::to_string(&workspace).unwrap();
+        assert!(json.contains(r#""y":"auto""#));
+        assert_eq!(serde_json::from_str::<Workspace>(&json).unwrap(), workspace);
+    }
+
+    #[test]
+    fn only_positive_scales_are_valid() {
+        for (scale, valid) in [
+            (1.5, Some(1.5)),
+            (0.0, None),
+            (-2.0, None),
+            (f64::NAN, None),
+            (f64::INFINITY, None),
+        ] {
+            let workspace = Workspace {
+                scale: Some(scale),
+                ..Workspace::default()
+            };
+            assert_eq!(workspace.valid_scale(), valid);
+        }
+    }
 }
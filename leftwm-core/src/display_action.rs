@@ -4,6 +4,7 @@ use crate::models::WindowHandle;
 use crate::models::WindowState;
 use crate::utils::modmask_lookup::Button;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// These are responses from the Window manager.
 /// The display server should act on these actions.
@@ -63,6 +64,9 @@ pub enum DisplayAction {
     /// Used to let the WM know of the tag for a given window.
     SetWindowTag(WindowHandle, Option<TagId>),
 
+    /// Set the opacity of a window, from 0.0 (transparent) to 1.0 (opaque).
+    SetOpacity(WindowHandle, f32),
+
     /// Tell the DM to return to normal mode if it is not (ie resize a
     /// window or moving a window).
     NormalMode,
@@ -70,3 +74,110 @@ pub enum DisplayAction {
     /// Configure a xlib window.
     ConfigureXlibWindow(Window),
 }
+
+impl DisplayAction {
+    /// Returns true if executing `self` makes executing `earlier` right before it pointless.
+    fn supersedes(&self, earlier: &Self) -> bool {
+        match (earlier, self) {
+            (Self::SetWindowTag(a, _), Self::SetWindowTag(b, _))
+            | (Self::MoveToTop(a), Self::MoveToTop(b))
+            | (Self::AddedWindow(a, ..), Self::AddedWindow(b, ..)) => a == b,
+            // The state is set, not toggled, so only the last value matters.
+            (Self::SetState(a, _, state_a), Self::SetState(b, _, state_b)) => {
+                a == b && state_a == state_b
+            }
+            (Self::SetWindowOrder(..), Self::SetWindowOrder(..))
+            | (Self::SetCurrentTags(_), Self::SetCurrentTags(_))
+            | (Self::FocusWindowUnderCursor, Self::FocusWindowUnderCursor)
+            | (Self::NormalMode, Self::NormalMode) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Removes actions which are immediately superseded by the action following them, so the
+/// display server doesn't do the same work twice.
+pub(crate) fn coalesce_actions(actions: VecDeque<DisplayAction>) -> VecDeque<DisplayAction> {
+    let mut coalesced: VecDeque<DisplayAction> = VecDeque::with_capacity(actions.len());
+    for action in actions {
+        while matches!(coalesced.back(), Some(previous) if action.supersedes(previous)) {
+            coalesced.pop_back();
+        }
+        coalesced.push_back(action);
+    }
+    coalesced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle(id: i32) -> WindowHandle {
+        WindowHandle::MockHandle(id)
+    }
+
+    #[test]
+    fn consecutive_window_tags_keep_the_last_one() {
+        let actions = VecDeque::from([
+            DisplayAction::SetWindowTag(handle(1), Some(1)),
+            DisplayAction::SetWindowTag(handle(1), Some(2)),
+            DisplayAction::SetWindowTag(handle(2), Some(3)),
+        ]);
+        let actions = coalesce_actions(actions);
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            actions[0],
+            DisplayAction::SetWindowTag(h, Some(2)) if h == handle(1)
+        ));
+        assert!(matches!(
+            actions[1],
+            DisplayAction::SetWindowTag(h, Some(3)) if h == handle(2)
+        ));
+    }
+
+    #[test]
+    fn consecutive_states_of_a_window_keep_the_last_value() {
+        let actions = VecDeque::from([
+            DisplayAction::SetState(handle(1), true, WindowState::Fullscreen),
+            DisplayAction::SetState(handle(1), false, WindowState::Fullscreen),
+            DisplayAction::SetState(handle(1), true, WindowState::Sticky),
+        ]);
+        let actions = coalesce_actions(actions);
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            actions[0],
+            DisplayAction::SetState(_, false, WindowState::Fullscreen)
+        ));
+        assert!(matches!(
+            actions[1],
+            DisplayAction::SetState(_, true, WindowState::Sticky)
+        ));
+    }
+
+    #[test]
+    fn only_the_last_window_order_is_kept() {
+        let actions = VecDeque::from([
+            DisplayAction::SetWindowOrder(vec![], vec![handle(1), handle(2)]),
+            DisplayAction::SetWindowOrder(vec![], vec![handle(2), handle(1)]),
+            DisplayAction::SetWindowOrder(vec![handle(3)], vec![handle(2), handle(1)]),
+        ]);
+        let actions = coalesce_actions(actions);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            &actions[0],
+            DisplayAction::SetWindowOrder(fullscreen, _) if fullscreen == &[handle(3)]
+        ));
+    }
+
+    #[test]
+    fn actions_separated_by_others_are_not_merged() {
+        let actions = VecDeque::from([
+            DisplayAction::SetWindowTag(handle(1), Some(1)),
+            DisplayAction::MoveToTop(handle(1)),
+            DisplayAction::SetWindowTag(handle(1), Some(2)),
+            DisplayAction::KillWindow(handle(1)),
+            DisplayAction::KillWindow(handle(1)),
+        ]);
+        assert_eq!(coalesce_actions(actions).len(), 5);
+    }
+}
@@ -0,0 +1,93 @@
+//! Handler tests shared by the display servers.
+//!
+//! They only go through the `DisplayServer` trait, so every backend is checked to work with
+//! the handlers of the core the same way. Failing checks panic, like in any test.
+#![allow(clippy::missing_panics_doc)]
+use super::DisplayServer;
+use crate::config::tests::TestConfig;
+use crate::display_action::DisplayAction;
+use crate::models::{Screen, Window, WindowHandle};
+use crate::{Command, DisplayEvent, Manager};
+
+/// A display server which keeps the actions it is asked to execute, so they can be checked.
+pub trait RecordingDisplayServer: DisplayServer {
+    /// Takes the actions executed since the last call.
+    fn take_actions(&mut self) -> Vec<DisplayAction>;
+}
+
+/// Runs every shared test against the display server.
+pub fn run_all<SERVER: RecordingDisplayServer>() {
+    creating_a_window_adds_it_then_tags_it::<SERVER>();
+    destroying_the_last_window_unfocuses_it::<SERVER>();
+    going_to_another_tag_hides_the_windows::<SERVER>();
+}
+
+fn manager<SERVER: RecordingDisplayServer>() -> Manager<TestConfig, SERVER> {
+    let mut manager: Manager<TestConfig, SERVER> = Manager::new(TestConfig {
+        tags: vec!["1".to_string(), "2".to_string()],
+        ..TestConfig::default()
+    });
+    manager.process_events(vec![DisplayEvent::ScreenCreate(Screen::default())]);
+    manager.display_server.take_actions();
+    manager
+}
+
+fn window_create(id: i32) -> DisplayEvent {
+    DisplayEvent::WindowCreate(
+        Window::new(WindowHandle::MockHandle(id), None, None),
+        -1,
+        -1,
+    )
+}
+
+pub fn creating_a_window_adds_it_then_tags_it<SERVER: RecordingDisplayServer>() {
+    let mut manager = manager::<SERVER>();
+    manager.process_events(vec![window_create(1)]);
+
+    let handle = WindowHandle::MockHandle(1);
+    let actions = manager.display_server.take_actions();
+    assert_eq!(actions.len(), 4, "{actions:?}");
+    assert!(matches!(actions[0], DisplayAction::AddedWindow(h, false, _) if h == handle));
+    assert!(matches!(actions[1], DisplayAction::SetWindowTag(h, Some(1)) if h == handle));
+    assert!(matches!(&actions[2], DisplayAction::SetWindowOrder(_, order) if order == &[handle]));
+    assert!(matches!(
+        &actions[3],
+        DisplayAction::WindowTakeFocus { window, .. } if window.handle == handle
+    ));
+}
+
+pub fn destroying_the_last_window_unfocuses_it<SERVER: RecordingDisplayServer>() {
+    let mut manager = manager::<SERVER>();
+    manager.process_events(vec![window_create(1), window_create(2)]);
+    manager.display_server.take_actions();
+
+    // Destroying a window without the focus needs nothing from the display server.
+    let unfocused = WindowHandle::MockHandle(2);
+    manager.process_events(vec![DisplayEvent::WindowDestroy(unfocused)]);
+    assert!(manager.display_server.take_actions().is_empty());
+
+    let handle = WindowHandle::MockHandle(1);
+    manager.process_events(vec![DisplayEvent::WindowDestroy(handle)]);
+    let actions = manager.display_server.take_actions();
+    assert_eq!(actions.len(), 1, "{actions:?}");
+    assert!(matches!(actions[0], DisplayAction::Unfocus(Some(h), false) if h == handle));
+    assert!(manager.state.windows.is_empty());
+}
+
+pub fn going_to_another_tag_hides_the_windows<SERVER: RecordingDisplayServer>() {
+    let mut manager = manager::<SERVER>();
+    manager.process_events(vec![window_create(1)]);
+    manager.display_server.take_actions();
+
+    let go_to = |tag| DisplayEvent::SendCommand(Command::GoToTag { tag, swap: false });
+    manager.process_events(vec![go_to(2)]);
+    assert!(!manager.state.windows[0].visible());
+    assert!(manager
+        .display_server
+        .take_actions()
+        .iter()
+        .any(|a| matches!(a, DisplayAction::Unfocus(Some(_), _))));
+
+    manager.process_events(vec![go_to(1)]);
+    assert!(manager.state.windows[0].visible());
+}
@@ -0,0 +1,69 @@
+use super::Config;
+use super::DisplayEvent;
+use super::DisplayServer;
+use crate::display_action::DisplayAction;
+use futures::future;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Display server without a display, for tests and CI.
+/// Events are taken from `events` and every action it is asked to execute is kept in `actions`.
+#[derive(Default)]
+pub struct HeadlessDisplayServer {
+    pub events: Vec<DisplayEvent>,
+    pub actions: Vec<DisplayAction>,
+}
+
+impl DisplayServer for HeadlessDisplayServer {
+    fn new(_: &impl Config) -> Self {
+        Self::default()
+    }
+
+    fn get_next_events(&mut self) -> Vec<DisplayEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn execute_action(&mut self, act: DisplayAction) -> Option<DisplayEvent> {
+        self.actions.push(act);
+        None
+    }
+
+    // There is nothing to read from, so this never becomes readable.
+    fn wait_readable(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(future::pending())
+    }
+
+    fn flush(&self) {}
+
+    fn generate_verify_focus_event(&self) -> Option<DisplayEvent> {
+        None
+    }
+}
+
+#[cfg(any(test, feature = "test-suite"))]
+impl super::handler_tests::RecordingDisplayServer for HeadlessDisplayServer {
+    fn take_actions(&mut self) -> Vec<DisplayAction> {
+        std::mem::take(&mut self.actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::handler_tests;
+    use super::*;
+
+    #[test]
+    fn creating_a_window_adds_it_then_tags_it() {
+        handler_tests::creating_a_window_adds_it_then_tags_it::<HeadlessDisplayServer>();
+    }
+
+    #[test]
+    fn destroying_the_last_window_unfocuses_it() {
+        handler_tests::destroying_the_last_window_unfocuses_it::<HeadlessDisplayServer>();
+    }
+
+    #[test]
+    fn going_to_another_tag_hides_the_windows() {
+        handler_tests::going_to_another_tag_hides_the_windows::<HeadlessDisplayServer>();
+    }
+}
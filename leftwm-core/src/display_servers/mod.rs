@@ -1,3 +1,6 @@
+#[cfg(any(test, feature = "test-suite"))]
+pub mod handler_tests;
+mod headless_display_server;
 #[cfg(test)]
 mod mock_display_server;
 
@@ -11,6 +14,7 @@ use crate::DisplayEvent;
 use futures::prelude::*;
 use std::pin::Pin;
 
+pub use self::headless_display_server::HeadlessDisplayServer;
 #[cfg(test)]
 pub use self::mock_display_server::MockDisplayServer;
 
@@ -12,6 +12,8 @@ pub enum LeftError {
     // TODO move Nanny to bin
     #[error("XDG error: {0}")]
     XdgBaseDirError(#[from] xdg::BaseDirectoriesError),
+    #[error("Could not parse command: {0}")]
+    CommandParse(String),
     #[error("Stream error")]
     StreamError,
 }
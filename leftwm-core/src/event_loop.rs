@@ -1,3 +1,5 @@
+use crate::display_action::coalesce_actions;
+use crate::models::TAG_FOCUS_DEBOUNCE_MS;
 use crate::{child_process::Nanny, config::Config};
 use crate::{
     Command, CommandPipe, DisplayEvent, DisplayServer, Manager, Mode, StateSocket, Window,
@@ -46,6 +48,7 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         while self.should_keep_running(&mut state_socket).await {
             self.update_manager_state(&mut state_socket).await;
             self.display_server.flush();
+            self.state.expire_launches();
 
             let response: EventResponse = tokio::select! {
                 _ = self.display_server.wait_readable(), if event_buffer.is_empty() => {
@@ -61,6 +64,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                         self.refresh_focus(&mut event_buffer);
                         continue;
                     }
+                // The tag focus commands wait for the focused tag to settle.
+                () = timeout(TAG_FOCUS_DEBOUNCE_MS), if event_buffer.is_empty() && self.tag_focus_change.is_some() => {
+                    self.run_tag_focus_cmds();
+                    continue;
+                }
                 Some(cmd) = command_pipe.read_command(), if event_buffer.is_empty() => self.execute_command(&cmd),
                 else => self.execute_display_events(&mut event_buffer),
             };
@@ -71,11 +79,17 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             };
 
             self.execute_actions(&mut event_buffer);
+            self.run_tag_focus_cmds();
 
             // We need to run once through all of the loop to properly initialize the state
             // before we can restore the previous state
             after_first_loop.call_once(|| {
-                self.config.load_state(&mut self.state);
+                if self.config.load_state(&mut self.state) {
+                    // The restored tag kept the focus through the reload.
+                    self.cmd_focused_tag = self.state.focus_manager.tag(0);
+                    self.tag_focus_change = None;
+                    self.run_reload_cmd();
+                }
             });
 
             if self.reap_requested.swap(false, Ordering::SeqCst) {
@@ -115,6 +129,17 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
     }
 
+    /// Handles the events and hands the resulting actions to the display server, like the event
+    /// loop does, until no more events are left.
+    pub fn process_events(&mut self, mut events: Vec<DisplayEvent>) {
+        while !events.is_empty() {
+            if self.execute_display_events(&mut events) == EventResponse::DisplayRefreshNeeded {
+                self.refresh_display();
+            }
+            self.execute_actions(&mut events);
+        }
+    }
+
     fn refresh_display(&mut self) {
         self.update_windows();
 
@@ -154,6 +179,7 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 
     // Perform any actions requested by the handler.
     fn execute_actions(&mut self, event_buffer: &mut Vec<DisplayEvent>) {
+        self.state.actions = coalesce_actions(std::mem::take(&mut self.state.actions));
         while !self.state.actions.is_empty() {
             if let Some(act) = self.state.actions.pop_front() {
                 if let Some(event) = self.display_server.execute_action(act) {
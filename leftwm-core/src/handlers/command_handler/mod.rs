@@ -5,12 +5,15 @@ mod scratchpad_handler;
 // details of the scratchpad handling code
 pub use scratchpad_handler::{Direction, ReleaseScratchPadOption};
 
+use super::window_handler::{insert_by_behavior, is_scratchpad};
 use super::*;
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-use crate::models::{TagId, WindowState};
-use crate::state::State;
+use crate::models::dto::{FocusedWindow, WorkspaceLayout};
+use crate::models::{FocusDirection, Side, TagId, Tags, WindowState};
+use crate::state::{Launch, State};
+use crate::utils::child_process;
 use crate::utils::helpers;
 use crate::utils::helpers::relative_find;
 use crate::{config::Config, models::FocusBehaviour};
@@ -26,25 +29,46 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
      *  */
     /// Processes a command and invokes the associated function.
     pub fn command_handler(&mut self, command: &Command) -> bool {
-        process_internal(self, command).unwrap_or(false)
+        let focused = self.state.focus_manager.window_history.front().copied();
+        let changed = process_internal(self, command).unwrap_or(false);
+        let hidden = self
+            .hide_scratchpad_on_focus_loss(focused.flatten())
+            .unwrap_or(false);
+        changed || hidden
     }
 }
 
+/// Runs a command against the manager, like a keybind or the command pipe would.
+/// Returns true if the display needs to be refreshed.
+#[allow(clippy::needless_pass_by_value)]
+pub fn execute_command<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    command: Command,
+) -> bool {
+    manager.command_handler(&command)
+}
+
 macro_rules! move_focus_common_vars {
     ($func:ident ($state:expr $(, $arg:expr )* $(,)? )) => {{
         let handle = $state.focus_manager.window(&$state.windows)?.handle;
         let tag_id = $state.focus_manager.tag(0)?;
         let tag = $state.tags.get(tag_id)?;
-        let layout = Some(tag.layout);
+        // With the stack hidden only the main window is shown, like in `Monocle`.
+        let layout = Some(if tag.stack_hidden {
+            Layout::Monocle
+        } else {
+            tag.layout
+        });
 
         let for_active_workspace =
-            |x: &Window| -> bool { x.tag == Some(tag_id) && x.is_managed() };
+            |x: &Window| -> bool { x.has_tag(&tag_id) && x.is_managed() };
 
         let to_reorder = helpers::vec_extract(&mut $state.windows, for_active_workspace);
         $func($state, handle, layout, to_reorder, $($arg),*)
     }};
 }
 
+#[allow(clippy::too_many_lines)]
 fn process_internal<C: Config, SERVER: DisplayServer>(
     manager: &mut Manager<C, SERVER>,
     command: &Command,
@@ -65,25 +89,48 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::PrevScratchPadWindow { scratchpad } => {
             scratchpad_handler::cycle_scratchpad_window(manager, scratchpad, Direction::Backward)
         }
+        Command::SaveScratchPadGeometry { name } => {
+            scratchpad_handler::save_scratchpad_geometry(state, name)
+        }
 
         Command::ToggleFullScreen => toggle_state(state, WindowState::Fullscreen),
         Command::ToggleSticky => toggle_state(state, WindowState::Sticky),
+        Command::ToggleMaximize => toggle_maximize(state),
 
         Command::SendWindowToTag { window, tag } => move_to_tag(*window, *tag, manager),
+        Command::MoveWindowToTagAndFollow { tag, swap } => {
+            move_to_tag_and_follow(manager, *tag, *swap)
+        }
+        Command::ToggleWindowTag { tag } => toggle_window_tag(state, *tag),
+        Command::SetWindowTags { tags } => set_window_tags(state, tags),
+        Command::MarkWindow { key } => mark_window(state, *key),
+        Command::GotoMark { key } => goto_mark(state, *key),
         Command::MoveWindowToNextTag { follow } => move_to_tag_relative(manager, *follow, 1),
         Command::MoveWindowToPreviousTag { follow } => move_to_tag_relative(manager, *follow, -1),
         Command::MoveWindowToLastWorkspace => move_to_last_workspace(state),
         Command::MoveWindowToNextWorkspace => move_window_to_workspace_change(manager, 1),
         Command::MoveWindowToPreviousWorkspace => move_window_to_workspace_change(manager, -1),
+        Command::MoveWindowToMonitor { dir } => move_window_to_monitor(manager, *dir),
         Command::MoveWindowUp => move_focus_common_vars!(move_window_change(state, -1)),
         Command::MoveWindowDown => move_focus_common_vars!(move_window_change(state, 1)),
         Command::MoveWindowTop { swap } => move_focus_common_vars!(move_window_top(state, *swap)),
+        Command::PromoteToMaster => promote_to_master(state),
+        Command::SwapWindowDirection { dir } => swap_window_direction(state, *dir),
+        Command::SplitFocusedWith { dir } => split_focused_with(state, *dir),
 
         Command::GoToTag { tag, swap } => goto_tag(state, *tag, *swap),
-        Command::ReturnToLastTag => return_to_last_tag(state),
+        Command::ReturnToLastTag { swap: true } => return_to_last_tag(state),
+        Command::ReturnToLastTag { swap: false } => {
+            let current_tag = state.focus_manager.tag(0)?;
+            goto_tag(state, current_tag, false)
+        }
 
         Command::CloseWindow => close_window(state),
+        Command::CloseAllOnTag => close_all_on_tag(state),
         Command::SwapScreens => swap_tags(state),
+        Command::SwapMonitorTags { a, b } => swap_monitor_tags(state, *a, *b),
+        Command::MoveTag { from, to } => Some(move_tag(manager, *from, *to)),
+        Command::SwapTagContents { a, b } => Some(swap_tag_contents(state, *a, *b)),
         Command::NextLayout => next_layout(state),
         Command::PreviousLayout => previous_layout(state),
 
@@ -92,6 +139,8 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::FloatingToTile => floating_to_tile(state),
         Command::TileToFloating => tile_to_floating(state),
         Command::ToggleFloating => toggle_floating(state),
+        Command::TileAllFloating => tile_all_floating(state),
+        Command::ToggleIgnoreInLayout => toggle_ignore_in_layout(state),
 
         Command::FocusNextTag => focus_tag_change(state, 1),
         Command::FocusPreviousTag => focus_tag_change(state, -1),
@@ -99,45 +148,84 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::FocusWindowUp => move_focus_common_vars!(focus_window_change(state, -1)),
         Command::FocusWindowDown => move_focus_common_vars!(focus_window_change(state, 1)),
         Command::FocusWindowTop { swap } => focus_window_top(state, *swap),
+        Command::FocusBySize { largest } => focus_by_size(state, *largest),
+        Command::FocusWindowDirection { dir } => focus_window_direction(state, *dir),
+        Command::FocusUrgent => focus_urgent(state),
+        Command::FocusNextOfClass => focus_next_of_class(state),
+        Command::FocusNextTiled => focus_tiled_change(state, 1),
+        Command::FocusPrevTiled => focus_tiled_change(state, -1),
         Command::FocusWorkspaceNext => focus_workspace_change(state, 1),
         Command::FocusWorkspacePrevious => focus_workspace_change(state, -1),
 
-        Command::SoftReload => {
-            // Make sure the currently focused window is saved for the tag.
-            if let Some((handle, Some(tag))) = state
-                .focus_manager
-                .window(&state.windows)
-                .map(|w| (w.handle, w.tag))
-            {
-                let old_handle = state
-                    .focus_manager
-                    .tags_last_window
-                    .entry(tag)
-                    .or_insert(handle);
-                *old_handle = handle;
-            }
-            manager.config.save_state(&manager.state);
-            manager.hard_reload();
-            None
-        }
+        Command::SoftReload => soft_reload(manager),
         Command::HardReload => {
             manager.hard_reload();
             None
         }
 
         Command::RotateTag => rotate_tag(state),
+        Command::ToggleStackVisibility => toggle_stack_visibility(state),
+        Command::SetMainPosition { side } => set_main_position(state, side.clone()),
 
         Command::IncreaseMainWidth(delta) => change_main_width(state, *delta, 1),
         Command::DecreaseMainWidth(delta) => change_main_width(state, *delta, -1),
+        Command::ChangeMainWidth { delta } => step_main_width(state, *delta),
+        Command::ResetMainWidth => reset_main_width(state),
+        Command::IncreaseBorder(delta) => Some(change_border_width(manager, *delta)),
+        Command::DecreaseBorder(delta) => Some(change_border_width(manager, -*delta)),
+        Command::IncreaseGridColumns => change_grid_columns(state, 1),
+        Command::DecreaseGridColumns => change_grid_columns(state, -1),
+        Command::SetGridColumns(columns) => set_grid_columns(state, *columns),
+        Command::ToggleOverview => state.toggle_overview(),
+        Command::CycleInsertBehavior => {
+            cycle_insert_behavior(state);
+            Some(false)
+        }
+        Command::ToggleOverlay => state.toggle_overlay(),
+        Command::ToggleWindowOverlay => state.toggle_window_overlay(),
         Command::SetMarginMultiplier(multiplier) => set_margin_multiplier(state, *multiplier),
+        Command::ToggleGaps => toggle_gaps(state),
         Command::SendWorkspaceToTag(ws_index, tag_index) => {
             Some(send_workspace_to_tag(state, *ws_index, *tag_index))
         }
         Command::CloseAllOtherWindows => close_all_other_windows(state),
+        Command::QueryFocusedWindow => query_focused_window(state),
+        Command::DumpLayout => {
+            dump_layout(state);
+            Some(false)
+        }
+        Command::Execute(command) => execute(manager, command),
+        Command::Spawn { command, tag } => spawn(manager, command, *tag),
+        Command::Chain(commands) => Some(chain(manager, commands)),
         Command::Other(cmd) => Some(C::command_handler(cmd, manager)),
     }
 }
 
+fn cycle_insert_behavior(state: &mut State) {
+    state.insert_behavior = state.insert_behavior.next();
+    tracing::info!("New windows are inserted: {:?}", state.insert_behavior);
+}
+
+fn soft_reload<C: Config, SERVER: DisplayServer>(manager: &mut Manager<C, SERVER>) -> Option<bool> {
+    let state = &mut manager.state;
+    // Make sure the currently focused window is saved for the tag.
+    if let Some((handle, Some(tag))) = state
+        .focus_manager
+        .window(&state.windows)
+        .map(|w| (w.handle, w.tag))
+    {
+        let old_handle = state
+            .focus_manager
+            .tags_last_window
+            .entry(tag)
+            .or_insert(handle);
+        *old_handle = handle;
+    }
+    manager.config.save_state(&manager.state);
+    manager.hard_reload();
+    None
+}
+
 fn toggle_state(state: &mut State, window_state: WindowState) -> Option<bool> {
     let window = state.focus_manager.window(&state.windows)?;
     let handle = window.handle;
@@ -151,6 +239,45 @@ fn toggle_state(state: &mut State, window_state: WindowState) -> Option<bool> {
     }
 }
 
+/// Floats the focused window over the usable area of its workspace, keeping the layout of the
+/// other windows. Toggling it again restores its floating state and position.
+fn toggle_maximize(state: &mut State) -> Option<bool> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let (area, usable) = (workspace.xyhw, workspace.usable_xyhw());
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let tiled: Vec<WindowHandle> = state
+        .windows
+        .iter()
+        .filter(|w| !w.floating() && workspace.is_managed(w))
+        .map(|w| w.handle)
+        .collect();
+    let index = state.windows.iter().position(|w| w.handle == handle)?;
+    let window = &mut state.windows[index];
+    if let Some((floating, offsets, position)) = window.maximized_from.take() {
+        window.set_floating(floating);
+        window.set_floating_offsets(offsets);
+        if !floating {
+            // Put the window back in its place in the layout.
+            let window = state.windows.remove(index);
+            let at = tiled
+                .get(position)
+                .and_then(|next| state.windows.iter().position(|w| &w.handle == next))
+                .unwrap_or(state.windows.len());
+            state.windows.insert(at, window);
+        }
+    } else {
+        let position = tiled.iter().position(|h| h == &handle).unwrap_or_default();
+        window.maximized_from = Some((window.floating(), window.get_floating_offsets(), position));
+        window.set_floating(true);
+        // Floating windows are placed relative to their workspace.
+        window.normal = area;
+        window.set_floating_exact(usable);
+    }
+    state.sort_windows();
+    state.handle_window_focus(&handle);
+    Some(true)
+}
+
 fn move_to_tag<C: Config, SERVER: DisplayServer>(
     window: Option<WindowHandle>,
     tag_num: TagId,
@@ -189,9 +316,7 @@ fn move_to_tag<C: Config, SERVER: DisplayServer>(
     manager.state.actions.push_back(act);
 
     manager.state.sort_windows();
-    manager
-        .state
-        .handle_single_border(manager.config.border_width());
+    manager.state.update_borders(&manager.config);
     if handle_focus {
         if let Some(new_handle) = new_handle {
             manager.state.focus_window(&new_handle);
@@ -204,6 +329,32 @@ fn move_to_tag<C: Config, SERVER: DisplayServer>(
     Some(true)
 }
 
+/// Sends the focused window to a tag, then shows the tag and focuses the window.
+fn move_to_tag_and_follow<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    tag: TagId,
+    swap: bool,
+) -> Option<bool> {
+    let current_tag = manager.state.focus_manager.tag(0).unwrap_or_default();
+    let tag = if swap && current_tag == tag {
+        manager.state.focus_manager.tag(1).unwrap_or_default()
+    } else {
+        tag
+    };
+    if tag == current_tag {
+        return Some(false);
+    }
+    let handle = manager
+        .state
+        .focus_manager
+        .window(&manager.state.windows)?
+        .handle;
+    move_to_tag(Some(handle), tag, manager)?;
+    manager.state.goto_tag_handler(tag)?;
+    manager.state.handle_window_focus(&handle);
+    Some(true)
+}
+
 /// Move currently focused window to tag relative to current tag
 ///
 /// Conditionally allow focus to follow the window to the target tag
@@ -244,6 +395,46 @@ fn move_window_to_workspace_change<C: Config, SERVER: DisplayServer>(
     move_to_tag(None, tag_num, manager)
 }
 
+/// Moves the focused window to the workspace next to the focused one in a direction.
+/// Floating windows keep floating at the same position relative to the workspace.
+fn move_window_to_monitor<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    dir: FocusDirection,
+) -> Option<bool> {
+    let state = &manager.state;
+    let current = state.focus_manager.workspace(&state.workspaces)?;
+    let target = closest_in_direction(
+        current.xyhw.center(),
+        state
+            .workspaces
+            .iter()
+            .filter(|ws| ws.id != current.id)
+            .map(|ws| (ws.xyhw.center(), ws)),
+        dir,
+    )?;
+    let tag = target.tag?;
+    let (from, to) = (current.xyhw, target.xyhw);
+    let window = state.focus_manager.window(&state.windows)?;
+    let (handle, floating) = (window.handle, window.floating());
+    let mut xyhw = window.exact_xyhw();
+
+    move_to_tag(None, tag, manager)?;
+    if floating {
+        let window = manager
+            .state
+            .windows
+            .iter_mut()
+            .find(|w| w.handle == handle)?;
+        xyhw.set_x(xyhw.x() - from.x() + to.x());
+        xyhw.set_y(xyhw.y() - from.y() + to.y());
+        window.set_floating(true);
+        window.normal = to;
+        window.set_floating_exact(xyhw);
+        manager.state.sort_windows();
+    }
+    Some(true)
+}
+
 fn goto_tag(state: &mut State, input_tag: TagId, current_tag_swap: bool) -> Option<bool> {
     let current_tag = state.focus_manager.tag(0).unwrap_or_default();
     let previous_tag = state.focus_manager.tag(1).unwrap_or_default();
@@ -255,6 +446,8 @@ fn goto_tag(state: &mut State, input_tag: TagId, current_tag_swap: bool) -> Opti
     state.goto_tag_handler(destination_tag)
 }
 
+/// Goes to the previously focused tag, calling it twice toggles between the two most recent tags.
+/// Unlike `GoToTag`, this doesn't depend on `disable_current_tag_swap`.
 fn return_to_last_tag(state: &mut State) -> Option<bool> {
     let previous_tag = state.focus_manager.tag(1).unwrap_or_default();
     state.goto_tag_handler(previous_tag)
@@ -279,6 +472,83 @@ fn focus_window(state: &mut State, param: &str) -> Option<bool> {
     }
 }
 
+/// Focuses the window which has been urgent the longest, showing its tag if needed.
+fn focus_urgent(state: &mut State) -> Option<bool> {
+    let is_urgent = |handle: &WindowHandle| {
+        state
+            .windows
+            .iter()
+            .any(|w| &w.handle == handle && w.urgent)
+    };
+    let window = state
+        .urgent_windows
+        .iter()
+        .find(|h| is_urgent(h))
+        .and_then(|h| state.windows.iter().find(|w| &w.handle == h))
+        // Windows which were already urgent before a reload aren't in the list.
+        .or_else(|| state.windows.iter().find(|w| w.urgent))?;
+    let (handle, tag) = (window.handle, window.tag);
+
+    if let Some(tag) = tag {
+        if !state.workspaces.iter().any(|ws| ws.has_tag(&tag)) {
+            state.goto_tag_handler(tag)?;
+        }
+    }
+    state.handle_window_focus(&handle);
+    Some(true)
+}
+
+/// Focuses the next window with the class of the focused window, going through the tags in
+/// order. The tag of the window is shown if it isn't already.
+fn focus_next_of_class(state: &mut State) -> Option<bool> {
+    let focused = state.focus_manager.window(&state.windows)?;
+    let class = focused.res_class.as_ref()?;
+    let len_normal = state.tags.len_normal();
+    let mut windows: Vec<&Window> = state
+        .windows
+        .iter()
+        .filter(|w| w.is_managed() && w.res_class.as_ref() == Some(class))
+        .filter(|w| matches!(w.tag, Some(tag) if tag <= len_normal))
+        .collect();
+    windows.sort_by_key(|w| w.tag);
+    let index = windows.iter().position(|w| w.handle == focused.handle)?;
+    let next = windows[(index + 1) % windows.len()];
+    if next.handle == focused.handle {
+        return Some(false);
+    }
+    let handle = next.handle;
+    if !state.workspaces.iter().any(|ws| ws.is_displaying(next)) {
+        let tag = next.tag?;
+        state.goto_tag_handler(tag)?;
+    }
+    state.handle_window_focus(&handle);
+    Some(true)
+}
+
+/// Focuses the tiled window `delta` places from the focused one on the focused workspace.
+/// From a floating window the first or last tiled window is focused.
+fn focus_tiled_change(state: &mut State, delta: i32) -> Option<bool> {
+    let focused = state.focus_manager.window(&state.windows).map(|w| w.handle);
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let tiled: Vec<&Window> = state
+        .windows_on_workspace(workspace)
+        .filter(|w| !w.floating() && (!w.skip_focus_cycle || Some(w.handle) == focused))
+        .collect();
+    let next = relative_find(&tiled, |w| Some(w.handle) == focused, delta, true).or_else(|| {
+        if delta < 0 {
+            tiled.last()
+        } else {
+            tiled.first()
+        }
+    })?;
+    if Some(next.handle) == focused {
+        return Some(false);
+    }
+    let handle = next.handle;
+    state.handle_window_focus(&handle);
+    Some(true)
+}
+
 fn focus_window_by_class(state: &mut State, window_class: &str) -> Option<bool> {
     let is_target = |w: &Window| -> bool {
         w.res_name
@@ -362,17 +632,7 @@ fn swap_tags(state: &mut State) -> Option<bool> {
     if state.workspaces.len() >= 2 && state.focus_manager.workspace_history.len() >= 2 {
         let hist_a = *state.focus_manager.workspace_history.get(0)?;
         let hist_b = *state.focus_manager.workspace_history.get(1)?;
-        //Update workspace tags
-        let mut temp = None;
-        std::mem::swap(&mut state.workspaces.get_mut(hist_a)?.tag, &mut temp);
-        std::mem::swap(&mut state.workspaces.get_mut(hist_b)?.tag, &mut temp);
-        std::mem::swap(&mut state.workspaces.get_mut(hist_a)?.tag, &mut temp);
-        // Update dock tags and layouts.
-        state.update_static();
-        state
-            .layout_manager
-            .update_layouts(&mut state.workspaces, state.tags.all_mut());
-
+        swap_workspace_tags(state, hist_a, hist_b)?;
         return Some(true);
     }
     if state.workspaces.len() == 1 {
@@ -382,15 +642,293 @@ fn swap_tags(state: &mut State) -> Option<bool> {
     None
 }
 
+/// Exchanges the tags shown by two workspaces, the focus stays on the focused tag.
+fn swap_monitor_tags(state: &mut State, a: usize, b: usize) -> Option<bool> {
+    let tag = state.focus_manager.tag(0);
+    if !swap_workspace_tags(state, a, b)? {
+        return Some(false);
+    }
+    if let Some(handle) = state.focus_manager.window(&state.windows).map(|w| w.handle) {
+        state.focus_window(&handle);
+    } else {
+        let workspace = state.workspaces.iter().find(|ws| ws.tag == tag)?.clone();
+        state.focus_workspace(&workspace);
+    }
+    Some(true)
+}
+
+/// Exchanges the tags shown by two workspaces, the windows follow their tags.
+/// Returns false if both workspaces show the same tag.
+fn swap_workspace_tags(state: &mut State, a: usize, b: usize) -> Option<bool> {
+    let tag_a = state.workspaces.get(a)?.tag;
+    let tag_b = state.workspaces.get(b)?.tag;
+    if tag_a == tag_b {
+        return Some(false);
+    }
+    state.workspaces[a].tag = tag_b;
+    state.workspaces[b].tag = tag_a;
+    // Update dock tags and layouts.
+    state.update_static();
+    state
+        .layout_manager
+        .update_layouts(&mut state.workspaces, state.tags.all_mut());
+    Some(true)
+}
+
+/// Launches a command, so its windows open on the tag it was launched from.
+fn execute<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    command: &str,
+) -> Option<bool> {
+    let tag = manager.state.focus_manager.tag(0)?;
+    launch(manager, command, tag)
+}
+
+/// Runs a command, so its windows open on the given tag, or the focused one.
+fn spawn<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    command: &str,
+    tag: Option<TagId>,
+) -> Option<bool> {
+    let tag = match tag {
+        Some(tag) if tag >= 1 && tag <= manager.state.tags.len_normal() => tag,
+        _ => manager.state.focus_manager.tag(0)?,
+    };
+    launch(manager, command, tag)
+}
+
+/// Launches a command with a startup id, remembering the tag its windows open on.
+fn launch<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    command: &str,
+    tag: TagId,
+) -> Option<bool> {
+    let startup_id = child_process::new_startup_id();
+    let pid =
+        child_process::exec_shell_with_startup_id(command, &startup_id, &mut manager.children)?;
+    manager
+        .state
+        .launches
+        .push(Launch::new(tag, startup_id, pid));
+    Some(false)
+}
+
+/// Writes the focused window as JSON to `focused_window.json` in the runtime directory.
+fn query_focused_window(state: &State) -> Option<bool> {
+    let path = xdg::BaseDirectories::with_prefix("leftwm")
+        .ok()?
+        .place_runtime_file("focused_window.json")
+        .ok()?;
+    if let Err(err) = std::fs::write(path, focused_window_json(state)) {
+        tracing::error!("Could not write the focused window: {}", err);
+    }
+    Some(false)
+}
+
+/// The focused window as JSON, or `null` if no window is focused.
+fn focused_window_json(state: &State) -> String {
+    serde_json::to_string(&FocusedWindow::from_state(state)).unwrap_or_else(|_| "null".to_owned())
+}
+
+/// Logs the layout and visible window geometry of every workspace, for bug reports.
+fn dump_layout(state: &State) {
+    tracing::info!("Layout dump: {}", layout_dump_json(state));
+}
+
+fn layout_dump_json(state: &State) -> String {
+    serde_json::to_string(&WorkspaceLayout::from_state(state)).unwrap_or_else(|_| "[]".to_owned())
+}
+
+/// Runs the commands in order, stopping at the first one that fails.
+fn chain<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    commands: &[Command],
+) -> bool {
+    let mut changed = false;
+    for (index, command) in commands.iter().enumerate() {
+        if let Some(result) = process_internal(manager, command) {
+            changed |= result;
+        } else {
+            tracing::error!(
+                "Stopping command chain, command {} ({:?}) failed",
+                index + 1,
+                command
+            );
+            break;
+        }
+    }
+    changed
+}
+
+/// Marks the focused window with a key, so `GotoMark` can jump back to it.
+fn mark_window(state: &mut State, key: char) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    state.marks.insert(key, handle);
+    Some(false)
+}
+
+/// Focuses the window marked with a key, switching to its tag if it isn't displayed.
+fn goto_mark(state: &mut State, key: char) -> Option<bool> {
+    let handle = *state.marks.get(&key)?;
+    let window = state.windows.iter().find(|w| w.handle == handle)?;
+    if !state.workspaces.iter().any(|ws| ws.is_displaying(window)) {
+        let tag = window.tag?;
+        state.goto_tag_handler(tag)?;
+    }
+    state.handle_window_focus(&handle);
+    Some(true)
+}
+
+/// Adds the tag to the tags of the focused window, or removes it if the window already has it.
+/// A window always keeps at least one tag.
+fn toggle_window_tag(state: &mut State, tag: TagId) -> Option<bool> {
+    if tag < 1 || tag > state.tags.len_normal() {
+        return Some(false);
+    }
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
+    if let Some(index) = window.extra_tags.iter().position(|t| *t == tag) {
+        window.extra_tags.remove(index);
+    } else if window.has_tag(&tag) {
+        // Show the window on one of its other tags instead.
+        if window.extra_tags.is_empty() {
+            return Some(false);
+        }
+        window.tag = Some(window.extra_tags.remove(0));
+    } else {
+        window.extra_tags.push(tag);
+    }
+    let act = DisplayAction::SetWindowTag(handle, window.tag);
+    state.actions.push_back(act);
+    Some(true)
+}
+
+/// Replaces the tags of the focused window, unknown tags are ignored.
+/// The window keeps its tag if it is given, otherwise it is shown on a displayed tag if possible.
+fn set_window_tags(state: &mut State, tags: &[TagId]) -> Option<bool> {
+    let mut valid: Vec<TagId> = vec![];
+    for &tag in tags {
+        if tag >= 1 && tag <= state.tags.len_normal() && !valid.contains(&tag) {
+            valid.push(tag);
+        }
+    }
+    if valid.is_empty() {
+        return Some(false);
+    }
+    let displayed: Vec<Option<TagId>> = state.workspaces.iter().map(|ws| ws.tag).collect();
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
+    let primary = window
+        .tag
+        .filter(|tag| valid.contains(tag))
+        .or_else(|| {
+            valid
+                .iter()
+                .copied()
+                .find(|t| displayed.contains(&Some(*t)))
+        })
+        .unwrap_or(valid[0]);
+    valid.retain(|t| *t != primary);
+    window.tag = Some(primary);
+    window.extra_tags = valid;
+    let act = DisplayAction::SetWindowTag(handle, window.tag);
+    state.actions.push_back(act);
+    Some(true)
+}
+
+/// Moves the tag `from` to the position of tag `to`, all references to tags follow their tag.
+fn move_tag<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    from: TagId,
+    to: TagId,
+) -> bool {
+    if from == to || !manager.state.tags.move_tag(from, to) {
+        return false;
+    }
+    manager.state.remap_tag(from, to);
+    manager.cmd_focused_tag = manager
+        .cmd_focused_tag
+        .map(|id| Tags::moved_id(id, from, to));
+    true
+}
+
+/// Moves the windows of tag `a` to tag `b` and the other way around.
+/// Sticky and scratchpad windows are left alone.
+fn swap_tag_contents(state: &mut State, a: TagId, b: TagId) -> bool {
+    let is_normal = |id: TagId| matches!(state.tags.get(id), Some(tag) if !tag.hidden);
+    if a == b || !is_normal(a) || !is_normal(b) {
+        return false;
+    }
+    let swapped = |id: TagId| match id {
+        id if id == a => b,
+        id if id == b => a,
+        id => id,
+    };
+    let handles: Vec<WindowHandle> = state
+        .windows
+        .iter()
+        .filter(|w| !w.is_sticky() && !is_scratchpad(state, w))
+        .map(|w| w.handle)
+        .collect();
+    for window in state
+        .windows
+        .iter_mut()
+        .filter(|w| handles.contains(&w.handle))
+    {
+        for tag in &mut window.extra_tags {
+            *tag = swapped(*tag);
+        }
+        let tag = window.tag.map(swapped);
+        if tag != window.tag {
+            window.tag = tag;
+            let act = DisplayAction::SetWindowTag(window.handle, tag);
+            state.actions.push_back(act);
+        }
+    }
+    let last_window = &mut state.focus_manager.tags_last_window;
+    let (last_a, last_b) = (last_window.remove(&a), last_window.remove(&b));
+    if let Some(handle) = last_a {
+        last_window.insert(b, handle);
+    }
+    if let Some(handle) = last_b {
+        last_window.insert(a, handle);
+    }
+    true
+}
+
 fn close_window(state: &mut State) -> Option<bool> {
     let window = state.focus_manager.window(&state.windows)?;
-    if window.is_managed() {
-        let act = DisplayAction::KillWindow(window.handle);
-        state.actions.push_back(act);
+    close_managed_window(state, window.handle);
+    None
+}
+
+/// Closes all windows on the focused tag, except sticky and scratchpad windows.
+fn close_all_on_tag(state: &mut State) -> Option<bool> {
+    let tag = state.focus_manager.tag(0)?;
+    let handles: Vec<WindowHandle> = state
+        .windows
+        .iter()
+        .filter(|w| w.has_tag(&tag) && !w.is_sticky() && !is_scratchpad(state, w))
+        .map(|w| w.handle)
+        .collect();
+    for handle in handles {
+        close_managed_window(state, handle);
     }
     None
 }
 
+/// Asks a managed window to close itself.
+fn close_managed_window(state: &mut State, handle: WindowHandle) {
+    let managed = state
+        .windows
+        .iter()
+        .any(|w| w.handle == handle && w.is_managed());
+    if managed {
+        let act = DisplayAction::KillWindow(handle);
+        state.actions.push_back(act);
+    }
+}
+
 fn move_to_last_workspace(state: &mut State) -> Option<bool> {
     if state.workspaces.len() >= 2 && state.focus_manager.workspace_history.len() >= 2 {
         let index = *state.focus_manager.workspace_history.get(1)?;
@@ -494,8 +1032,13 @@ fn floating_to_tile(state: &mut State) -> Option<bool> {
 }
 
 fn tile_to_floating(state: &mut State) -> Option<bool> {
-    let width = state.default_width;
-    let height = state.default_height;
+    let (width, height) = match state.focus_manager.workspace(&state.workspaces) {
+        Some(ws) => (
+            ws.scaled(state.default_width),
+            ws.scaled(state.default_height),
+        ),
+        None => (state.default_width, state.default_height),
+    };
     let window = state.focus_manager.window_mut(&mut state.windows)?;
     if window.must_float() {
         return None;
@@ -532,6 +1075,49 @@ fn toggle_floating(state: &mut State) -> Option<bool> {
     }
 }
 
+/// Floats the focused window and keeps it floating, so the layout tiles the other windows as if
+/// it wasn't there. Toggling it again tiles the window.
+fn toggle_ignore_in_layout(state: &mut State) -> Option<bool> {
+    let window = state.focus_manager.window(&state.windows)?;
+    if window.ignore_in_layout {
+        state
+            .focus_manager
+            .window_mut(&mut state.windows)?
+            .ignore_in_layout = false;
+        return floating_to_tile(state);
+    }
+    if !window.floating() {
+        tile_to_floating(state)?;
+    }
+    state
+        .focus_manager
+        .window_mut(&mut state.windows)?
+        .ignore_in_layout = true;
+    Some(true)
+}
+
+/// Tiles the floating windows on the focused workspace, except scratchpads and windows which
+/// must float. They are placed in the stack like new windows.
+fn tile_all_floating(state: &mut State) -> Option<bool> {
+    let tag = state.focus_manager.workspace(&state.workspaces)?.tag?;
+    let handles: Vec<WindowHandle> = state
+        .windows_on_tag(tag)
+        .filter(|w| w.floating() && !w.must_float() && !is_scratchpad(state, w))
+        .map(|w| w.handle)
+        .collect();
+    if handles.is_empty() {
+        return Some(false);
+    }
+    for handle in handles {
+        let index = state.windows.iter().position(|w| w.handle == handle)?;
+        let mut window = state.windows.remove(index);
+        window.set_floating(false);
+        insert_by_behavior(state, window);
+    }
+    state.sort_windows();
+    Some(true)
+}
+
 fn move_window_change(
     state: &mut State,
     mut handle: WindowHandle,
@@ -593,6 +1179,29 @@ fn move_window_top(
     Some(true)
 }
 
+/// Moves the focused tiled window in front of the other tiled windows on its workspace, making it
+/// the main window. The windows before it shift down by one instead of swapping places.
+fn promote_to_master(state: &mut State) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let index = state.windows.iter().position(|w| w.handle == handle)?;
+    if state.windows[index].floating() || !workspace.is_managed(&state.windows[index]) {
+        return None;
+    }
+    let main_index = state
+        .windows
+        .iter()
+        .position(|w| !w.floating() && workspace.is_managed(w))?;
+    if main_index == index {
+        return Some(false);
+    }
+    let window = state.windows.remove(index);
+    state.windows.insert(main_index, window);
+    state.sort_windows();
+    state.handle_window_focus(&handle);
+    Some(true)
+}
+
 fn focus_window_change(
     state: &mut State,
     mut handle: WindowHandle,
@@ -601,12 +1210,18 @@ fn focus_window_change(
     val: i32,
 ) -> Option<bool> {
     let is_handle = |x: &Window| -> bool { x.handle == handle };
+    // Windows skipping the focus cycle can only be focused directly.
+    let in_cycle = |x: &&Window| !x.skip_focus_cycle || is_handle(x);
     if layout == Some(Layout::Monocle) {
         // For Monocle we want to also move windows up/down
         // Not the best solution but results
         // in desired behaviour
-        handle = helpers::relative_find(&to_reorder, is_handle, -val, true)?.handle;
-        let _ = helpers::cycle_vec(&mut to_reorder, val);
+        let cycled: Vec<&Window> = to_reorder.iter().filter(in_cycle).collect();
+        let next = helpers::relative_find(&cycled, |w| is_handle(w), -val, true)?.handle;
+        let from = to_reorder.iter().position(|w| w.handle == next)?;
+        let to = to_reorder.iter().position(is_handle)?;
+        let _ = helpers::cycle_vec(&mut to_reorder, to as i32 - from as i32);
+        handle = next;
     } else if layout == Some(Layout::MainAndDeck) {
         let len = to_reorder.len() as i32;
         if len > 0 {
@@ -620,11 +1235,14 @@ fn focus_window_change(
                 }
                 None => len.saturating_sub(1) as usize,
             };
-            let window_group = &to_reorder[..=index];
-            handle = helpers::relative_find(window_group, is_handle, -val, true)?.handle;
+            let window_group: Vec<&Window> = to_reorder[..=index].iter().filter(in_cycle).collect();
+            handle = helpers::relative_find(&window_group, |w| is_handle(w), -val, true)?.handle;
+        }
+    } else {
+        let cycled: Vec<&Window> = to_reorder.iter().filter(in_cycle).collect();
+        if let Some(new_focused) = helpers::relative_find(&cycled, |w| is_handle(w), val, true) {
+            handle = new_focused.handle;
         }
-    } else if let Some(new_focused) = helpers::relative_find(&to_reorder, is_handle, val, true) {
-        handle = new_focused.handle;
     }
     state.windows.append(&mut to_reorder);
     state.handle_window_focus(&handle);
@@ -638,7 +1256,7 @@ fn focus_window_top(state: &mut State, swap: bool) -> Option<bool> {
     let next = state
         .windows
         .iter()
-        .find(|x| x.tag == Some(tag) && !x.floating() && x.is_managed())
+        .find(|x| x.has_tag(&tag) && !x.floating() && x.is_managed())
         .map(|w| w.handle);
 
     match (next, cur, prev) {
@@ -651,28 +1269,207 @@ fn focus_window_top(state: &mut State, swap: bool) -> Option<bool> {
     None
 }
 
-fn close_all_other_windows(state: &mut State) -> Option<bool> {
-    let current_window: Option<WindowHandle> =
-        state.focus_manager.window(&state.windows).map(|w| w.handle);
-    let current_workspace = state.focus_manager.workspace(&state.workspaces);
+/// Focuses the visible window on the focused workspace with the largest or smallest area.
+/// Ties go to the window highest in the stack.
+fn focus_by_size(state: &mut State, largest: bool) -> Option<bool> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let area = |w: &&Window| {
+        let xyhw = w.calculated_xyhw();
+        i64::from(xyhw.w()) * i64::from(xyhw.h())
+    };
+    let candidates = state
+        .windows_on_workspace(workspace)
+        .filter(|w| w.visible());
+    let window = if largest {
+        candidates.rev().max_by_key(area)
+    } else {
+        candidates.min_by_key(area)
+    };
+    let handle = window?.handle;
+    state.handle_window_focus(&handle);
+    None
+}
 
-    for window in &state.windows {
-        if window.handle.ne(&current_window?)
-            && current_workspace?.is_displaying(window)
-            && window.r#type.ne(&WindowType::Normal)
-        {
-            let act = DisplayAction::KillWindow(window.handle);
-            state.actions.push_back(act);
-        }
+fn focus_window_direction(state: &mut State, dir: FocusDirection) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let next = find_window_in_direction(state, &handle, dir)?;
+    state.handle_window_focus(&next);
+    None
+}
+
+fn swap_window_direction(state: &mut State, dir: FocusDirection) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let other = find_window_in_direction(state, &handle, dir)?;
+    let index = state.windows.iter().position(|w| w.handle == handle)?;
+    let other_index = state.windows.iter().position(|w| w.handle == other)?;
+
+    if state.windows[index].floating() && state.windows[other_index].floating() {
+        // Floating windows trade places on the screen, keeping their sizes.
+        let mut xyhw = state.windows[index].exact_xyhw();
+        let mut other_xyhw = state.windows[other_index].exact_xyhw();
+        let (x, y) = (xyhw.x(), xyhw.y());
+        xyhw.set_x(other_xyhw.x());
+        xyhw.set_y(other_xyhw.y());
+        other_xyhw.set_x(x);
+        other_xyhw.set_y(y);
+        state.windows[index].set_floating_exact(xyhw);
+        state.windows[other_index].set_floating_exact(other_xyhw);
+    } else {
+        state.windows.swap(index, other_index);
+        state.sort_windows();
     }
+    state.handle_window_focus(&handle);
     Some(true)
 }
 
-fn focus_workspace_change(state: &mut State, val: i32) -> Option<bool> {
-    let current = state.focus_manager.workspace(&state.workspaces)?;
-    let workspace = helpers::relative_find(&state.workspaces, |w| w == current, val, true)?.clone();
+fn split_focused_with(state: &mut State, dir: FocusDirection) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let other = find_window_in_direction(state, &handle, dir)?;
+    let index = state.windows.iter().position(|w| w.handle == handle)?;
+    let other_index = state.windows.iter().position(|w| w.handle == other)?;
+    let (floating, other_floating) = (
+        state.windows[index].floating(),
+        state.windows[other_index].floating(),
+    );
+
+    if floating && other_floating {
+        // Floating windows share the span they cover together along the axis.
+        let (first, second) = match dir {
+            FocusDirection::Right | FocusDirection::Down => (index, other_index),
+            FocusDirection::Left | FocusDirection::Up => (other_index, index),
+        };
+        let mut first_xyhw = state.windows[first].exact_xyhw();
+        let mut second_xyhw = state.windows[second].exact_xyhw();
+        if matches!(dir, FocusDirection::Left | FocusDirection::Right) {
+            let start = first_xyhw.x().min(second_xyhw.x());
+            let end = (first_xyhw.x() + first_xyhw.w()).max(second_xyhw.x() + second_xyhw.w());
+            let half = (end - start) / 2;
+            first_xyhw.set_x(start);
+            first_xyhw.set_w(half);
+            second_xyhw.set_x(start + half);
+            second_xyhw.set_w(end - start - half);
+        } else {
+            let start = first_xyhw.y().min(second_xyhw.y());
+            let end = (first_xyhw.y() + first_xyhw.h()).max(second_xyhw.y() + second_xyhw.h());
+            let half = (end - start) / 2;
+            first_xyhw.set_y(start);
+            first_xyhw.set_h(half);
+            second_xyhw.set_y(start + half);
+            second_xyhw.set_h(end - start - half);
+        }
+        state.windows[first].set_floating_exact(first_xyhw);
+        state.windows[second].set_floating_exact(second_xyhw);
+    } else if !floating && !other_floating && straddles_main_split(state, index, other_index, dir) {
+        // Tiled windows are split by the ratio between the main window and the stack.
+        set_main_width(state, 50)?;
+    } else {
+        return None;
+    }
+    state.handle_window_focus(&handle);
+    Some(true)
+}
 
-    if state.focus_manager.behaviour.is_sloppy() && state.focus_manager.sloppy_mouse_follows_focus {
+/// Returns true if one of the tiled windows is the main window and the other one lies beyond it
+/// in the stack, along the axis of the direction.
+fn straddles_main_split(
+    state: &State,
+    index: usize,
+    other_index: usize,
+    dir: FocusDirection,
+) -> bool {
+    let main = state.focus_manager.tag(0).and_then(|tag| {
+        state
+            .windows
+            .iter()
+            .position(|w| w.has_tag(&tag) && w.is_managed() && !w.floating())
+    });
+    let (main, stack) = match main {
+        Some(main) if main == index => (index, other_index),
+        Some(main) if main == other_index => (other_index, index),
+        _ => return false,
+    };
+    let main = state.windows[main].calculated_xyhw();
+    let stack = state.windows[stack].calculated_xyhw();
+    match dir {
+        FocusDirection::Left | FocusDirection::Right => {
+            main.x() + main.w() <= stack.x() || stack.x() + stack.w() <= main.x()
+        }
+        FocusDirection::Up | FocusDirection::Down => {
+            main.y() + main.h() <= stack.y() || stack.y() + stack.h() <= main.y()
+        }
+    }
+}
+
+/// Find the window closest to the given window in a direction, on the focused workspace.
+fn find_window_in_direction(
+    state: &State,
+    handle: &WindowHandle,
+    dir: FocusDirection,
+) -> Option<WindowHandle> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let current = state
+        .windows
+        .iter()
+        .find(|w| &w.handle == handle)?
+        .calculated_xyhw();
+
+    let candidates = state
+        .windows
+        .iter()
+        .filter(|w| &w.handle != handle && w.visible() && workspace.is_managed(w))
+        .map(|w| (w.calculated_xyhw().center(), w.handle));
+    closest_in_direction(current.center(), candidates, dir)
+}
+
+/// Find the candidate whose center is closest to `from` in a direction.
+/// Ties are broken by the distance between the centers.
+fn closest_in_direction<T>(
+    from: (i32, i32),
+    candidates: impl Iterator<Item = ((i32, i32), T)>,
+    dir: FocusDirection,
+) -> Option<T> {
+    let (cur_x, cur_y) = from;
+    candidates
+        .filter_map(|((x, y), candidate)| {
+            let distance = match dir {
+                FocusDirection::Up => cur_y - y,
+                FocusDirection::Down => y - cur_y,
+                FocusDirection::Left => cur_x - x,
+                FocusDirection::Right => x - cur_x,
+            };
+            let center_distance = (x - cur_x).pow(2) + (y - cur_y).pow(2);
+            if distance > 0 {
+                Some((distance, center_distance, candidate))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|&(distance, center_distance, _)| (distance, center_distance))
+        .map(|(_, _, candidate)| candidate)
+}
+
+fn close_all_other_windows(state: &mut State) -> Option<bool> {
+    let current_window: Option<WindowHandle> =
+        state.focus_manager.window(&state.windows).map(|w| w.handle);
+    let current_workspace = state.focus_manager.workspace(&state.workspaces);
+
+    for window in &state.windows {
+        if window.handle.ne(&current_window?)
+            && current_workspace?.is_displaying(window)
+            && window.r#type.ne(&WindowType::Normal)
+        {
+            let act = DisplayAction::KillWindow(window.handle);
+            state.actions.push_back(act);
+        }
+    }
+    Some(true)
+}
+
+fn focus_workspace_change(state: &mut State, val: i32) -> Option<bool> {
+    let current = state.focus_manager.workspace(&state.workspaces)?;
+    let workspace = helpers::relative_find(&state.workspaces, |w| w == current, val, true)?.clone();
+
+    if state.focus_manager.behaviour.is_sloppy() && state.focus_manager.sloppy_mouse_follows_focus {
         let action = workspace
             .tag
             .as_ref()
@@ -694,6 +1491,37 @@ fn rotate_tag(state: &mut State) -> Option<bool> {
     Some(true)
 }
 
+fn set_main_position(state: &mut State, side: Option<Side>) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let tag = state.tags.get_mut(tag_id)?;
+    tag.main_position = side;
+    Some(true)
+}
+
+/// Hides the stack of the focused tag, showing only its main window, or shows it again.
+/// The layout of the tag is kept.
+fn toggle_stack_visibility(state: &mut State) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let tag = state.tags.get_mut(tag_id)?;
+    tag.stack_hidden = !tag.stack_hidden;
+    if !tag.stack_hidden {
+        return Some(true);
+    }
+    // A window in the stack can't keep the focus while it is hidden.
+    match state.focus_manager.window(&state.windows) {
+        Some(focused) if !focused.floating() => {}
+        _ => return Some(true),
+    }
+    let main = state
+        .windows_on_tag(tag_id)
+        .find(|w| !w.floating())
+        .map(|w| w.handle);
+    if let Some(main) = main {
+        state.focus_window(&main);
+    }
+    Some(true)
+}
+
 fn change_main_width(state: &mut State, delta: i8, factor: i8) -> Option<bool> {
     let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
     workspace.change_main_width(delta * factor);
@@ -703,6 +1531,68 @@ fn change_main_width(state: &mut State, delta: i8, factor: i8) -> Option<bool> {
     Some(true)
 }
 
+/// Changes the main width by a percentage, keeping both the main area and the stack visible.
+fn step_main_width(state: &mut State, delta: i32) -> Option<bool> {
+    let current = i32::from(
+        state
+            .tags
+            .get(state.focus_manager.tag(0)?)?
+            .main_width_percentage,
+    );
+    let width = current.saturating_add(delta).clamp(10, 90);
+    // Both widths are percentages, so the step fits.
+    change_main_width(state, (width - current) as i8, 1)
+}
+
+/// Sets the main width back to the one the layout starts with.
+fn reset_main_width(state: &mut State) -> Option<bool> {
+    let layout = state.focus_manager.workspace(&state.workspaces)?.layout;
+    set_main_width(state, layout.main_width())
+}
+
+/// Sets the main width of the focused workspace and tag.
+fn set_main_width(state: &mut State, width: u8) -> Option<bool> {
+    let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
+    workspace.main_width_percentage = width;
+    let tag_id = state.focus_manager.tag(0)?;
+    state.tags.get_mut(tag_id)?.set_main_width(width);
+    Some(true)
+}
+
+/// Changes the border width of all windows, without going below zero.
+fn change_border_width<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    delta: i32,
+) -> bool {
+    let widest = std::cmp::max(
+        manager.config.floating_border_width(),
+        manager.config.tiled_border_width(),
+    );
+    let state = &mut manager.state;
+    // Don't keep shrinking once all borders are gone.
+    state.border_width_offset = (state.border_width_offset + delta).max(-widest);
+    state.update_borders(&manager.config)
+}
+
+/// Sets the number of columns the `Grid` layout has on the focused tag, at least one.
+fn set_grid_columns(state: &mut State, columns: usize) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    state.tags.get_mut(tag_id)?.grid_columns = columns.max(1);
+    Some(true)
+}
+
+/// Adds or removes a column of the `Grid` layout on the focused workspace.
+fn change_grid_columns(state: &mut State, delta: isize) -> Option<bool> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    if workspace.layout != Layout::Grid {
+        return None;
+    }
+    let tag_id = state.focus_manager.tag(0)?;
+    let tag = state.tags.get_mut(tag_id)?;
+    tag.grid_columns = (tag.grid_columns as isize + delta).max(1) as usize;
+    Some(true)
+}
+
 fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bool> {
     let ws = state.focus_manager.workspace_mut(&mut state.workspaces)?;
     ws.set_margin_multiplier(margin_multiplier);
@@ -722,6 +1612,30 @@ fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bo
     Some(true)
 }
 
+/// Hides the margins and gutters of all workspaces and the margins of the windows, or shows them
+/// again.
+fn toggle_gaps(state: &mut State) -> Option<bool> {
+    let hide = state.workspaces.iter().all(|ws| ws.hidden_gaps.is_none());
+    for ws in &mut state.workspaces {
+        if ws.hidden_gaps.is_none() == hide {
+            ws.toggle_gaps();
+        }
+    }
+    // Windows on tags which aren't shown get the multiplier of the first workspace.
+    let fallback = state.workspaces.first()?.margin_multiplier();
+    let workspaces = &state.workspaces;
+    for window in &mut state.windows {
+        if window.r#type == WindowType::Normal {
+            let multiplier = workspaces
+                .iter()
+                .find(|ws| ws.is_displaying(window))
+                .map_or(fallback, Workspace::margin_multiplier);
+            window.apply_margin_multiplier(multiplier);
+        }
+    }
+    Some(true)
+}
+
 fn send_workspace_to_tag(state: &mut State, ws_index: usize, tag_index: usize) -> bool {
     // todo: address inconsistency of using the index instead of the id here
     if ws_index < state.workspaces.len() && tag_index < state.tags.len_normal() {
@@ -736,7 +1650,49 @@ fn send_workspace_to_tag(state: &mut State, ws_index: usize, tag_index: usize) -
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Tags;
+    use crate::config::tests::TestConfig;
+    use crate::config::InsertBehavior;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{BBox, Gutter, Margins, Overview, Side, Tags, XyhwBuilder};
+
+    #[test]
+    fn execute_command_drives_the_manager() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let command = crate::parse_external_command("GoToTag 2 false").unwrap();
+
+        assert!(execute_command(&mut manager, command));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+    }
+
+    #[test]
+    fn chain_runs_the_commands_in_order() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let command =
+            crate::parse_external_command(r#"Chain ["GoToTag 2 false", "Spawn true"]"#).unwrap();
+
+        assert!(execute_command(&mut manager, command));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        // The spawned app is placed on the tag focused by the first command.
+        let launched: Vec<TagId> = manager.state.launches.iter().map(|l| l.tag).collect();
+        assert_eq!(launched, vec![2]);
+    }
+
+    #[test]
+    fn chain_stops_at_the_first_failing_command() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        // Marking fails, as there is no window to mark.
+        manager.command_handler(&Command::Chain(vec![
+            Command::MarkWindow { key: 'a' },
+            Command::GoToTag {
+                tag: 2,
+                swap: false,
+            },
+        ]));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+    }
 
     #[test]
     fn return_to_last_tag_should_go_back_to_last_tag() {
@@ -765,11 +1721,71 @@ mod tests {
         let current_tag = manager.state.focus_manager.tag(0).unwrap_or_default();
         assert_eq!(current_tag, 2);
 
-        manager.command_handler(&Command::ReturnToLastTag);
+        manager.command_handler(&Command::ReturnToLastTag { swap: true });
         let current_tag = manager.state.focus_manager.tag(0).unwrap_or_default();
         assert_eq!(current_tag, 1);
     }
 
+    #[test]
+    fn return_to_last_tag_toggles_between_the_two_most_recent_tags() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.focus_manager.tag_history = [2, 3, 1].into_iter().collect();
+
+        assert!(manager.command_handler(&Command::ReturnToLastTag { swap: true }));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+        assert!(manager.command_handler(&Command::ReturnToLastTag { swap: true }));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        assert!(manager.command_handler(&Command::ReturnToLastTag { swap: true }));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+    }
+
+    #[test]
+    fn return_to_last_tag_toggles_only_with_swap() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.focus_manager.tag_history = [2, 3, 1].into_iter().collect();
+
+        assert!(manager.command_handler(&Command::ReturnToLastTag { swap: true }));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+        assert!(manager.command_handler(&Command::ReturnToLastTag { swap: true }));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+
+        // With the current tag swap disabled the focused tag is kept.
+        manager.command_handler(&Command::ReturnToLastTag { swap: false });
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        manager.command_handler(&Command::ReturnToLastTag { swap: true });
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+    }
+
+    #[test]
+    fn return_to_last_tag_works_without_current_tag_swap() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.command_handler(&Command::GoToTag {
+            tag: 3,
+            swap: false,
+        });
+
+        // With the current tag swap disabled going to the current tag stays there ...
+        manager.command_handler(&Command::GoToTag {
+            tag: 3,
+            swap: false,
+        });
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+
+        // ... but the last tag can still be reached explicitly.
+        manager.command_handler(&Command::ReturnToLastTag { swap: true });
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+
+        // With the swap enabled going to the current tag does the same.
+        manager.command_handler(&Command::GoToTag { tag: 1, swap: true });
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+    }
+
     #[test]
     fn go_to_tag_should_return_false_if_no_screen_is_created() {
         let mut manager = Manager::new_test(vec![]);
@@ -1002,6 +2018,111 @@ mod tests {
         assert_eq!(manager.state.windows[0].handle, expected.handle);
     }
 
+    #[test]
+    fn windows_ignored_in_the_layout_are_left_out_of_the_tiling() {
+        let tiled_manager = |ids: &[i32]| {
+            let mut manager = Manager::new_test(vec!["1".to_string()]);
+            manager.screen_create_handler(Screen::default());
+            for &id in ids {
+                manager.window_created_handler(
+                    Window::new(WindowHandle::MockHandle(id), None, None),
+                    -1,
+                    -1,
+                );
+            }
+            manager.update_windows();
+            manager
+        };
+        let geometry = |manager: &Manager<TestConfig, MockDisplayServer>, id: i32| {
+            let handle = WindowHandle::MockHandle(id);
+            let window = manager.state.windows.iter().find(|w| w.handle == handle);
+            window.unwrap().calculated_xyhw()
+        };
+        let mut manager = tiled_manager(&[1, 2, 3]);
+        let expected = tiled_manager(&[1, 3]);
+
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(2));
+        assert!(manager.command_handler(&Command::ToggleIgnoreInLayout));
+        manager.update_windows();
+        assert!(manager.state.windows[0].floating());
+        for id in [1, 3] {
+            assert_eq!(geometry(&manager, id), geometry(&expected, id));
+        }
+
+        // The window stays floating until it is toggled back.
+        assert!(!manager.command_handler(&Command::FloatingToTile));
+        assert!(!manager.command_handler(&Command::TileAllFloating));
+        assert!(manager.command_handler(&Command::ToggleIgnoreInLayout));
+        manager.update_windows();
+        assert!(manager.state.windows.iter().all(|w| !w.floating()));
+        assert_ne!(geometry(&manager, 1), geometry(&expected, 1));
+    }
+
+    #[test]
+    fn move_window_to_tag_and_follow_goes_with_the_window() {
+        let tags = (1..=3).map(|t| t.to_string()).collect();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(2));
+
+        let command = Command::MoveWindowToTagAndFollow { tag: 3, swap: true };
+        assert!(manager.command_handler(&command));
+        assert_eq!(tag_of(&manager, 2), Some(3));
+        assert_eq!(tag_of(&manager, 1), Some(1));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+        assert_eq!(focused_window(&manager), WindowHandle::MockHandle(2));
+
+        // Sending it to the focused tag goes back to the previous tag, unless swapping is off.
+        let command = Command::MoveWindowToTagAndFollow {
+            tag: 3,
+            swap: false,
+        };
+        assert!(!manager.command_handler(&command));
+        assert_eq!(tag_of(&manager, 2), Some(3));
+        let command = Command::MoveWindowToTagAndFollow { tag: 3, swap: true };
+        assert!(manager.command_handler(&command));
+        assert_eq!(tag_of(&manager, 2), Some(1));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+        assert_eq!(focused_window(&manager), WindowHandle::MockHandle(2));
+    }
+
+    #[test]
+    fn promote_to_master_shifts_the_other_windows_down() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        let before = handles(&manager);
+
+        manager
+            .state
+            .focus_window(&WindowHandle::MockHandle(before[2]));
+        assert!(manager.command_handler(&Command::PromoteToMaster));
+
+        assert_eq!(handles(&manager), vec![before[2], before[0], before[1]]);
+        assert_eq!(
+            focused_window(&manager),
+            WindowHandle::MockHandle(before[2])
+        );
+        assert!(!manager.command_handler(&Command::PromoteToMaster));
+    }
+
     #[test]
     fn move_window_to_next_or_prev_tag_should_be_able_to_cycle() {
         let mut manager = Manager::new_test(vec![
@@ -1115,4 +2236,1247 @@ mod tests {
         assert_eq!(manager.state.windows[0].border(), 1);
         assert_eq!(manager.state.windows[1].border(), 1);
     }
+
+    /// Creates four windows in a 2x2 grid:
+    /// ```text
+    /// 1 2
+    /// 3 4
+    /// ```
+    fn grid_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        for (id, x, y) in [(1, 0, 0), (2, 400, 0), (3, 0, 300), (4, 400, 300)] {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+            let window = manager.state.windows.last_mut().unwrap();
+            window.normal = XyhwBuilder {
+                x,
+                y,
+                w: 400,
+                h: 300,
+                ..Default::default()
+            }
+            .into();
+            window.set_visible(true);
+        }
+        manager
+    }
+
+    fn focused_window(manager: &Manager<TestConfig, MockDisplayServer>) -> WindowHandle {
+        manager
+            .state
+            .focus_manager
+            .window(&manager.state.windows)
+            .unwrap()
+            .handle
+    }
+
+    #[test]
+    fn focus_window_direction_moves_focus_in_the_grid() {
+        let mut manager = grid_manager();
+        let moves = [
+            (1, FocusDirection::Right, 2),
+            (2, FocusDirection::Down, 4),
+            (4, FocusDirection::Left, 3),
+            (3, FocusDirection::Up, 1),
+            (1, FocusDirection::Down, 3),
+            (3, FocusDirection::Right, 4),
+            (4, FocusDirection::Up, 2),
+            (2, FocusDirection::Left, 1),
+        ];
+        for (from, dir, to) in moves {
+            manager.state.focus_window(&WindowHandle::MockHandle(from));
+            manager.command_handler(&Command::FocusWindowDirection { dir });
+            assert_eq!(focused_window(&manager), WindowHandle::MockHandle(to));
+        }
+    }
+
+    #[test]
+    fn focus_window_direction_keeps_focus_at_the_edge() {
+        let mut manager = grid_manager();
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        manager.command_handler(&Command::FocusWindowDirection {
+            dir: FocusDirection::Left,
+        });
+        manager.command_handler(&Command::FocusWindowDirection {
+            dir: FocusDirection::Up,
+        });
+        assert_eq!(focused_window(&manager), WindowHandle::MockHandle(1));
+    }
+
+    fn side_by_side_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 800,
+            y: 0,
+            width: 800,
+            height: 600,
+        }));
+        let first = manager.state.workspaces[0].clone();
+        manager.state.focus_workspace(&first);
+        manager
+    }
+
+    fn tag_of(manager: &Manager<TestConfig, MockDisplayServer>, id: i32) -> Option<TagId> {
+        let handle = WindowHandle::MockHandle(id);
+        manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == handle)?
+            .tag
+    }
+
+    #[test]
+    fn move_window_to_monitor_moves_to_the_adjacent_workspace() {
+        let mut manager = side_by_side_manager();
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+
+        assert!(!manager.command_handler(&Command::MoveWindowToMonitor {
+            dir: FocusDirection::Left
+        }));
+        assert_eq!(tag_of(&manager, 1), Some(1));
+
+        assert!(manager.command_handler(&Command::MoveWindowToMonitor {
+            dir: FocusDirection::Right
+        }));
+        assert_eq!(tag_of(&manager, 1), Some(2));
+        assert!(!manager.state.windows[0].floating());
+
+        manager.state.focus_window(&handle);
+        assert!(manager.command_handler(&Command::MoveWindowToMonitor {
+            dir: FocusDirection::Left
+        }));
+        assert_eq!(tag_of(&manager, 1), Some(1));
+    }
+
+    #[test]
+    fn move_window_to_monitor_keeps_floating_windows_in_place() {
+        let mut manager = side_by_side_manager();
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+        let window = &mut manager.state.windows[0];
+        window.set_floating(true);
+        window.normal = manager.state.workspaces[0].xyhw;
+        window.set_floating_exact(
+            XyhwBuilder {
+                x: 100,
+                y: 50,
+                w: 300,
+                h: 200,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+
+        assert!(manager.command_handler(&Command::MoveWindowToMonitor {
+            dir: FocusDirection::Right
+        }));
+        let window = &manager.state.windows[0];
+        let xyhw = window.exact_xyhw();
+        assert_eq!(window.tag, Some(2));
+        assert!(window.floating());
+        assert_eq!(
+            (xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h()),
+            (900, 50, 300, 200)
+        );
+    }
+
+    #[test]
+    fn swap_monitor_tags_moves_the_tags_with_their_windows() {
+        let mut manager = side_by_side_manager();
+        let first = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(first, None, None), -1, -1);
+        let second = manager.state.workspaces[1].clone();
+        manager.state.focus_workspace(&second);
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&first);
+
+        assert!(manager.command_handler(&Command::SwapMonitorTags { a: 0, b: 1 }));
+        manager.update_windows();
+
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        assert_eq!(manager.state.workspaces[1].tag, Some(1));
+        assert_eq!(
+            (tag_of(&manager, 1), tag_of(&manager, 2)),
+            (Some(1), Some(2))
+        );
+        let x_of = |id| {
+            let handle = WindowHandle::MockHandle(id);
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == handle)
+                .unwrap()
+                .x()
+        };
+        assert!(x_of(1) >= 800);
+        assert!(x_of(2) < 800);
+        assert_eq!(focused_window(&manager), first);
+        let focused = manager
+            .state
+            .focus_manager
+            .workspace(&manager.state.workspaces);
+        assert_eq!(focused.unwrap().tag, Some(1));
+    }
+
+    #[test]
+    fn swap_monitor_tags_does_nothing_for_the_same_tag() {
+        let mut manager = side_by_side_manager();
+        assert!(!manager.command_handler(&Command::SwapMonitorTags { a: 1, b: 1 }));
+        assert!(!manager.command_handler(&Command::SwapMonitorTags { a: 0, b: 5 }));
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+        assert_eq!(manager.state.workspaces[1].tag, Some(2));
+    }
+
+    #[test]
+    fn swap_tag_contents_exchanges_the_windows_of_two_tags() {
+        let mut manager =
+            Manager::new_test(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=4 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.windows[1].tag = Some(2);
+        manager.state.windows[2].tag = Some(3);
+        manager.state.windows[3].set_states(vec![WindowState::Sticky]);
+        let tags = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            (1..=4).map(|id| tag_of(manager, id)).collect::<Vec<_>>()
+        };
+
+        assert!(manager.command_handler(&Command::SwapTagContents { a: 1, b: 2 }));
+        assert_eq!(tags(&manager), vec![Some(2), Some(1), Some(3), Some(1)]);
+        let labels: Vec<&str> = manager
+            .state
+            .tags
+            .normal()
+            .iter()
+            .map(|t| t.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["a", "b", "c"]);
+
+        assert!(!manager.command_handler(&Command::SwapTagContents { a: 1, b: 1 }));
+        assert!(!manager.command_handler(&Command::SwapTagContents { a: 1, b: 9 }));
+    }
+
+    #[test]
+    fn move_tag_keeps_windows_and_focus_with_their_tags() {
+        let mut manager =
+            Manager::new_test(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.goto_tag_handler(3);
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager.state.goto_tag_handler(1);
+
+        assert!(manager.command_handler(&Command::MoveTag { from: 1, to: 3 }));
+
+        let labels: Vec<&str> = manager
+            .state
+            .tags
+            .normal()
+            .iter()
+            .map(|t| t.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["b", "c", "a"]);
+        assert_eq!(
+            (tag_of(&manager, 1), tag_of(&manager, 2)),
+            (Some(3), Some(2))
+        );
+        assert_eq!(manager.state.workspaces[0].tag, Some(3));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+        assert_eq!(manager.state.focus_manager.tag(1), Some(2));
+        assert_eq!(
+            manager.state.focus_manager.tags_last_window.get(&2),
+            Some(&WindowHandle::MockHandle(2))
+        );
+    }
+
+    #[test]
+    fn move_tag_remaps_every_tag_kept_in_the_state() {
+        let mut manager =
+            Manager::new_test(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        let state = &mut manager.state;
+        state.windows[0].extra_tags = vec![2];
+        state.windows[0].spawn_tag = Some(3);
+        state.remember_app_tags = Some(5);
+        state.remember_app_tag("st", 1);
+        state.launches.push(Launch::new(2, "id".to_string(), 7));
+        state.launches.push(Launch::new(3, "other".to_string(), 8));
+        state.overview = Some(Overview {
+            tag: 1,
+            layout: Layout::default(),
+            grid_columns: Layout::GRID_COLUMNS,
+            window_tags: [(WindowHandle::MockHandle(1), Some(3))]
+                .into_iter()
+                .collect(),
+        });
+
+        assert!(manager.command_handler(&Command::MoveTag { from: 1, to: 3 }));
+
+        let state = &manager.state;
+        assert_eq!(state.windows[0].extra_tags, vec![1]);
+        assert_eq!(state.windows[0].spawn_tag, Some(2));
+        assert_eq!(state.app_tag("st"), Some(3));
+        let launched: Vec<TagId> = state.launches.iter().map(|l| l.tag).collect();
+        assert_eq!(launched, vec![1, 2]);
+        let overview = state.overview.as_ref().unwrap();
+        assert_eq!(overview.tag, 3);
+        assert_eq!(
+            overview.window_tags.get(&WindowHandle::MockHandle(1)),
+            Some(&Some(2))
+        );
+    }
+
+    #[test]
+    fn toggle_window_tag_adds_and_removes_tags() {
+        let tags = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+
+        assert!(manager.command_handler(&Command::ToggleWindowTag { tag: 3 }));
+        assert_eq!(manager.state.windows[0].extra_tags, vec![3]);
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetWindowTag(h, Some(1)) if *h == handle
+        )));
+
+        manager.state.goto_tag_handler(3);
+        assert_eq!(tag_of(&manager, 1), Some(1));
+        assert!(manager.state.workspaces[0].is_displaying(&manager.state.windows[0]));
+
+        assert!(manager.command_handler(&Command::ToggleWindowTag { tag: 1 }));
+        assert_eq!(tag_of(&manager, 1), Some(3));
+        assert!(manager.state.windows[0].extra_tags.is_empty());
+        manager.state.goto_tag_handler(1);
+        assert!(!manager.state.workspaces[0].is_displaying(&manager.state.windows[0]));
+    }
+
+    #[test]
+    fn windows_show_on_all_displayed_tags_they_are_on() {
+        let mut manager = side_by_side_manager();
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+        manager.state.focus_window(&handle);
+
+        // The second tag is already shown on the other workspace.
+        assert!(manager.command_handler(&Command::ToggleWindowTag { tag: 2 }));
+        let window = &manager.state.windows[0];
+        assert!(manager.state.workspaces[0].is_displaying(window));
+        assert!(manager.state.workspaces[1].is_displaying(window));
+
+        manager.command_handler(&Command::SwapScreens);
+        let window = &manager.state.windows[0];
+        assert!(manager
+            .state
+            .workspaces
+            .iter()
+            .all(|ws| ws.is_displaying(window)));
+    }
+
+    #[test]
+    fn toggle_window_tag_keeps_the_last_tag() {
+        let mut manager = side_by_side_manager();
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+
+        assert!(!manager.command_handler(&Command::ToggleWindowTag { tag: 1 }));
+        assert!(!manager.command_handler(&Command::ToggleWindowTag { tag: 5 }));
+        assert_eq!(tag_of(&manager, 1), Some(1));
+        assert!(manager.state.windows[0].extra_tags.is_empty());
+
+        assert!(manager.command_handler(&Command::ToggleWindowTag { tag: 2 }));
+        assert!(manager.command_handler(&Command::ToggleWindowTag { tag: 1 }));
+        assert_eq!(tag_of(&manager, 1), Some(2));
+        assert!(!manager.command_handler(&Command::ToggleWindowTag { tag: 2 }));
+        assert_eq!(tag_of(&manager, 1), Some(2));
+    }
+
+    #[test]
+    fn set_window_tags_shows_the_window_on_all_given_tags() {
+        let tags = (1..=5).map(|t| t.to_string()).collect();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+        let visible = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let window = &manager.state.windows[0];
+            manager.state.workspaces[0].is_displaying(window)
+        };
+
+        assert!(!manager.command_handler(&Command::SetWindowTags { tags: vec![0, 9] }));
+        assert!(manager.command_handler(&Command::SetWindowTags {
+            tags: vec![2, 1, 3, 9, 3]
+        }));
+        assert_eq!(tag_of(&manager, 1), Some(1));
+        assert_eq!(manager.state.windows[0].extra_tags, vec![2, 3]);
+
+        for (tag, shown) in [(2, true), (4, false), (3, true), (5, false), (1, true)] {
+            manager.state.goto_tag_handler(tag);
+            assert_eq!(visible(&manager), shown, "tag {tag}");
+        }
+    }
+
+    #[test]
+    fn move_tag_does_nothing_for_unknown_tags() {
+        let mut manager = side_by_side_manager();
+        assert!(!manager.command_handler(&Command::MoveTag { from: 1, to: 1 }));
+        assert!(!manager.command_handler(&Command::MoveTag { from: 1, to: 5 }));
+        assert_eq!(manager.state.tags.get(1).unwrap().label, "1");
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+    }
+
+    fn handles(manager: &Manager<TestConfig, MockDisplayServer>) -> Vec<i32> {
+        manager
+            .state
+            .windows
+            .iter()
+            .map(|w| match w.handle {
+                WindowHandle::MockHandle(id) => id,
+                WindowHandle::XlibHandle(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn swap_window_direction_swaps_the_stack_positions_in_the_grid() {
+        let swaps = [
+            (1, FocusDirection::Right, vec![2, 1, 3, 4]),
+            (1, FocusDirection::Down, vec![3, 2, 1, 4]),
+            (4, FocusDirection::Left, vec![1, 2, 4, 3]),
+            (4, FocusDirection::Up, vec![1, 4, 3, 2]),
+        ];
+        for (from, dir, expected) in swaps {
+            let mut manager = grid_manager();
+            manager.state.focus_window(&WindowHandle::MockHandle(from));
+            assert!(manager.command_handler(&Command::SwapWindowDirection { dir }));
+            assert_eq!(handles(&manager), expected);
+            assert_eq!(focused_window(&manager), WindowHandle::MockHandle(from));
+        }
+    }
+
+    #[test]
+    fn swap_window_direction_swaps_floating_window_positions() {
+        let mut manager = grid_manager();
+        for window in &mut manager.state.windows {
+            let xyhw = window.normal;
+            window.set_floating(true);
+            window.set_floating_exact(xyhw);
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(manager.command_handler(&Command::SwapWindowDirection {
+            dir: FocusDirection::Right,
+        }));
+
+        let position = |id| {
+            let window = manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == WindowHandle::MockHandle(id))
+                .unwrap();
+            (window.x(), window.y())
+        };
+        assert_eq!(position(1), (400, 0));
+        assert_eq!(position(2), (0, 0));
+        assert_eq!(handles(&manager), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn split_focused_with_halves_floating_windows() {
+        let mut manager = grid_manager();
+        // Only the top row stays, its windows are 300 and 500 wide.
+        manager.state.windows.truncate(2);
+        for (window, (x, w)) in manager.state.windows.iter_mut().zip([(0, 300), (300, 500)]) {
+            let mut xyhw = window.normal;
+            xyhw.set_x(x);
+            xyhw.set_w(w);
+            window.set_floating(true);
+            window.set_floating_exact(xyhw);
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        assert!(manager.command_handler(&Command::SplitFocusedWith {
+            dir: FocusDirection::Left,
+        }));
+
+        let geometry = |id| {
+            let window = manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == WindowHandle::MockHandle(id))
+                .unwrap();
+            (window.x(), window.width())
+        };
+        assert_eq!(geometry(1), (0, 400));
+        assert_eq!(geometry(2), (400, 400));
+        assert_eq!(focused_window(&manager), WindowHandle::MockHandle(2));
+    }
+
+    #[test]
+    fn split_focused_with_evens_out_the_main_width() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::IncreaseMainWidth(20));
+        manager.update_windows();
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(manager.command_handler(&Command::SplitFocusedWith {
+            dir: FocusDirection::Right,
+        }));
+        manager.update_windows();
+
+        let width = manager.state.workspaces[0].width();
+        let widths: Vec<i32> = manager.state.windows.iter().map(Window::width).collect();
+        assert_eq!(widths, vec![width / 2, width / 2]);
+    }
+
+    #[test]
+    fn split_focused_with_leaves_the_main_width_alone_within_one_side() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::IncreaseMainWidth(20));
+        manager.update_windows();
+        let main_width = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager.state.workspaces[0].main_width_percentage
+        };
+
+        // Both windows are in the stack.
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        assert!(!manager.command_handler(&Command::SplitFocusedWith {
+            dir: FocusDirection::Down,
+        }));
+        assert_eq!(main_width(&manager), 70);
+
+        // The main window spans the whole width above the stack.
+        manager.command_handler(&Command::SetLayout(Layout::MainAndHorizontalStack));
+        manager.update_windows();
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(!manager.command_handler(&Command::SplitFocusedWith {
+            dir: FocusDirection::Right,
+        }));
+        assert_eq!(main_width(&manager), 70);
+        assert!(manager.command_handler(&Command::SplitFocusedWith {
+            dir: FocusDirection::Down,
+        }));
+        assert_eq!(main_width(&manager), 50);
+    }
+
+    #[test]
+    fn close_all_on_tag_only_closes_the_focused_tags_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=5 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, Some(id as u32)),
+                -1,
+                -1,
+            );
+        }
+        for window in &mut manager.state.windows {
+            match window.handle {
+                WindowHandle::MockHandle(3) => window.tag = Some(2),
+                WindowHandle::MockHandle(4) => window.set_states(vec![WindowState::Sticky]),
+                _ => {}
+            }
+        }
+        manager
+            .state
+            .active_scratchpads
+            .insert("Alacritty".into(), std::collections::VecDeque::from([5]));
+        manager.state.actions.clear();
+
+        manager.command_handler(&Command::CloseAllOnTag);
+
+        let mut closed: Vec<WindowHandle> = manager
+            .state
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                DisplayAction::KillWindow(handle) => Some(*handle),
+                _ => None,
+            })
+            .collect();
+        closed.sort_by_key(|handle| match handle {
+            WindowHandle::MockHandle(id) => *id,
+            WindowHandle::XlibHandle(_) => unreachable!(),
+        });
+        assert_eq!(
+            closed,
+            vec![WindowHandle::MockHandle(1), WindowHandle::MockHandle(2)]
+        );
+    }
+
+    #[test]
+    fn grid_columns_can_be_changed_at_runtime() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            layouts: vec![Layout::MainAndVertStack, Layout::Grid],
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        let layout =
+            |manager: &Manager<TestConfig, MockDisplayServer>| manager.state.workspaces[0].layout;
+        let columns = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager.state.tags.get(1).unwrap().grid_columns
+        };
+
+        // Only the grid has columns.
+        assert!(!manager.command_handler(&Command::IncreaseGridColumns));
+
+        manager.command_handler(&Command::NextLayout);
+        assert_eq!(layout(&manager), Layout::Grid);
+        assert_eq!(columns(&manager), 2);
+        manager.command_handler(&Command::IncreaseGridColumns);
+        assert_eq!(columns(&manager), 3);
+        for _ in 0..3 {
+            manager.command_handler(&Command::DecreaseGridColumns);
+        }
+        assert_eq!(columns(&manager), 1);
+
+        manager.command_handler(&Command::NextLayout);
+        assert_eq!(layout(&manager), Layout::MainAndVertStack);
+        assert!(manager.command_handler(&Command::SetLayout(Layout::Grid)));
+        assert!(manager.command_handler(&Command::SetGridColumns(4)));
+        assert_eq!(layout(&manager), Layout::Grid);
+        assert_eq!(columns(&manager), 4);
+    }
+
+    #[test]
+    fn main_width_commands_resize_the_centered_main_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.command_handler(&Command::SetLayout(Layout::CenteredMain));
+        let width = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager.state.tags.get(1).unwrap().main_width_percentage
+        };
+
+        manager.command_handler(&Command::IncreaseMainWidth(10));
+        assert_eq!(width(&manager), 60);
+        manager.command_handler(&Command::DecreaseMainWidth(100));
+        assert_eq!(width(&manager), 0);
+        manager.command_handler(&Command::ResetMainWidth);
+        assert_eq!(width(&manager), 50);
+        assert_eq!(manager.state.workspaces[0].layout, Layout::CenteredMain);
+    }
+
+    #[test]
+    fn border_commands_change_the_border_width_down_to_zero() {
+        let mut manager = Manager::new_test_with_border(vec!["1".to_string()], 2);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        let borders = |m: &Manager<_, _>| -> Vec<i32> {
+            m.state.windows.iter().map(Window::border).collect()
+        };
+
+        assert!(manager.command_handler(&Command::IncreaseBorder(3)));
+        assert_eq!(borders(&manager), vec![5, 5]);
+
+        manager.command_handler(&Command::DecreaseBorder(10));
+        assert_eq!(borders(&manager), vec![0, 0]);
+        // Nothing needs a refresh once all borders are gone.
+        assert!(!manager.command_handler(&Command::DecreaseBorder(1)));
+
+        manager.command_handler(&Command::IncreaseBorder(1));
+        assert_eq!(borders(&manager), vec![1, 1]);
+    }
+
+    #[test]
+    fn goto_mark_jumps_back_to_the_marked_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(!manager.command_handler(&Command::MarkWindow { key: 'a' }));
+
+        manager.command_handler(&Command::GoToTag {
+            tag: 2,
+            swap: false,
+        });
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+
+        assert!(manager.command_handler(&Command::GotoMark { key: 'a' }));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+        assert_eq!(focused_window(&manager), WindowHandle::MockHandle(1));
+
+        // Unknown marks and marks of closed windows do nothing.
+        assert!(!manager.command_handler(&Command::GotoMark { key: 'b' }));
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(1));
+        assert!(manager.state.marks.is_empty());
+    }
+
+    #[test]
+    fn tile_all_floating_tiles_only_eligible_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=5 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, Some(id as u32)),
+                -1,
+                -1,
+            );
+        }
+        manager
+            .state
+            .active_scratchpads
+            .insert("Alacritty".into(), std::collections::VecDeque::from([5]));
+        for window in &mut manager.state.windows {
+            match window.handle {
+                WindowHandle::MockHandle(1 | 5) => window.set_floating(true),
+                WindowHandle::MockHandle(3) => window.must_float = true,
+                WindowHandle::MockHandle(4) => {
+                    window.tag = Some(2);
+                    window.set_floating(true);
+                }
+                _ => {}
+            }
+        }
+
+        assert!(manager.command_handler(&Command::TileAllFloating));
+        let floating: Vec<bool> = (1..=5)
+            .map(|id| {
+                let handle = WindowHandle::MockHandle(id);
+                let window = manager.state.windows.iter().find(|w| w.handle == handle);
+                window.unwrap().floating()
+            })
+            .collect();
+        assert_eq!(floating, vec![false, false, true, true, true]);
+    }
+
+    #[test]
+    fn focus_next_of_class_cycles_through_windows_of_the_focused_class() {
+        let tags = (1..=3).map(|t| t.to_string()).collect();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        // Terminals on tags 1, 3 and 1, a browser on tag 2.
+        for (id, class, tag) in [
+            (1, "term", 1),
+            (2, "web", 2),
+            (3, "term", 3),
+            (4, "term", 1),
+        ] {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.res_class = Some(class.to_string());
+            window.tag = Some(tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+        let focused = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager.state.focus_manager.window_history[0]
+        };
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(1));
+
+        for expected in [4, 3, 1, 4] {
+            assert!(manager.command_handler(&Command::FocusNextOfClass));
+            assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(expected)));
+            let tag = tag_of(&manager, expected);
+            assert_eq!(manager.state.focus_manager.tag(0), tag);
+        }
+
+        // The only window of its class has nothing to cycle to.
+        manager.state.goto_tag_handler(2);
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(2));
+        assert!(!manager.command_handler(&Command::FocusNextOfClass));
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(2)));
+    }
+
+    #[test]
+    fn focus_cycling_bypasses_windows_skipping_it() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.skip_focus_cycle = id == 2;
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.update_windows();
+        let first = manager.state.windows[0].handle;
+        manager.state.handle_window_focus(&first);
+
+        for layout in [
+            Layout::MainAndVertStack,
+            Layout::Monocle,
+            Layout::MainAndDeck,
+        ] {
+            manager.command_handler(&Command::SetLayout(layout));
+            for command in [
+                Command::FocusWindowDown,
+                Command::FocusWindowUp,
+                Command::FocusNextTiled,
+                Command::FocusPrevTiled,
+            ] {
+                for _ in 0..3 {
+                    manager.command_handler(&command);
+                    assert_ne!(
+                        focused_window(&manager),
+                        WindowHandle::MockHandle(2),
+                        "{layout:?}"
+                    );
+                }
+            }
+            assert_eq!(manager.state.windows.len(), 3);
+        }
+        manager.command_handler(&Command::SetLayout(Layout::MainAndVertStack));
+        assert!(manager.state.windows.iter().all(Window::visible));
+    }
+
+    #[test]
+    fn focus_tiled_cycles_skip_floating_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=4 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        for id in [2, 4] {
+            manager
+                .state
+                .handle_window_focus(&WindowHandle::MockHandle(id));
+            assert!(manager.command_handler(&Command::TileToFloating));
+        }
+        manager.update_windows();
+        let tiled: Vec<WindowHandle> = manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| !w.floating())
+            .map(|w| w.handle)
+            .collect();
+        assert_eq!(tiled.len(), 2);
+
+        // From a floating window the first tiled one is focused, then the cycle wraps around.
+        for expected in [tiled[0], tiled[1], tiled[0]] {
+            assert!(manager.command_handler(&Command::FocusNextTiled));
+            assert_eq!(focused_window(&manager), expected);
+        }
+        for expected in [tiled[1], tiled[0]] {
+            assert!(manager.command_handler(&Command::FocusPrevTiled));
+            assert_eq!(focused_window(&manager), expected);
+        }
+    }
+
+    #[test]
+    fn cycle_insert_behavior_advances_and_wraps() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.state.insert_behavior = InsertBehavior::Top;
+        for expected in [
+            InsertBehavior::Bottom,
+            InsertBehavior::AfterCurrent,
+            InsertBehavior::BeforeCurrent,
+            InsertBehavior::Top,
+        ] {
+            manager.command_handler(&Command::CycleInsertBehavior);
+            assert_eq!(manager.state.insert_behavior, expected);
+        }
+    }
+
+    #[test]
+    fn change_main_width_steps_and_clamps_the_main_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        let main_width = |manager: &mut Manager<TestConfig, MockDisplayServer>| {
+            manager.update_windows();
+            manager.state.windows[0].width()
+        };
+        let default = main_width(&mut manager);
+
+        assert!(manager.command_handler(&Command::ChangeMainWidth { delta: 25 }));
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 75);
+        assert!(main_width(&mut manager) > default);
+
+        manager.command_handler(&Command::ChangeMainWidth { delta: 50 });
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 90);
+        let widest = main_width(&mut manager);
+        manager.command_handler(&Command::ChangeMainWidth { delta: -100 });
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 10);
+        assert!(main_width(&mut manager) < widest);
+        manager.command_handler(&Command::ChangeMainWidth { delta: i32::MAX });
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 90);
+        manager.command_handler(&Command::ChangeMainWidth { delta: i32::MIN });
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 10);
+        assert_eq!(manager.state.workspaces[0].main_width_percentage, 10);
+
+        assert!(manager.command_handler(&Command::ResetMainWidth));
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 50);
+        assert_eq!(main_width(&mut manager), default);
+    }
+
+    #[test]
+    fn toggle_maximize_fills_the_workspace_and_restores() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.update_windows();
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        let index = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let mut windows = manager.state.windows.iter();
+            windows
+                .position(|w| w.handle == WindowHandle::MockHandle(2))
+                .unwrap()
+        };
+        let tiled = manager.state.windows[index(&manager)].calculated_xyhw();
+
+        assert!(manager.command_handler(&Command::ToggleMaximize));
+        manager.update_windows();
+        let window = &manager.state.windows[index(&manager)];
+        assert!(window.floating());
+        assert_eq!(
+            window.exact_xyhw(),
+            manager.state.workspaces[0].usable_xyhw()
+        );
+
+        assert!(manager.command_handler(&Command::ToggleMaximize));
+        manager.update_windows();
+        let window = &manager.state.windows[index(&manager)];
+        assert!(!window.floating());
+        assert_eq!(window.maximized_from, None);
+        assert_eq!(window.calculated_xyhw(), tiled);
+    }
+
+    #[test]
+    fn toggle_maximize_restores_floating_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let window = Window::new(WindowHandle::MockHandle(1), None, None);
+        manager.window_created_handler(window, -1, -1);
+        manager.command_handler(&Command::TileToFloating);
+        manager.update_windows();
+        let floating = manager.state.windows[0].exact_xyhw();
+
+        manager.command_handler(&Command::ToggleMaximize);
+        assert_ne!(manager.state.windows[0].exact_xyhw(), floating);
+        manager.command_handler(&Command::ToggleMaximize);
+        assert!(manager.state.windows[0].floating());
+        assert_eq!(manager.state.windows[0].exact_xyhw(), floating);
+    }
+
+    #[test]
+    fn toggle_gaps_removes_and_restores_all_gaps() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.workspaces[0].margin = Margins::new(5);
+        manager.state.workspaces[0].gutters = vec![Gutter::new(Side::Top, 20, None)];
+        for id in 1..=2 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.state.windows[1].tag = Some(2);
+        manager.update_windows();
+        let usable = manager.state.workspaces[0].usable_xyhw();
+        let shown = manager.state.windows[0].calculated_xyhw();
+        let multipliers = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let windows = manager.state.windows.iter();
+            windows.map(Window::margin_multiplier).collect::<Vec<f32>>()
+        };
+
+        assert!(manager.command_handler(&Command::ToggleGaps));
+        manager.update_windows();
+        let workspace = &manager.state.workspaces[0];
+        assert_eq!(workspace.usable_xyhw(), workspace.xyhw);
+        let full = XyhwBuilder {
+            x: workspace.xyhw.x(),
+            y: workspace.xyhw.y(),
+            w: workspace.xyhw.w(),
+            h: workspace.xyhw.h(),
+            ..XyhwBuilder::default()
+        };
+        assert_eq!(manager.state.windows[0].calculated_xyhw(), full.into());
+        assert_eq!(multipliers(&manager), vec![0.0, 0.0]);
+
+        assert!(manager.command_handler(&Command::ToggleGaps));
+        manager.update_windows();
+        assert_eq!(manager.state.workspaces[0].usable_xyhw(), usable);
+        assert_eq!(manager.state.windows[0].calculated_xyhw(), shown);
+        assert_eq!(multipliers(&manager), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn focus_by_size_focuses_the_largest_or_smallest_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=4 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        // A tiny window on a hidden tag is never picked.
+        let hidden = WindowHandle::MockHandle(4);
+        let window = manager
+            .state
+            .windows
+            .iter_mut()
+            .find(|w| w.handle == hidden);
+        window.unwrap().tag = Some(2);
+        manager.update_windows();
+        let tiled: Vec<WindowHandle> = manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| w.handle != hidden)
+            .map(|w| w.handle)
+            .collect();
+        let focused = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager.state.focus_manager.window_history[0]
+        };
+
+        // The main window is the largest, the stack windows are equally small.
+        manager.command_handler(&Command::FocusBySize { largest: false });
+        assert_eq!(focused(&manager), Some(tiled[1]));
+        manager.command_handler(&Command::FocusBySize { largest: true });
+        assert_eq!(focused(&manager), Some(tiled[0]));
+    }
+
+    #[test]
+    fn set_main_position_can_be_reset() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let main_position = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager.state.tags.get(1).unwrap().main_position.clone()
+        };
+
+        assert!(manager.command_handler(&Command::SetMainPosition {
+            side: Some(Side::Right),
+        }));
+        assert_eq!(main_position(&manager), Some(Side::Right));
+        assert!(manager.command_handler(&Command::SetMainPosition { side: None }));
+        assert_eq!(main_position(&manager), None);
+    }
+
+    #[test]
+    fn toggle_stack_visibility_hides_and_restores_the_stack() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+        let visible = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let windows = &manager.state.windows;
+            windows.iter().map(Window::visible).collect::<Vec<bool>>()
+        };
+        let geometry = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let windows = &manager.state.windows;
+            windows.iter().map(|w| w.normal).collect::<Vec<_>>()
+        };
+        let tiled = geometry(&manager);
+        assert_eq!(visible(&manager), vec![true, true, true]);
+
+        manager
+            .state
+            .focus_window(&manager.state.windows[2].handle.clone());
+        assert!(manager.command_handler(&Command::ToggleStackVisibility));
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![true, false, false]);
+        let main = manager.state.windows[0].handle;
+        assert_eq!(manager.state.focus_manager.window_history[0], Some(main));
+        assert_eq!(manager.state.tags.get(1).unwrap().layout, Layout::default());
+
+        assert!(manager.command_handler(&Command::ToggleStackVisibility));
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![true, true, true]);
+        assert_eq!(geometry(&manager), tiled);
+    }
+
+    #[test]
+    fn focus_and_move_commands_only_show_one_window_with_the_stack_hidden() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::ToggleStackVisibility);
+        let commands = [
+            Command::FocusWindowDown,
+            Command::FocusWindowUp,
+            Command::MoveWindowDown,
+            Command::MoveWindowUp,
+        ];
+        for command in &commands {
+            let before = focused_window(&manager);
+            manager.command_handler(command);
+            manager.update_windows();
+            let shown: Vec<WindowHandle> = manager
+                .state
+                .windows
+                .iter()
+                .filter(|w| w.visible())
+                .map(|w| w.handle)
+                .collect();
+            assert_ne!(focused_window(&manager), before, "{command:?}");
+            assert_eq!(shown, vec![focused_window(&manager)], "{command:?}");
+        }
+    }
+
+    #[test]
+    fn focused_window_json_describes_the_focused_window() {
+        let mut manager = Manager::new_test(vec!["main".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        assert_eq!(focused_window_json(&manager.state), "null");
+
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.res_class = Some("Alacritty".to_string());
+        manager.window_created_handler(window, -1, -1);
+        manager.command_handler(&Command::TileToFloating);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&focused_window_json(&manager.state)).unwrap();
+        let window = &manager.state.windows[0];
+        let xyhw = window.calculated_xyhw();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "handle": { "MockHandle": 1 },
+                "x": xyhw.x(),
+                "y": xyhw.y(),
+                "h": xyhw.h(),
+                "w": xyhw.w(),
+                "floating": true,
+                "tag": "main",
+                "class": "Alacritty",
+            })
+        );
+    }
+
+    #[test]
+    fn tile_to_floating_scales_the_default_size() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen {
+            scale: 2.0,
+            ..Screen::default()
+        });
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.command_handler(&Command::TileToFloating);
+        let floating = manager.state.windows[0].get_floating_offsets().unwrap();
+        let default = (manager.state.default_width, manager.state.default_height);
+        assert_eq!((floating.w(), floating.h()), (2 * default.0, 2 * default.1));
+    }
+
+    #[test]
+    fn layout_dump_lists_the_visible_windows_of_each_workspace() {
+        let mut manager = Manager::new_test(vec!["main".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.res_class = Some(format!("class{id}"));
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager.update_windows();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&layout_dump_json(&manager.state)).unwrap();
+        let workspace = &manager.state.workspaces[0];
+        let usable = workspace.usable_xyhw();
+        let windows: Vec<serde_json::Value> = manager
+            .state
+            .windows
+            .iter()
+            .map(|w| {
+                let xyhw = w.calculated_xyhw();
+                serde_json::json!({
+                    "handle": w.handle,
+                    "class": w.res_class,
+                    "x": xyhw.x(),
+                    "y": xyhw.y(),
+                    "h": xyhw.h(),
+                    "w": xyhw.w(),
+                })
+            })
+            .collect();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "id": workspace.id,
+                "tag": "main",
+                "layout": "MainAndVertStack",
+                "x": usable.x(),
+                "y": usable.y(),
+                "h": usable.h(),
+                "w": usable.w(),
+                "windows": windows,
+            }])
+        );
+        assert_eq!(json[0]["windows"].as_array().map(Vec::len), Some(2));
+    }
 }
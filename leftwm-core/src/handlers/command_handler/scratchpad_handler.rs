@@ -9,7 +9,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     child_process::{exec_shell, ChildID},
-    models::{ScratchPadName, TagId, WindowHandle},
+    handlers::window_handler::scratchpad_of,
+    models::{ScratchPadName, TagId, WindowHandle, XyhwBuilder},
+    state::State,
     Command, Config, DisplayAction, DisplayServer, Manager, Window,
 };
 
@@ -34,8 +36,7 @@ fn hide_scratchpad<C: Config, SERVER: DisplayServer>(
     tracing::trace!("Hide scratchpad window {:?}", scratchpad_window);
     let nsp_tag = manager
         .state
-        .tags
-        .get_hidden_by_label("NSP")
+        .scratchpad_tag()
         .ok_or("Could not find NSP tag")?;
     let window = manager
         .state
@@ -46,16 +47,13 @@ fn hide_scratchpad<C: Config, SERVER: DisplayServer>(
 
     window.untag();
     // Hide the scratchpad.
-    window.tag(&nsp_tag.id);
+    window.tag(&nsp_tag);
     window.set_visible(false);
 
     // Send tag changement to X
     let act = DisplayAction::SetWindowTag(*scratchpad_window, window.tag);
     manager.state.actions.push_back(act);
-    manager.state.sort_windows();
-    manager
-        .state
-        .handle_single_border(manager.config.border_width());
+    manager.state.update_borders(&manager.config);
 
     // Will ignore current window handler because we just set it invisible
     let last_focused_still_visible = manager
@@ -97,6 +95,44 @@ fn hide_scratchpad<C: Config, SERVER: DisplayServer>(
     Ok(())
 }
 
+impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
+    /// Hide the scratchpad window `previous` if it lost the focus and its scratchpad is set to
+    /// `hide_on_focus_loss`. A focused dialog of the scratchpad window keeps it visible.
+    /// Returns `Some(true)` if the scratchpad was hidden.
+    pub(crate) fn hide_scratchpad_on_focus_loss(
+        &mut self,
+        previous: Option<WindowHandle>,
+    ) -> Option<bool> {
+        let previous = previous?;
+        if let Some(focused) = self.state.focus_manager.window(&self.state.windows) {
+            if focused.handle == previous || focused.transient == Some(previous) {
+                return Some(false);
+            }
+        }
+        let nsp_tag = self.state.scratchpad_tag()?;
+        let window = self.state.windows.iter().find(|w| w.handle == previous)?;
+        if window.has_tag(&nsp_tag) {
+            return Some(false);
+        }
+        let state = &self.state;
+        let name = scratchpad_of(state, window)?;
+        let hide = state
+            .scratchpads
+            .iter()
+            .any(|s| &s.name == name && s.hide_on_focus_loss);
+        if !hide {
+            return Some(false);
+        }
+        match hide_scratchpad(self, &previous) {
+            Ok(()) => Some(true),
+            Err(msg) => {
+                tracing::error!("{}", msg);
+                Some(false)
+            }
+        }
+    }
+}
+
 /// Makes a scratchpad window visible:
 /// Expects that the window handle is a valid handle to an invisible scratchpad window
 fn show_scratchpad<C: Config, SERVER: DisplayServer>(
@@ -134,10 +170,7 @@ fn show_scratchpad<C: Config, SERVER: DisplayServer>(
     // Send tag changement to X
     let act = DisplayAction::SetWindowTag(*scratchpad_window, window.tag);
     manager.state.actions.push_back(act);
-    manager.state.sort_windows();
-    manager
-        .state
-        .handle_single_border(manager.config.border_width());
+    manager.state.update_borders(&manager.config);
     manager.state.handle_window_focus(scratchpad_window);
     manager.state.move_to_top(scratchpad_window);
 
@@ -247,6 +280,42 @@ pub fn toggle_scratchpad<C: Config, SERVER: DisplayServer>(
         }
     }
 
+    // Windows of the scratchpad's class are toggled instead of starting a new one.
+    if let Some(window) =
+        find_window_of_class(&manager.state, scratchpad.class.as_ref(), *current_tag)
+    {
+        let handle = window.handle;
+        let action_result = if window.has_tag(current_tag) {
+            hide_scratchpad(manager, &handle)
+        } else {
+            tracing::debug!("Show window of class {:?} as scratchpad", scratchpad.class);
+            let ws = manager
+                .state
+                .focus_manager
+                .workspace(&manager.state.workspaces)?
+                .xyhw;
+            let window = manager
+                .state
+                .windows
+                .iter_mut()
+                .find(|w| w.handle == handle)?;
+            // Windows taken over from a tag are put in place like a new scratchpad window.
+            if !window.floating() {
+                window.set_floating(true);
+                window.normal = ws;
+                window.set_floating_exact(scratchpad.xyhw(&ws));
+            }
+            show_scratchpad(manager, &handle)
+        };
+        return match action_result {
+            Ok(()) => Some(true),
+            Err(msg) => {
+                tracing::error!("{}", msg);
+                Some(false)
+            }
+        };
+    }
+
     tracing::debug!(
         "no active scratchpad found for name {:?}. Creating a new one",
         scratchpad.name
@@ -269,6 +338,21 @@ pub fn toggle_scratchpad<C: Config, SERVER: DisplayServer>(
     None
 }
 
+/// Finds a window of `class`, preferring one shown on `tag`.
+fn find_window_of_class<'a>(
+    state: &'a State,
+    class: Option<&String>,
+    tag: TagId,
+) -> Option<&'a Window> {
+    let class = class?;
+    let mut windows = state
+        .windows
+        .iter()
+        .filter(|w| w.res_class.as_ref() == Some(class));
+    let first = windows.clone().next()?;
+    Some(windows.find(|w| w.has_tag(&tag)).unwrap_or(first))
+}
+
 /// Attaches the `WindowHandle` or the currently selected window to the selected `scratchpad`
 pub fn attach_scratchpad<C: Config, SERVER: DisplayServer>(
     window: Option<WindowHandle>,
@@ -352,6 +436,28 @@ pub fn attach_scratchpad<C: Config, SERVER: DisplayServer>(
     Some(true)
 }
 
+/// Saves the geometry of the currently focused window, relative to its workspace, as the geometry
+/// of the `scratchpad`. Returns `None` if the scratchpad isn't defined.
+pub fn save_scratchpad_geometry(state: &mut State, scratchpad: &ScratchPadName) -> Option<bool> {
+    if !state.scratchpads.iter().any(|s| &s.name == scratchpad) {
+        return None;
+    }
+    let ws = state.focus_manager.workspace(&state.workspaces)?.xyhw;
+    let exact = state.focus_manager.window(&state.windows)?.exact_xyhw();
+    let relative = XyhwBuilder {
+        x: exact.x() - ws.x(),
+        y: exact.y() - ws.y(),
+        h: exact.h(),
+        w: exact.w(),
+        ..XyhwBuilder::default()
+    };
+    state
+        .scratchpad_geometries
+        .insert(scratchpad.clone(), relative.into());
+    state.apply_scratchpad_geometries();
+    Some(false)
+}
+
 /// Release a scratchpad to become a normal window. When tag is None, use current active tag as the
 /// destination. Window can be a handle to select a specific window, the name of a scratchpad or
 /// none to select the current window.
@@ -517,15 +623,19 @@ pub fn cycle_scratchpad_window<C: Config, SERVER: DisplayServer>(
         return Some(false);
     }
 
-    // Communicate changes to the rest of manager
-    manager.state.sort_windows();
-
     Some(true)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{config::ScratchPad, models::ScratchPadName};
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::handlers::window_handler::is_scratchpad;
+    use crate::{
+        config::ScratchPad,
+        models::{ScratchPadEdge, ScratchPadName},
+        DisplayEvent,
+    };
 
     use super::*;
 
@@ -533,7 +643,7 @@ mod tests {
     fn show_scratchpad_test() {
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
         manager.screen_create_handler(Default::default());
-        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
         let first_tag = manager.state.tags.get(1).unwrap().id;
 
         let mock_window = 1_u32;
@@ -568,7 +678,7 @@ mod tests {
     fn hide_scratchpad_test() {
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
         manager.screen_create_handler(Default::default());
-        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
         let first_tag = manager.state.tags.get(1).unwrap().id;
 
         let mock_window = 1_u32;
@@ -603,7 +713,7 @@ mod tests {
     fn toggle_scratchpad_test() {
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
         manager.screen_create_handler(Default::default());
-        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
 
         let mock_window = 1_u32;
         let window_handle = WindowHandle::MockHandle(mock_window as i32);
@@ -616,6 +726,9 @@ mod tests {
             y: None,
             height: None,
             width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         });
         manager
             .state
@@ -712,7 +825,7 @@ mod tests {
     fn release_scratchpad_multiple_windows_test() {
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
         manager.screen_create_handler(Default::default());
-        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
 
         // Setup
         let mock_window1 = 1_u32;
@@ -776,7 +889,7 @@ mod tests {
     fn attach_scratchpad_test() {
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
         manager.screen_create_handler(Default::default());
-        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
 
         // Setup
         let mock_window1 = 1_u32;
@@ -790,6 +903,9 @@ mod tests {
             y: None,
             height: None,
             width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         });
         manager.state.active_scratchpads.insert(
             scratchpad_name.clone(),
@@ -946,7 +1062,7 @@ mod tests {
 
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
         manager.screen_create_handler(Default::default());
-        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
 
         // Setup
         let mock_window1 = 1_u32;
@@ -973,6 +1089,9 @@ mod tests {
             y: None,
             height: None,
             width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         });
         manager.state.active_scratchpads.insert(
             scratchpad_name.clone(),
@@ -1082,6 +1201,9 @@ mod tests {
             y: None,
             height: None,
             width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         });
         manager
             .state
@@ -1192,6 +1314,9 @@ mod tests {
             y: None,
             height: None,
             width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         });
         manager
             .state
@@ -1256,6 +1381,9 @@ mod tests {
             y: None,
             height: None,
             width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         });
         manager
             .state
@@ -1299,4 +1427,210 @@ mod tests {
             assert_eq!(window.border(), 0);
         }
     }
+
+    fn hide_on_focus_loss_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let scratchpad_name: ScratchPadName = "Alacritty".into();
+        manager.state.scratchpads.push(ScratchPad {
+            name: scratchpad_name.clone(),
+            value: String::new(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+            hide_on_focus_loss: true,
+            class: None,
+            position: ScratchPadEdge::Center,
+        });
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, Some(2)),
+            -1,
+            -1,
+        );
+        manager
+            .state
+            .active_scratchpads
+            .insert(scratchpad_name, VecDeque::from([1]));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, Some(1)),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        manager
+    }
+
+    #[test]
+    fn scratchpad_hides_when_it_loses_the_focus() {
+        let mut manager = hide_on_focus_loss_manager();
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
+        let scratchpad = WindowHandle::MockHandle(1);
+
+        assert!(manager
+            .display_event_handler(DisplayEvent::WindowTakeFocus(WindowHandle::MockHandle(2))));
+
+        let window = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == scratchpad)
+            .unwrap();
+        assert!(window.has_tag(&nsp_tag));
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetWindowTag(h, Some(tag)) if *h == scratchpad && *tag == nsp_tag
+        )));
+    }
+
+    #[test]
+    fn scratchpad_stays_visible_while_its_dialog_is_focused() {
+        let mut manager = hide_on_focus_loss_manager();
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
+        let scratchpad = WindowHandle::MockHandle(1);
+        let mut dialog = Window::new(WindowHandle::MockHandle(3), None, None);
+        dialog.transient = Some(scratchpad);
+        manager.window_created_handler(dialog, -1, -1);
+
+        manager.display_event_handler(DisplayEvent::WindowTakeFocus(WindowHandle::MockHandle(3)));
+
+        let window = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == scratchpad)
+            .unwrap();
+        assert!(!window.has_tag(&nsp_tag));
+    }
+
+    #[test]
+    fn scratchpads_are_hidden_on_the_configured_tag() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["NSP".to_string()],
+            scratchpad_tag_label: Some("hidden".to_string()),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Default::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, Some(1)), -1, -1);
+
+        hide_scratchpad(&mut manager, &handle).unwrap();
+
+        let hidden_tag = manager.state.tags.get_hidden_by_label("hidden").unwrap().id;
+        assert_eq!(manager.state.scratchpad_tag(), Some(hidden_tag));
+        assert!(manager.state.tags.get_hidden_by_label("NSP").is_none());
+        assert_eq!(manager.state.windows[0].tag, Some(hidden_tag));
+    }
+
+    fn scratchpad_named(name: &str) -> ScratchPad {
+        ScratchPad {
+            name: name.into(),
+            value: "scratchpad".to_string(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
+        }
+    }
+
+    fn scratchpad_of_class(class: &str) -> ScratchPad {
+        ScratchPad {
+            class: Some(class.to_string()),
+            ..scratchpad_named("Term")
+        }
+    }
+
+    fn window_of_class(id: i32, class: &str, pid: Option<u32>) -> Window {
+        let mut window = Window::new(WindowHandle::MockHandle(id), None, pid);
+        window.res_class = Some(class.to_string());
+        window
+    }
+
+    fn window_by_id(manager: &Manager<TestConfig, MockDisplayServer>, id: i32) -> &Window {
+        let handle = WindowHandle::MockHandle(id);
+        manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == handle)
+            .unwrap()
+    }
+
+    #[test]
+    fn toggling_a_scratchpad_takes_over_a_window_of_its_class() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        manager.window_created_handler(window_of_class(1, "Other", Some(1)), -1, -1);
+        // Windows without a process are found by their class as well.
+        manager.window_created_handler(window_of_class(2, "Term", None), -1, -1);
+        manager.state.scratchpads.push(scratchpad_of_class("Term"));
+        manager.state.goto_tag_handler(2);
+
+        let name: ScratchPadName = "Term".into();
+        assert!(manager.command_handler(&Command::ToggleScratchPad(name.clone())));
+        assert!(manager.state.active_scratchpads.is_empty());
+        let window = window_by_id(&manager, 2);
+        assert!(window.floating());
+        assert!(window.has_tag(&2));
+
+        // From now on the window is toggled like any other scratchpad window.
+        assert!(manager.command_handler(&Command::ToggleScratchPad(name.clone())));
+        let nsp_tag = manager.state.scratchpad_tag().unwrap();
+        assert!(window_by_id(&manager, 2).has_tag(&nsp_tag));
+        assert!(manager.command_handler(&Command::ToggleScratchPad(name)));
+        assert!(window_by_id(&manager, 2).has_tag(&2));
+        assert!(!window_by_id(&manager, 1).has_tag(&2));
+    }
+
+    #[test]
+    fn new_windows_of_the_class_of_a_scratchpad_join_it() {
+        let mut manager = Manager::new_test(vec!["AO".to_string()]);
+        manager.screen_create_handler(Default::default());
+        manager.state.scratchpads.push(scratchpad_of_class("Term"));
+        // Both windows belong to the same process, only the one of the class is a scratchpad.
+        manager.window_created_handler(window_of_class(1, "Other", Some(7)), -1, -1);
+        manager.window_created_handler(window_of_class(2, "Term", Some(7)), -1, -1);
+
+        assert!(manager.state.active_scratchpads.is_empty());
+        assert!(window_by_id(&manager, 2).floating());
+        assert!(!window_by_id(&manager, 1).floating());
+        assert!(!is_scratchpad(&manager.state, window_by_id(&manager, 1)));
+    }
+
+    #[test]
+    fn saving_the_geometry_of_a_scratchpad_reuses_the_window_geometry() {
+        let mut manager = Manager::new_test(vec!["AO".to_string()]);
+        manager.screen_create_handler(Default::default());
+        manager
+            .state
+            .scratchpads
+            .push(scratchpad_named("Alacritty"));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        let geometry = XyhwBuilder {
+            x: 100,
+            y: 50,
+            h: 200,
+            w: 300,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        let window = &mut manager.state.windows[0];
+        window.set_floating(true);
+        window.set_floating_exact(geometry);
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+
+        assert!(!manager.command_handler(&Command::SaveScratchPadGeometry {
+            name: "Alacritty".into()
+        }));
+
+        let ws = manager.state.workspaces[0].xyhw;
+        assert_eq!(manager.state.scratchpads[0].xyhw(&ws), geometry);
+    }
 }
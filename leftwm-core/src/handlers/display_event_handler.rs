@@ -8,16 +8,22 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /// Process a collection of events, and apply them changes to a manager.
     /// Returns true if changes need to be rendered.
     pub fn display_event_handler(&mut self, event: DisplayEvent) -> bool {
+        let focused = self.state.focus_manager.window_history.front().copied();
         let state = &mut self.state;
-        match event {
+        let changed = match event {
             DisplayEvent::ScreenCreate(s) => self.screen_create_handler(s),
             DisplayEvent::WindowCreate(w, x, y) => self.window_created_handler(w, x, y),
             DisplayEvent::WindowChange(w) => self.window_changed_handler(w),
             DisplayEvent::WindowDestroy(handle) => self.window_destroyed_handler(&handle),
             DisplayEvent::SendCommand(command) => self.command_handler(&command),
-            DisplayEvent::MouseCombo(mod_mask, button, handle, x, y) => self
-                .state
-                .mouse_combo_handler(mod_mask, button, handle, x, y),
+            DisplayEvent::MouseCombo(mod_mask, button, handle, x, y) => {
+                if let Some(command) = self.state.mousebind_command(mod_mask, button) {
+                    self.command_handler(&command)
+                } else {
+                    self.state
+                        .mouse_combo_handler(mod_mask, button, handle, x, y)
+                }
+            }
 
             DisplayEvent::WindowTakeFocus(handle) => from_window_take_focus(state, handle),
             DisplayEvent::HandleWindowFocus(handle) => from_handle_window_focus(state, handle),
@@ -28,7 +34,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             DisplayEvent::MoveWindow(handle, x, y) => from_move_window(self, handle, x, y),
             DisplayEvent::ResizeWindow(handle, x, y) => from_resize_window(self, handle, x, y),
             DisplayEvent::ConfigureXlibWindow(handle) => from_configure_xlib_window(state, handle),
-        }
+        };
+        let hidden = self
+            .hide_scratchpad_on_focus_loss(focused.flatten())
+            .unwrap_or(false);
+        changed || hidden
     }
 }
 
@@ -3,7 +3,10 @@
 use super::*;
 use crate::models::TagId;
 use crate::state::State;
-use crate::{display_action::DisplayAction, models::FocusBehaviour};
+use crate::{
+    display_action::DisplayAction,
+    models::{EmptyTagFocus, FocusBehaviour},
+};
 
 impl State {
     /// Focuses a window based upon the `FocusBehaviour`
@@ -34,8 +37,8 @@ impl State {
             let _ = self.focus_workspace_work(workspace_id);
         }
 
-        // Make sure the focused window's tag is focused.
-        if let Some(tag) = window.tag {
+        // Make sure the focused window's tag is focused, the overlay is shown above it.
+        if let Some(tag) = window.tag.filter(|tag| Some(*tag) != self.overlay_tag()) {
             let _ = self.focus_tag_work(tag);
         }
     }
@@ -78,22 +81,16 @@ impl State {
         {
             let act = DisplayAction::FocusWindowUnderCursor;
             self.actions.push_back(act);
-        } else if let Some(handle) = self.focus_manager.tags_last_window.get(tag).copied() {
+        } else if let Some(handle) = self.window_to_focus_on_tag(*tag) {
             self.focus_window_work(&handle);
-        } else if let Some(ws) = to_focus.first() {
-            let handle = self
-                .windows
-                .iter()
-                .find(|w| ws.is_managed(w))
-                .map(|w| w.handle);
-            if let Some(h) = handle {
-                self.focus_window_work(&h);
-            }
         }
 
-        // Unfocus last window if the target tag is empty
+        // Unfocus last window if the target tag is empty, unless it should keep the focus.
+        if self.focus_manager.focus_on_empty_tag == EmptyTagFocus::KeepLast {
+            return;
+        }
         if let Some(window) = self.focus_manager.window(&self.windows) {
-            if window.tag != Some(*tag) {
+            if !window.has_tag(tag) {
                 self.unfocus_current_window();
             }
         }
@@ -168,6 +165,15 @@ impl State {
         }
     }
 
+    /// The last focused window of a tag if it is still on it, otherwise its first window.
+    fn window_to_focus_on_tag(&self, tag: TagId) -> Option<WindowHandle> {
+        let last = self.focus_manager.tags_last_window.get(&tag);
+        self.windows_on_tag(tag)
+            .find(|w| Some(&w.handle) == last)
+            .or_else(|| self.windows_on_tag(tag).next())
+            .map(|w| w.handle)
+    }
+
     fn focus_tag_work(&mut self, tag: TagId) -> bool {
         if let Some(current_tag) = self.focus_manager.tag(0) {
             if current_tag == tag {
@@ -211,18 +217,27 @@ impl State {
             }
         }
 
+        let previous = previous.cloned();
+
         // Clean old history.
         self.focus_manager.window_history.truncate(10);
         // Add this focus change to the history.
         self.focus_manager.window_history.push_front(Some(*handle));
 
+        // Focusing a window takes care of its urgency.
+        self.urgent_windows.retain(|h| h != handle);
+        let found = self.windows.iter_mut().find(|w| &w.handle == handle)?;
+        found.urgent = false;
+        found.demands_attention = false;
+        let found = found.clone();
+
         let act = DisplayAction::WindowTakeFocus {
             window: found.clone(),
-            previous_window: previous.cloned(),
+            previous_window: previous,
         };
         self.actions.push_back(act);
 
-        Some(found.clone())
+        Some(found)
     }
 
     fn focus_workspace_work(&mut self, workspace_id: Option<i32>) -> bool {
@@ -242,7 +257,7 @@ impl State {
         false
     }
 
-    fn unfocus_current_window(&mut self) {
+    pub(crate) fn unfocus_current_window(&mut self) {
         if let Some(window) = self.focus_manager.window(&self.windows) {
             self.actions.push_back(DisplayAction::Unfocus(
                 Some(window.handle),
@@ -270,6 +285,8 @@ fn distance(window: &Window, x: i32, y: i32) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
     use crate::Manager;
 
     #[test]
@@ -477,4 +494,98 @@ mod tests {
         let focused = manager.state.focus_manager.window(&manager.state.windows);
         assert!(focused.is_none());
     }
+
+    fn empty_tag_manager(
+        focus_on_empty_tag: EmptyTagFocus,
+    ) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            focus_on_empty_tag,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        manager.state.actions.clear();
+        manager
+    }
+
+    #[test]
+    fn switching_to_an_empty_tag_clears_the_focus() {
+        let mut manager = empty_tag_manager(EmptyTagFocus::Clear);
+        manager.state.goto_tag_handler(2);
+
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert!(focused.is_none());
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::Unfocus(Some(WindowHandle::MockHandle(1)), _)
+        )));
+    }
+
+    #[test]
+    fn switching_to_an_empty_tag_can_keep_the_last_focused_window() {
+        let mut manager = empty_tag_manager(EmptyTagFocus::KeepLast);
+        manager.state.goto_tag_handler(2);
+
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.map(|w| w.handle), Some(WindowHandle::MockHandle(1)));
+        assert!(!manager
+            .state
+            .actions
+            .iter()
+            .any(|a| matches!(a, DisplayAction::Unfocus(..))));
+    }
+
+    fn focused(manager: &Manager<TestConfig, MockDisplayServer>) -> Option<WindowHandle> {
+        manager
+            .state
+            .focus_manager
+            .window(&manager.state.windows)
+            .map(|w| w.handle)
+    }
+
+    #[test]
+    fn switching_back_to_a_tag_focuses_its_last_focused_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+
+        manager.state.goto_tag_handler(2);
+        assert_eq!(focused(&manager), None);
+        manager.state.goto_tag_handler(1);
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(2)));
+    }
+
+    #[test]
+    fn switching_back_to_a_tag_falls_back_to_its_first_window() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        manager.state.goto_tag_handler(2);
+        // The remembered window leaves the tag while it is hidden.
+        manager.state.windows[1].tag(&3);
+
+        manager.state.goto_tag_handler(1);
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(1)));
+    }
 }
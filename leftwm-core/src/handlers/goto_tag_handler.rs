@@ -1,4 +1,8 @@
-use crate::{models::TagId, state::State};
+use crate::{
+    models::{TagId, WindowHandle},
+    state::State,
+    DisplayAction,
+};
 
 impl State {
     pub fn goto_tag_handler(&mut self, tag_num: TagId) -> Option<bool> {
@@ -22,17 +26,59 @@ impl State {
         }
 
         self.focus_manager.workspace_mut(&mut self.workspaces)?.tag = new_tag;
+        if self.follow_modal_dialogs {
+            self.move_modal_dialogs(old_tag, tag_num);
+        }
         self.focus_tag(&tag_num);
         self.update_static();
         self.layout_manager
             .update_layouts(&mut self.workspaces, self.tags.all_mut());
         Some(true)
     }
+
+    /// Moves the modal dialogs of a tag to another one, remembering where they came from.
+    fn move_modal_dialogs(&mut self, from: TagId, to: TagId) {
+        for window in self
+            .windows
+            .iter_mut()
+            .filter(|w| w.has_tag(&from) && w.is_modal_dialog())
+        {
+            self.modal_dialog_tags
+                .entry(window.handle)
+                .or_insert(window.tag);
+            window.tag = Some(to);
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.actions.push_back(act);
+        }
+    }
+
+    /// Sends a dialog that stopped being modal back to the tag it came from.
+    /// Returns true if the dialog was moved.
+    pub(crate) fn restore_modal_dialog_tag(&mut self, handle: &WindowHandle) -> bool {
+        if !self.modal_dialog_tags.contains_key(handle) {
+            return false;
+        }
+        let window = match self.windows.iter_mut().find(|w| &w.handle == handle) {
+            Some(window) if window.is_modal_dialog() => return false,
+            Some(window) => window,
+            None => {
+                self.modal_dialog_tags.remove(handle);
+                return false;
+            }
+        };
+        window.tag = self.modal_dialog_tags.remove(handle).flatten();
+        let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+        self.actions.push_back(act);
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::models::Screen;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
+    use crate::models::{LayoutMode, Screen, Window, WindowChange, WindowHandle, WindowState};
     use crate::Manager;
 
     #[test]
@@ -44,4 +90,85 @@ mod tests {
         assert_eq!(manager.state.workspaces[0].tag, Some(2));
         assert_eq!(manager.state.workspaces[1].tag, Some(1));
     }
+
+    #[test]
+    fn modal_dialogs_follow_the_focused_tag_when_enabled() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.state.follow_modal_dialogs = true;
+        manager.screen_create_handler(Screen::default());
+        let parent = WindowHandle::MockHandle(1);
+        let dialog = WindowHandle::MockHandle(2);
+        manager.window_created_handler(Window::new(parent, None, None), -1, -1);
+        let mut modal = Window::new(dialog, None, None);
+        modal.transient = Some(parent);
+        modal.set_states(vec![WindowState::Modal]);
+        manager.window_created_handler(modal, -1, -1);
+
+        manager.state.goto_tag_handler(2);
+        let tag_of = |m: &Manager<_, _>, h| m.state.windows.iter().find(|w| w.handle == h)?.tag;
+        assert_eq!(tag_of(&manager, parent), Some(1));
+        assert_eq!(tag_of(&manager, dialog), Some(2));
+
+        let mut change = WindowChange::new(dialog);
+        change.states = Some(vec![]);
+        manager.window_changed_handler(change);
+        assert_eq!(tag_of(&manager, dialog), Some(1));
+        assert!(manager.state.modal_dialog_tags.is_empty());
+    }
+
+    #[test]
+    fn modal_dialogs_stay_on_their_tag_by_default() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let parent = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(parent, None, None), -1, -1);
+        let mut modal = Window::new(WindowHandle::MockHandle(2), None, None);
+        modal.transient = Some(parent);
+        modal.set_states(vec![WindowState::Modal]);
+        manager.window_created_handler(modal, -1, -1);
+
+        manager.state.goto_tag_handler(2);
+        assert!(manager.state.windows.iter().all(|w| w.has_tag(&1)));
+    }
+
+    #[test]
+    fn default_layouts_are_applied_the_first_time_a_tag_is_shown() {
+        let tags = vec!["dev".to_string(), "chat".to_string(), "web".to_string()];
+        let default_layouts = [("chat".to_string(), Layout::Monocle)].into();
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags,
+            layout_mode: LayoutMode::Tag,
+            default_layouts: Some(default_layouts),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        let global_default = manager.state.layout_manager.new_layout(None);
+        assert_eq!(manager.state.workspaces[0].layout, global_default);
+
+        manager.state.goto_tag_handler(2);
+        assert_eq!(manager.state.workspaces[0].layout, Layout::Monocle);
+        assert_eq!(manager.state.tags.get(2).unwrap().layout, Layout::Monocle);
+
+        manager.state.goto_tag_handler(3);
+        assert_eq!(manager.state.workspaces[0].layout, global_default);
+    }
+
+    #[test]
+    fn default_layouts_do_not_override_runtime_changes() {
+        let tags = vec!["dev".to_string(), "chat".to_string()];
+        let default_layouts = [("chat".to_string(), Layout::Monocle)].into();
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags,
+            layout_mode: LayoutMode::Tag,
+            default_layouts: Some(default_layouts),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.state.goto_tag_handler(2);
+        manager.state.workspaces[0].layout = Layout::Fibonacci;
+        manager.state.tags.get_mut(2).unwrap().layout = Layout::Fibonacci;
+        manager.state.goto_tag_handler(1);
+        manager.state.goto_tag_handler(2);
+        assert_eq!(manager.state.workspaces[0].layout, Layout::Fibonacci);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::command::Command;
 use crate::display_action::DisplayAction;
 use crate::models::Mode;
 use crate::models::WindowHandle;
@@ -17,7 +18,12 @@ impl State {
         y: i32,
     ) -> bool {
         if let Some(window) = self.windows.iter().find(|w| w.handle == handle) {
-            if !self.disable_tile_drag || window.floating() {
+            let layout = self
+                .focus_manager
+                .workspace(&self.workspaces)
+                .map(|ws| ws.layout)
+                .unwrap_or_default();
+            if !self.disable_tile_drag.is_disabled_for(&layout) || window.floating() {
                 let modifier = utils::modmask_lookup::into_modmask(&self.mousekey);
                 // Build the display to say whether we are ready to move/resize.
                 let act = self.build_action(modmask, button, handle, modifier);
@@ -37,6 +43,14 @@ impl State {
         true
     }
 
+    /// Returns the command of the mousebind for the button pressed with the modifiers.
+    pub(crate) fn mousebind_command(&self, modmask: ModMask, button: Button) -> Option<Command> {
+        self.mousebinds
+            .iter()
+            .find(|bind| bind.modmask == modmask && bind.button == button)
+            .map(|bind| bind.command.clone())
+    }
+
     fn build_action(
         &mut self,
         mod_mask: ModMask,
@@ -70,3 +84,80 @@ impl State {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::config::{DisableTileDrag, Mousebind};
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
+    use crate::models::{Screen, Window};
+    use crate::{DisplayEvent, Manager};
+
+    fn drag_tiled_window(layout: Layout) -> bool {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            disable_tile_drag: DisableTileDrag::Layouts(vec![Layout::Monocle]),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.state.workspaces[0].layout = layout;
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.actions.clear();
+        manager.state.mouse_combo_handler(
+            xlib::Mod4Mask,
+            xlib::Button1,
+            WindowHandle::MockHandle(1),
+            0,
+            0,
+        );
+        manager
+            .state
+            .actions
+            .iter()
+            .any(|a| matches!(a, DisplayAction::ReadyToMoveWindow(_)))
+    }
+
+    #[test]
+    fn tiled_windows_can_be_dragged_in_layouts_not_disabled() {
+        assert!(drag_tiled_window(Layout::MainAndVertStack));
+    }
+
+    #[test]
+    fn tiled_windows_can_not_be_dragged_in_disabled_layouts() {
+        assert!(!drag_tiled_window(Layout::Monocle));
+    }
+
+    #[test]
+    fn bound_buttons_run_their_command() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            mousebinds: vec![Mousebind {
+                modmask: xlib::Mod4Mask,
+                button: xlib::Button4,
+                command: Command::FocusNextTag,
+            }],
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        let scroll = |modmask| {
+            DisplayEvent::MouseCombo(modmask, xlib::Button4, WindowHandle::MockHandle(1), 0, 0)
+        };
+
+        manager.display_event_handler(scroll(xlib::ControlMask));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+
+        manager.display_event_handler(scroll(xlib::Mod4Mask));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+    }
+}
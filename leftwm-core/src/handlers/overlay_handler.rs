@@ -0,0 +1,153 @@
+use crate::{
+    models::{TagId, Window},
+    state::State,
+    DisplayAction,
+};
+
+impl State {
+    /// The hidden tag holding the windows shown above the focused workspace by `ToggleOverlay`.
+    #[must_use]
+    pub fn overlay_tag(&self) -> Option<TagId> {
+        self.tags
+            .get_hidden_by_label(&self.overlay_tag_label)
+            .map(|tag| tag.id)
+    }
+
+    /// Returns true if the window is on the overlay while it is shown.
+    #[must_use]
+    pub fn is_on_shown_overlay(&self, window: &Window) -> bool {
+        self.overlay_shown && window.tag.is_some() && window.tag == self.overlay_tag()
+    }
+
+    /// Shows the windows of the overlay above the focused workspace, whatever tag it shows,
+    /// or hides them again.
+    pub fn toggle_overlay(&mut self) -> Option<bool> {
+        let overlay = self.overlay_tag()?;
+        self.overlay_shown = !self.overlay_shown;
+        let focused = self.focus_manager.window(&self.windows);
+        if !self.overlay_shown && matches!(focused, Some(w) if w.has_tag(&overlay)) {
+            // Give the focus back to the windows of the focused tag.
+            let last = self
+                .focus_manager
+                .tag(0)
+                .and_then(|tag| self.focus_manager.tags_last_window.get(&tag).copied());
+            match last {
+                Some(handle) => self.handle_window_focus(&handle),
+                None => self.unfocus_current_window(),
+            }
+        }
+        self.sort_windows();
+        Some(true)
+    }
+
+    /// Moves the focused window onto the overlay, or from the overlay to the focused tag.
+    pub fn toggle_window_overlay(&mut self) -> Option<bool> {
+        let overlay = self.overlay_tag()?;
+        let tag = self.focus_manager.tag(0)?;
+        let window = self.focus_manager.window_mut(&mut self.windows)?;
+        let new_tag = if window.has_tag(&overlay) {
+            tag
+        } else {
+            overlay
+        };
+        window.untag();
+        window.tag(&new_tag);
+        let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+        self.actions.push_back(act);
+        self.sort_windows();
+        Some(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{Screen, Window, WindowHandle};
+    use crate::Manager;
+
+    fn overlay_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        // Window 3 goes on the overlay.
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(3));
+        assert_eq!(manager.state.toggle_window_overlay(), Some(true));
+        manager
+    }
+
+    fn visible(manager: &Manager<TestConfig, MockDisplayServer>) -> Vec<WindowHandle> {
+        manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| w.visible())
+            .map(|w| w.handle)
+            .collect()
+    }
+
+    #[test]
+    fn overlay_windows_are_shown_above_any_tag() {
+        let mut manager = overlay_manager();
+        let overlay_window = WindowHandle::MockHandle(3);
+        manager.update_windows();
+        assert!(!visible(&manager).contains(&overlay_window));
+
+        assert_eq!(manager.state.toggle_overlay(), Some(true));
+        manager.update_windows();
+        assert_eq!(manager.state.windows[0].handle, overlay_window);
+        assert!(visible(&manager).contains(&overlay_window));
+        assert_eq!(
+            manager.state.windows[0].normal,
+            manager.state.workspaces[0].xyhw
+        );
+
+        // Showing another tag keeps the overlay on top.
+        manager.state.goto_tag_handler(2);
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![overlay_window]);
+        assert_eq!(manager.state.windows[0].handle, overlay_window);
+
+        assert_eq!(manager.state.toggle_overlay(), Some(true));
+        manager.update_windows();
+        assert!(visible(&manager).is_empty());
+    }
+
+    #[test]
+    fn windows_move_between_the_overlay_and_the_focused_tag() {
+        let mut manager = overlay_manager();
+        let overlay = manager.state.overlay_tag();
+        assert!(overlay.is_some());
+        let tag_of_3 = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let handle = WindowHandle::MockHandle(3);
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == handle)
+                .and_then(|w| w.tag)
+        };
+        assert_eq!(tag_of_3(&manager), overlay);
+
+        manager.state.toggle_overlay();
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(3));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+        assert_eq!(manager.state.toggle_window_overlay(), Some(true));
+        let window = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(3));
+        assert_eq!(window.unwrap().tag, Some(1));
+    }
+}
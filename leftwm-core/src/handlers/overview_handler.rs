@@ -0,0 +1,169 @@
+use crate::{
+    layouts::Layout,
+    models::{Overview, TagId},
+    state::State,
+    DisplayAction,
+};
+use std::collections::HashMap;
+
+impl State {
+    /// Gathers the windows of all tags in a grid on the focused tag, or sends them back.
+    pub fn toggle_overview(&mut self) -> Option<bool> {
+        if self.overview.is_some() {
+            return self.hide_overview();
+        }
+        let tag_id = self.focus_manager.tag(0)?;
+        let layout = self.focus_manager.workspace(&self.workspaces)?.layout;
+        let grid_columns = self.tags.get(tag_id)?.grid_columns;
+
+        let tags = &self.tags;
+        let mut window_tags = HashMap::new();
+        for window in self.windows.iter_mut().filter(|w| {
+            let on_hidden_tag = w.tag.and_then(|t| tags.get(t)).map(|t| t.hidden);
+            w.is_managed() && w.tag != Some(tag_id) && on_hidden_tag == Some(false)
+        }) {
+            window_tags.insert(window.handle, window.tag);
+            window.tag = Some(tag_id);
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.actions.push_back(act);
+        }
+
+        let tiled = self
+            .windows_on_tag(tag_id)
+            .filter(|w| !w.floating())
+            .count();
+        let columns = (tiled as f32).sqrt().ceil().max(1.0) as usize;
+        self.set_overview_layout(tag_id, Layout::Grid, columns);
+        self.overview = Some(Overview {
+            tag: tag_id,
+            layout,
+            grid_columns,
+            window_tags,
+        });
+        self.sort_windows();
+        Some(true)
+    }
+
+    /// Sends the gathered windows back to their tags and shows the tag of the selected one.
+    fn hide_overview(&mut self) -> Option<bool> {
+        let overview = self.overview.take()?;
+        let selected = self.focus_manager.window(&self.windows).map(|w| w.handle);
+
+        for window in &mut self.windows {
+            if let Some(tag) = overview.window_tags.get(&window.handle) {
+                window.tag = *tag;
+                let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+                self.actions.push_back(act);
+            }
+        }
+        self.set_overview_layout(overview.tag, overview.layout, overview.grid_columns);
+        self.sort_windows();
+
+        if let Some(handle) = selected {
+            if let Some(Some(tag)) = overview.window_tags.get(&handle) {
+                self.goto_tag_handler(*tag);
+                self.handle_window_focus(&handle);
+            }
+        }
+        Some(true)
+    }
+
+    fn set_overview_layout(&mut self, tag_id: TagId, layout: Layout, grid_columns: usize) {
+        for workspace in self
+            .workspaces
+            .iter_mut()
+            .filter(|ws| ws.tag == Some(tag_id))
+        {
+            workspace.layout = layout;
+        }
+        if let Some(tag) = self.tags.get_mut(tag_id) {
+            tag.set_layout(layout, tag.main_width_percentage);
+            tag.grid_columns = grid_columns;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
+    use crate::models::{Screen, TagId, Window, WindowHandle};
+    use crate::Manager;
+    use std::collections::HashMap;
+
+    fn overview_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for (id, tag) in [(1, 1), (2, 2), (3, 3), (4, 3)] {
+            let handle = WindowHandle::MockHandle(id);
+            manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+            let window = manager
+                .state
+                .windows
+                .iter_mut()
+                .find(|w| w.handle == handle);
+            window.unwrap().tag = Some(tag);
+        }
+        manager
+    }
+
+    fn window_tags(
+        manager: &Manager<TestConfig, MockDisplayServer>,
+    ) -> HashMap<WindowHandle, Option<TagId>> {
+        manager
+            .state
+            .windows
+            .iter()
+            .map(|w| (w.handle, w.tag))
+            .collect()
+    }
+
+    #[test]
+    fn overview_gathers_all_windows_and_sends_them_back() {
+        let mut manager = overview_manager();
+        let before = window_tags(&manager);
+        let layout = manager.state.workspaces[0].layout;
+        manager.state.tags.get_mut(1).unwrap().grid_columns = 5;
+
+        assert_eq!(manager.state.toggle_overview(), Some(true));
+        assert!(manager.state.windows.iter().all(|w| w.has_tag(&1)));
+        assert_eq!(manager.state.workspaces[0].layout, Layout::Grid);
+        assert_eq!(manager.state.tags.get(1).unwrap().grid_columns, 2);
+
+        assert_eq!(manager.state.toggle_overview(), Some(true));
+        assert_eq!(window_tags(&manager), before);
+        assert_eq!(manager.state.workspaces[0].layout, layout);
+        assert_eq!(manager.state.tags.get(1).unwrap().grid_columns, 5);
+        assert!(manager.state.overview.is_none());
+    }
+
+    #[test]
+    fn leaving_the_overview_shows_the_tag_of_the_selected_window() {
+        let mut manager = overview_manager();
+        let before = window_tags(&manager);
+        let selected = WindowHandle::MockHandle(3);
+        manager.state.toggle_overview();
+        manager.state.handle_window_focus(&selected);
+
+        manager.state.toggle_overview();
+        assert_eq!(window_tags(&manager), before);
+        assert_eq!(manager.state.focus_manager.tag(0), Some(3));
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.map(|w| w.handle), Some(selected));
+    }
+
+    #[test]
+    fn closed_windows_are_forgotten_by_the_overview() {
+        let mut manager = overview_manager();
+        manager.state.toggle_overview();
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+
+        let overview = manager.state.overview.as_ref().unwrap();
+        assert!(!overview
+            .window_tags
+            .contains_key(&WindowHandle::MockHandle(2)));
+        assert_eq!(overview.window_tags.len(), 2);
+    }
+}
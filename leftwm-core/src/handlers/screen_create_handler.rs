@@ -7,7 +7,6 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     ///
     /// Returns `true` if changes need to be rendered.
     pub fn screen_create_handler(&mut self, screen: Screen) -> bool {
-        let tag_index = self.state.workspaces.len();
         let tag_len = self.state.tags.len_normal();
         let workspace_id = screen.wsid.unwrap_or_else(|| {
             self.state
@@ -27,11 +26,19 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         if new_workspace.id.unwrap_or(0) as usize >= tag_len {
             dbg!("Workspace ID needs to be less than or equal to the number of tags available.");
         }
+        new_workspace.no_new_windows = screen.no_new_windows;
+        new_workspace.always_float = screen.always_float;
+        new_workspace.scale = screen.scale;
         new_workspace.load_config(&self.config);
 
-        // Make sure there are enough tags for this new screen.
-        let next_id = if tag_len > tag_index {
-            tag_index + 1
+        // A free default tag is preferred, then the first free tag, otherwise make sure there
+        // are enough tags for this new screen.
+        let is_free = |tag: &usize| !self.state.workspaces.iter().any(|ws| ws.has_tag(tag));
+        let default_tag = screen
+            .default_tag
+            .filter(|tag| (1..=tag_len).contains(tag) && is_free(tag));
+        let next_id = if let Some(tag) = default_tag.or_else(|| (1..=tag_len).find(is_free)) {
+            tag
         } else {
             // Add a new tag for the workspace.
             self.state
@@ -40,6 +47,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         };
 
         if let Some(tag) = self.state.tags.get_mut(next_id) {
+            if let Some(layout) = self.state.layout_manager.take_default_layout(&tag.label) {
+                new_workspace.layout = layout;
+            }
             tag.layout = new_workspace.layout;
         }
 
@@ -105,4 +115,50 @@ mod tests {
         assert!(manager.state.workspaces[2].has_tag(&3));
         assert!(manager.state.workspaces[3].has_tag(&4));
     }
+
+    #[test]
+    fn screens_should_start_on_their_default_tag() {
+        let tags = (1..=9).map(|tag| tag.to_string()).collect();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen {
+            default_tag: Some(1),
+            ..Screen::default()
+        });
+        manager.screen_create_handler(Screen {
+            default_tag: Some(9),
+            ..Screen::default()
+        });
+        assert!(manager.state.workspaces[0].has_tag(&1));
+        assert!(manager.state.workspaces[1].has_tag(&9));
+    }
+
+    #[test]
+    fn unavailable_default_tags_should_be_ignored() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen {
+            default_tag: Some(1),
+            ..Screen::default()
+        });
+        manager.screen_create_handler(Screen {
+            default_tag: Some(1),
+            ..Screen::default()
+        });
+        manager.screen_create_handler(Screen {
+            default_tag: Some(7),
+            ..Screen::default()
+        });
+        assert!(manager.state.workspaces[0].has_tag(&1));
+        assert!(manager.state.workspaces[1].has_tag(&2));
+        assert!(manager.state.workspaces[2].has_tag(&3));
+
+        // The tag taken as the default of the first screen isn't used again by the second.
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen {
+            default_tag: Some(2),
+            ..Screen::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        let tags: Vec<_> = manager.state.workspaces.iter().map(|ws| ws.tag).collect();
+        assert_eq!(tags, vec![Some(2), Some(1)]);
+    }
 }
@@ -1,11 +1,11 @@
 use super::{Manager, Window, WindowChange, WindowType, Workspace};
 use crate::child_process::exec_shell;
-use crate::config::{Config, InsertBehavior};
+use crate::config::{Config, FloatingPlacement, InsertBehavior};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-use crate::models::{WindowHandle, WindowState, Xyhw};
-use crate::state::State;
+use crate::models::{FocusAfterLastClose, ScratchPadName, TagId, WindowHandle, WindowState, Xyhw};
+use crate::state::{Launch, State};
 use crate::utils::helpers;
 use std::env;
 use std::str::FromStr;
@@ -18,6 +18,10 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         if self.state.windows.iter().any(|w| w.handle == window.handle) {
             return false;
         }
+        // Override-redirect windows place themselves and must not be managed.
+        if window.override_redirect {
+            return false;
+        }
 
         // Setup any predifined hooks.
         self.config
@@ -26,6 +30,7 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         let mut on_same_tag = true;
         // Random value
         let mut layout: Layout = Layout::MainAndVertStack;
+        let mut always_float = None;
         setup_window(
             &mut self.state,
             &mut window,
@@ -33,11 +38,28 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             &mut layout,
             &mut is_first,
             &mut on_same_tag,
+            &mut always_float,
         );
         self.config.load_window(&mut window);
+        // The workspace may override the global `always_float`.
+        if let Some(always_float) = always_float {
+            window.must_float = always_float;
+        }
+        window.spawn_tag = window.tag;
         insert_window(&mut self.state, &mut window, layout);
 
+        // Make sure the workspaces do not draw on the docks.
+        if window.r#type == WindowType::Dock {
+            update_workspace_avoid_list(&mut self.state);
+        }
+
+        // Windows asking not to be focused are mapped without taking the focus.
+        // With focus stealing prevented, only the first window or one demanding attention is.
+        let steals_focus =
+            self.state.focus_manager.prevent_focus_stealing && !is_first && !window.urgent;
+        let take_focus = !window.never_focus && !steals_focus;
         let follow_mouse = self.state.focus_manager.focus_new_windows
+            && take_focus
             && self.state.focus_manager.behaviour.is_sloppy()
             && self.state.focus_manager.sloppy_mouse_follows_focus
             && on_same_tag;
@@ -53,9 +75,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 
         // Tell the WM to reevaluate the stacking order, so the new window is put in the correct layer
         self.state.sort_windows();
-        self.state.handle_single_border(self.config.border_width());
+        self.state.update_borders(&self.config);
 
-        if (self.state.focus_manager.focus_new_windows || is_first) && on_same_tag {
+        if (self.state.focus_manager.focus_new_windows || is_first) && on_same_tag && take_focus {
             self.state.focus_window(&window.handle);
         }
 
@@ -72,18 +94,43 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         // Find the next or previous window on the workspace.
         let new_handle = self.get_next_or_previous_handle(handle);
         // If there is a parent we would want to focus it.
-        let (transient, floating, visible) =
+        let (transient, floating, visible, app_tag, tag) =
             match self.state.windows.iter().find(|w| &w.handle == handle) {
-                Some(window) => (window.transient, window.floating(), window.visible()),
+                Some(window) => (
+                    window.transient,
+                    window.floating(),
+                    window.visible(),
+                    app_tag(&self.state, window),
+                    window.tag,
+                ),
                 None => return false,
             };
+        if let Some((class, tag)) = app_tag {
+            self.state.remember_app_tag(&class, tag);
+        }
         self.state
             .focus_manager
             .tags_last_window
             .retain(|_, h| h != handle);
         self.state.windows.retain(|w| &w.handle != handle);
+        self.state.modal_dialog_tags.remove(handle);
+        self.state.urgent_windows.retain(|h| h != handle);
+        self.state.marks.retain(|_, h| h != handle);
+        if let Some(overview) = &mut self.state.overview {
+            overview.window_tags.remove(handle);
+        }
+        if let Some(tag) = tag {
+            let is_empty = !self
+                .state
+                .windows
+                .iter()
+                .any(|w| w.has_tag(&tag) && w.is_managed());
+            if is_empty && self.config.reset_layout_on_empty() {
+                self.reset_layout(tag);
+            }
+        }
 
-        self.state.handle_single_border(self.config.border_width());
+        self.state.update_borders(&self.config);
 
         // Make sure the workspaces do not draw on the docks.
         update_workspace_avoid_list(&mut self.state);
@@ -91,21 +138,18 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         let focused = self.state.focus_manager.window_history.get(0);
         // Make sure focus is recalculated if we closed the currently focused window
         if focused == Some(&Some(*handle)) {
-            if self.state.focus_manager.behaviour.is_sloppy()
+            let parent = find_transient_parent(&self.state.windows, transient).map(|p| p.handle);
+            if parent.is_none() && new_handle.is_none() {
+                self.focus_after_last_close(handle, floating);
+            } else if self.state.focus_manager.behaviour.is_sloppy()
                 && self.state.focus_manager.sloppy_mouse_follows_focus
             {
                 let act = DisplayAction::FocusWindowUnderCursor;
                 self.state.actions.push_back(act);
-            } else if let Some(parent) =
-                find_transient_parent(&self.state.windows, transient).map(|p| p.handle)
-            {
+            } else if let Some(parent) = parent {
                 self.state.focus_window(&parent);
             } else if let Some(handle) = new_handle {
                 self.state.focus_window(&handle);
-            } else {
-                let act = DisplayAction::Unfocus(Some(*handle), floating);
-                self.state.actions.push_back(act);
-                self.state.focus_manager.window_history.push_front(None);
             }
         }
 
@@ -113,33 +157,104 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         visible
     }
 
+    /// Puts a tag, and the workspace showing it, back to the configured layout of the tag.
+    fn reset_layout(&mut self, tag_id: TagId) {
+        let workspace = self
+            .state
+            .workspaces
+            .iter_mut()
+            .find(|ws| ws.has_tag(&tag_id));
+        let label = self.state.tags.get(tag_id).map(|tag| tag.label.clone());
+        let layout = self
+            .config
+            .default_layouts()
+            .zip(label)
+            .and_then(|(mut layouts, label)| layouts.remove(&label))
+            .unwrap_or_else(|| {
+                let workspace_id = workspace.as_ref().and_then(|ws| ws.id);
+                self.state.layout_manager.new_layout(workspace_id)
+            });
+        if let Some(tag) = self.state.tags.get_mut(tag_id) {
+            tag.set_layout(layout, layout.main_width());
+        }
+        if let Some(workspace) = workspace {
+            workspace.layout = layout;
+            workspace.main_width_percentage = layout.main_width();
+        }
+    }
+
+    /// Moves the focus according to `focus_after_last_close`, once the last window is closed.
+    fn focus_after_last_close(&mut self, handle: &WindowHandle, floating: bool) {
+        let focus_manager = &self.state.focus_manager;
+        let follows_mouse =
+            focus_manager.behaviour.is_sloppy() && focus_manager.sloppy_mouse_follows_focus;
+        match focus_manager.focus_after_last_close {
+            FocusAfterLastClose::FollowFocusBehaviour if follows_mouse => {
+                let act = DisplayAction::FocusWindowUnderCursor;
+                self.state.actions.push_back(act);
+                return;
+            }
+            FocusAfterLastClose::UnderCursor => {
+                let act = DisplayAction::FocusWindowUnderCursor;
+                self.state.actions.push_back(act);
+                return;
+            }
+            FocusAfterLastClose::PreviousTag => {
+                if let Some(tag) = self.state.focus_manager.tag(1) {
+                    self.state.goto_tag_handler(tag);
+                    return;
+                }
+            }
+            FocusAfterLastClose::FollowFocusBehaviour | FocusAfterLastClose::None => {}
+        }
+        let act = DisplayAction::Unfocus(Some(*handle), floating);
+        self.state.actions.push_back(act);
+        self.state.focus_manager.window_history.push_front(None);
+    }
+
     pub fn window_changed_handler(&mut self, change: WindowChange) -> bool {
         let mut changed = false;
         let mut fullscreen_changed = false;
+        let mut stacking_changed = false;
+        let mut class_changed = false;
         let strut_changed = change.strut.is_some();
-        let windows = self.state.windows.clone();
-        if let Some(window) = self
+        let handle = change.handle;
+        // Resolve the area to center dialogs in before borrowing the window mutably.
+        let container = self
             .state
             .windows
-            .iter_mut()
-            .find(|w| w.handle == change.handle)
-        {
+            .iter()
+            .find(|w| w.handle == handle)
+            .and_then(|window| {
+                match find_transient_parent(&self.state.windows, window.transient) {
+                    Some(parent) => Some(parent.exact_xyhw()),
+                    None if window.r#type == WindowType::Dialog => self
+                        .state
+                        .workspaces
+                        .iter()
+                        .find(|ws| ws.tag == window.tag)
+                        .map(|ws| ws.xyhw),
+                    _ => None,
+                }
+            });
+        if let Some(window) = self.state.windows.iter_mut().find(|w| w.handle == handle) {
             if let Some(ref states) = change.states {
                 let change_contains = states.contains(&WindowState::Fullscreen);
                 fullscreen_changed = change_contains || window.is_fullscreen();
+                stacking_changed = states.contains(&WindowState::Above) != window.is_above()
+                    || states.contains(&WindowState::Below) != window.is_below();
             }
-            let container = match find_transient_parent(&windows, window.transient) {
-                Some(parent) => Some(parent.exact_xyhw()),
-                None if window.r#type == WindowType::Dialog => self
-                    .state
-                    .workspaces
-                    .iter()
-                    .find(|ws| ws.tag == window.tag)
-                    .map(|ws| ws.xyhw),
-                _ => None,
-            };
 
+            let class = (window.res_name.clone(), window.res_class.clone());
             changed = change.update(window, container);
+            class_changed = class != (window.res_name.clone(), window.res_class.clone());
+            let urgent = window.urgent;
+            let urgent_windows = &mut self.state.urgent_windows;
+            if !urgent {
+                urgent_windows.retain(|h| h != &handle);
+            } else if !urgent_windows.contains(&handle) {
+                urgent_windows.push(handle);
+            }
             if window.r#type == WindowType::Dock {
                 update_workspace_avoid_list(&mut self.state);
                 // Don't let changes from docks re-render the worker. This will result in an
@@ -160,24 +275,67 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 
             // Reorder windows.
             self.state.sort_windows();
+        } else if stacking_changed {
+            self.state.sort_windows();
         }
         if strut_changed {
             self.state.update_static();
         }
+        if class_changed && self.reapply_window_rules(&handle) {
+            changed = true;
+        }
+        if self.state.restore_modal_dialog_tag(&handle) {
+            changed = true;
+        }
         changed
     }
 
+    /// Apply the window rules again, for windows setting their `WM_CLASS` after being mapped.
+    /// Windows the user moved to another tag since are left alone.
+    /// Returns true if a rule was applied.
+    fn reapply_window_rules(&mut self, handle: &WindowHandle) -> bool {
+        let mut window = match self.state.windows.iter().find(|w| &w.handle == handle) {
+            Some(window) if window.tag == window.spawn_tag => window.clone(),
+            _ => return false,
+        };
+        if !self
+            .config
+            .setup_predefined_window(&mut self.state, &mut window)
+        {
+            return false;
+        }
+        window.spawn_tag = window.tag;
+        let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+        self.state.actions.push_back(act);
+        if let Some(w) = self.state.windows.iter_mut().find(|w| &w.handle == handle) {
+            *w = window;
+        }
+        true
+    }
+
     /// Find the next or previous window on the currently focused workspace.
+    /// Visible windows are preferred over hidden ones (e.g. in `Monocle`).
     /// May return `None` if no other window is present.
     pub fn get_next_or_previous_handle(&mut self, handle: &WindowHandle) -> Option<WindowHandle> {
         let focused_workspace = self.state.focus_manager.workspace(&self.state.workspaces)?;
         let on_focused_workspace = |x: &Window| -> bool { focused_workspace.is_managed(x) };
+        let tag = focused_workspace.tag;
         let mut windows_on_workspace =
             helpers::vec_extract(&mut self.state.windows, on_focused_workspace);
+        let ordered_windows = in_visual_order(&self.state, tag, &windows_on_workspace);
         let is_handle = |x: &Window| -> bool { &x.handle == handle };
-        let new_handle = helpers::relative_find(&windows_on_workspace, is_handle, 1, false)
-            .or_else(|| helpers::relative_find(&windows_on_workspace, is_handle, -1, false))
-            .map(|w| w.handle);
+        let find_neighbour = |windows: &[Window]| {
+            helpers::relative_find(windows, is_handle, 1, false)
+                .or_else(|| helpers::relative_find(windows, is_handle, -1, false))
+                .map(|w| w.handle)
+        };
+        let visible_windows: Vec<Window> = ordered_windows
+            .iter()
+            .filter(|w| (w.visible() && !w.skip_focus_cycle) || is_handle(w))
+            .cloned()
+            .collect();
+        let new_handle =
+            find_neighbour(&visible_windows).or_else(|| find_neighbour(&ordered_windows));
         self.state.windows.append(&mut windows_on_workspace);
         new_handle
     }
@@ -185,6 +343,37 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 
 // Helper functions.
 
+/// Whether a new window floats like the focused one.
+/// Rules and the workspace setting take precedence over the focused window.
+fn inherits_floating(state: &State, window: &Window, ws: &Workspace) -> bool {
+    state.inherit_floating_from_focused
+        && window.rule_score == 0
+        && ws.always_float.is_none()
+        && matches!(state.focus_manager.window(&state.windows), Some(w) if w.floating())
+}
+
+/// Returns the windows with the tiled ones rearranged the way the layout of the tag shows them.
+/// Floating windows keep their place.
+fn in_visual_order(state: &State, tag: Option<TagId>, windows: &[Window]) -> Vec<Window> {
+    let tiled: Vec<&Window> = windows.iter().filter(|w| !w.floating()).collect();
+    let order = match tag.and_then(|id| state.tags.get(id)) {
+        Some(tag) if !tag.stack_hidden => tag.layout.visual_order(tag, tiled.len()),
+        _ => (0..tiled.len()).collect(),
+    };
+    let mut ordered_tiled = order.into_iter().filter_map(|i| tiled.get(i));
+    windows
+        .iter()
+        .filter_map(|w| {
+            if w.floating() {
+                Some(w)
+            } else {
+                ordered_tiled.next().copied()
+            }
+        })
+        .cloned()
+        .collect()
+}
+
 fn find_terminal(state: &State, pid: Option<u32>) -> Option<&Window> {
     // Get $SHELL, e.g. /bin/zsh
     let shell_path = env::var("SHELL").ok()?;
@@ -198,24 +387,48 @@ fn find_terminal(state: &State, pid: Option<u32>) -> Option<&Window> {
         Some(parent_str == shell)
     };
 
-    let get_parent = |pid: u32| -> Option<u32> {
-        let stat = std::fs::read(format!("/proc/{}/stat", pid)).ok()?;
-        let ppid_bytes = stat.split(|&c| c == b' ').nth(3)?;
-        let ppid_str = std::str::from_utf8(ppid_bytes).ok()?;
-        let ppid_u32 = u32::from_str(ppid_str).ok()?;
-        Some(ppid_u32)
-    };
-
     let pid = pid?;
-    let shell_id = get_parent(pid)?;
+    let shell_id = parent_pid(pid)?;
     if is_terminal(shell_id)? {
-        let terminal = get_parent(shell_id)?;
+        let terminal = parent_pid(shell_id)?;
         return state.windows.iter().find(|w| w.pid == Some(terminal));
     }
 
     None
 }
 
+fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = std::fs::read(format!("/proc/{}/stat", pid)).ok()?;
+    let ppid_bytes = stat.split(|&c| c == b' ').nth(3)?;
+    let ppid_str = std::str::from_utf8(ppid_bytes).ok()?;
+    let ppid_u32 = u32::from_str(ppid_str).ok()?;
+    Some(ppid_u32)
+}
+
+/// Finds the launch a window belongs to by its startup id, or else by the launched process
+/// its own process descends from.
+fn find_launch(
+    launches: &[Launch],
+    startup_id: Option<&str>,
+    pid: Option<u32>,
+    parent_of: impl Fn(u32) -> Option<u32>,
+) -> Option<usize> {
+    // Apps are usually started by a shell, so a few generations are enough.
+    const MAX_GENERATIONS: usize = 8;
+    let by_id = startup_id.and_then(|id| launches.iter().position(|l| l.startup_id == id));
+    if by_id.is_some() || launches.is_empty() {
+        return by_id;
+    }
+    let mut pid = pid?;
+    for _ in 0..MAX_GENERATIONS {
+        if let Some(index) = launches.iter().position(|l| l.pid == pid) {
+            return Some(index);
+        }
+        pid = parent_of(pid).filter(|&parent| parent > 1)?;
+    }
+    None
+}
+
 fn find_transient_parent(windows: &[Window], transient: Option<WindowHandle>) -> Option<&Window> {
     let mut transient = transient?;
     loop {
@@ -235,11 +448,12 @@ fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
     let mut was_fullscreen = false;
     if window.r#type == WindowType::Normal {
         let for_active_workspace = |x: &Window| -> bool { window.tag == x.tag && x.is_managed() };
-        // Only minimize when the new window is type normal.
+        // Only minimize when the new window is type normal, unless it should open behind.
+        let keep_fullscreen = state.keep_fullscreen_on_new_window;
         if let Some(fsw) = state
             .windows
             .iter_mut()
-            .find(|w| for_active_workspace(w) && w.is_fullscreen())
+            .find(|w| !keep_fullscreen && for_active_workspace(w) && w.is_fullscreen())
         {
             let act =
                 DisplayAction::SetState(fsw.handle, !fsw.is_fullscreen(), WindowState::Fullscreen);
@@ -281,6 +495,11 @@ fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
         return;
     }
 
+    insert_by_behavior(state, window.clone());
+}
+
+/// Inserts the window into the window list based on the configured insert behavior.
+pub(crate) fn insert_by_behavior(state: &mut State, window: Window) {
     let current_index = state
         .focus_manager
         .window(&state.windows)
@@ -294,22 +513,50 @@ fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
 
     // Past special cases we just insert the window based on the configured insert behavior
     match state.insert_behavior {
-        InsertBehavior::Top => state.windows.insert(0, window.clone()),
-        InsertBehavior::Bottom => state.windows.push(window.clone()),
+        InsertBehavior::Top => state.windows.insert(0, window),
+        InsertBehavior::Bottom => state.windows.push(window),
         InsertBehavior::AfterCurrent if current_index < state.windows.len() => {
-            state.windows.insert(current_index + 1, window.clone());
+            state.windows.insert(current_index + 1, window);
         }
         InsertBehavior::AfterCurrent | InsertBehavior::BeforeCurrent => {
-            state.windows.insert(current_index, window.clone());
+            state.windows.insert(current_index, window);
         }
     }
 }
 
-fn is_scratchpad(state: &State, window: &Window) -> bool {
-    state
+pub(crate) fn is_scratchpad(state: &State, window: &Window) -> bool {
+    scratchpad_of(state, window).is_some()
+}
+
+/// Returns the name of the scratchpad a window belongs to: the one which started its process,
+/// or else the one identified by its class.
+pub(crate) fn scratchpad_of<'a>(state: &'a State, window: &Window) -> Option<&'a ScratchPadName> {
+    let started_by = state
         .active_scratchpads
         .iter()
-        .any(|(_, id)| id.iter().any(|id| window.pid == Some(*id)))
+        .find(|(_, id)| id.iter().any(|id| window.pid == Some(*id)))
+        .map(|(name, _)| name);
+    started_by.or_else(|| {
+        let class = window.res_class.as_ref()?;
+        state
+            .scratchpads
+            .iter()
+            .find(|s| s.class.as_ref() == Some(class))
+            .map(|s| &s.name)
+    })
+}
+
+/// Resizes floating geometry to the size hints of the window, keeping its center.
+fn fit_to_size_hints(window: &Window, mut xyhw: Xyhw) -> Xyhw {
+    // Size hints don't include the border.
+    let border = 2 * window.border();
+    let (w, h) = window.clamp_to_size_hints(xyhw.w() - border, xyhw.h() - border);
+    xyhw.clear_minmax();
+    xyhw.set_x(xyhw.x() - (w + border - xyhw.w()) / 2);
+    xyhw.set_y(xyhw.y() - (h + border - xyhw.h()) / 2);
+    xyhw.set_w(w + border);
+    xyhw.set_h(h + border);
+    xyhw
 }
 
 fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
@@ -317,12 +564,13 @@ fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
     window.normal = ws.xyhw;
     let xyhw = window.requested.map_or_else(
         || ws.center_halfed(),
-        |mut requested| {
-            requested.center_relative(outer, window.border);
+        |requested| {
+            let mut requested = fit_to_size_hints(window, requested);
+            requested.center_relative(outer, window.border());
             if ws.xyhw.contains_xyhw(&requested) {
                 requested
             } else {
-                requested.center_relative(ws.xyhw, window.border);
+                requested.center_relative(ws.xyhw, window.border());
                 requested
             }
         },
@@ -330,6 +578,48 @@ fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
     window.set_floating_exact(xyhw);
 }
 
+/// How far a cascaded floating window is moved from the last one, right and down.
+const CASCADE_STEP: i32 = 32;
+
+/// Floats a new window and moves it to where the `floating_placement` puts it.
+fn set_placed_floating(state: &mut State, window: &mut Window, ws: &Workspace, cursor: (i32, i32)) {
+    set_relative_floating(window, ws, ws.xyhw);
+    let mut xyhw = window.exact_xyhw();
+    let area = ws.usable_xyhw();
+    let (x, y) = match state.floating_placement {
+        FloatingPlacement::Center => return,
+        FloatingPlacement::Cascade => {
+            let (x, y) = match state.cascade_positions.get(&ws.id) {
+                Some((x, y)) => (x + CASCADE_STEP, y + CASCADE_STEP),
+                None => (xyhw.x(), xyhw.y()),
+            };
+            // Start over in the corner once the window would leave the workspace.
+            if x + xyhw.w() > area.x() + area.w() || y + xyhw.h() > area.y() + area.h() {
+                (area.x(), area.y())
+            } else {
+                (x, y)
+            }
+        }
+        FloatingPlacement::Cursor => (cursor.0 - xyhw.w() / 2, cursor.1 - xyhw.h() / 2),
+    };
+    // Keep the window on the workspace as far as it fits.
+    xyhw.set_x(x.min(area.x() + area.w() - xyhw.w()).max(area.x()));
+    xyhw.set_y(y.min(area.y() + area.h() - xyhw.h()).max(area.y()));
+    if state.floating_placement == FloatingPlacement::Cascade {
+        state.cascade_positions.insert(ws.id, (xyhw.x(), xyhw.y()));
+    }
+    window.set_floating_exact(xyhw);
+}
+
+/// Returns the class and tag of a normal window, to remember the tag of its app by.
+fn app_tag(state: &State, window: &Window) -> Option<(String, TagId)> {
+    let tag = window.tag.filter(|tag| *tag <= state.tags.len_normal())?;
+    if window.r#type != WindowType::Normal || window.transient.is_some() {
+        return None;
+    }
+    Some((window.res_class.clone()?, tag))
+}
+
 fn setup_window(
     state: &mut State,
     window: &mut Window,
@@ -337,13 +627,25 @@ fn setup_window(
     layout: &mut Layout,
     is_first: &mut bool,
     on_same_tag: &mut bool,
+    always_float: &mut Option<bool>,
 ) {
+    // Windows of apps launched by `Execute` or `Spawn` open on the tag they were launched for.
+    let launched_on = find_launch(
+        &state.launches,
+        window.startup_id.as_deref(),
+        window.pid,
+        parent_pid,
+    )
+    .map(|index| state.launches.remove(index).tag);
+
     // When adding a window we add to the workspace under the cursor, This isn't necessarily the
     // focused workspace. If the workspace is empty, it might not have received focus. This is so
     // the workspace that has windows on its is still active not the empty workspace.
+    // Workspaces excluded from receiving new windows are skipped.
     let ws: Option<&Workspace> = state
         .workspaces
         .iter()
+        .filter(|ws| !ws.no_new_windows)
         .find(|ws| ws.xyhw.contains_point(xy.0, xy.1) && state.focus_manager.behaviour.is_sloppy())
         .or_else(|| state.focus_manager.workspace(&state.workspaces)); // Backup plan.
 
@@ -353,18 +655,17 @@ fn setup_window(
         *is_first = !state.windows.iter().any(|w| for_active_workspace(w));
         // May have been set by a predefined tag.
         if window.tag.is_none() {
-            window.tag =
-                find_terminal(state, window.pid).map_or_else(|| ws.tag, |terminal| terminal.tag);
+            window.tag = launched_on
+                .or_else(|| window.res_class.as_ref().and_then(|c| state.app_tag(c)))
+                .or_else(|| {
+                    find_terminal(state, window.pid).map_or_else(|| ws.tag, |terminal| terminal.tag)
+                });
         }
         *on_same_tag = ws.tag == window.tag;
         *layout = ws.layout;
 
         // Setup a scratchpad window.
-        if let Some((scratchpad_name, _)) = state
-            .active_scratchpads
-            .iter()
-            .find(|(_, id)| id.iter().any(|id| Some(*id) == window.pid))
-        {
+        if let Some(scratchpad_name) = scratchpad_of(state, window) {
             window.set_floating(true);
             if let Some(s) = state
                 .scratchpads
@@ -380,33 +681,50 @@ fn setup_window(
 
         // Setup a child window.
         if let Some(parent) = find_transient_parent(&state.windows, window.transient) {
+            // Child windows of some classes are configured to float or tile.
+            let float = window
+                .res_class
+                .as_ref()
+                .and_then(|class| state.transient_float.get(class).copied());
             // This is currently for vlc, this probably will need to be more general if another
             // case comes up where we don't want to move the window.
-            if window.r#type != WindowType::Utility {
+            window.tile_transient = float == Some(false);
+            if float.unwrap_or(window.r#type != WindowType::Utility) {
                 set_relative_floating(window, ws, parent.exact_xyhw());
                 return;
             }
         }
 
+        let float_ws = float_workspace(state, ws, xy);
+
         // Setup window based on type.
         match window.r#type {
             WindowType::Normal => {
                 window.apply_margin_multiplier(ws.margin_multiplier);
+                *always_float = ws.always_float;
+                if inherits_floating(state, window, ws) {
+                    window.set_floating(true);
+                }
+                // Windows beyond the maximum of tiled windows on the tag float instead.
+                if !window.floating() && is_tag_full(state, window.tag) {
+                    window.set_floating(true);
+                }
                 if window.floating() {
-                    set_relative_floating(window, ws, ws.xyhw);
+                    let float_ws = float_ws.clone();
+                    set_placed_floating(state, window, &float_ws, xy);
                 }
             }
             WindowType::Dialog => {
                 if window.can_resize() {
                     window.set_floating(true);
-                    let new_float_exact = ws.center_halfed();
-                    window.normal = ws.xyhw;
+                    let new_float_exact = fit_to_size_hints(window, float_ws.center_halfed());
+                    window.normal = float_ws.xyhw;
                     window.set_floating_exact(new_float_exact);
                 } else {
-                    set_relative_floating(window, ws, ws.xyhw);
+                    set_relative_floating(window, float_ws, float_ws.xyhw);
                 }
             }
-            WindowType::Splash => set_relative_floating(window, ws, ws.xyhw),
+            WindowType::Splash => set_relative_floating(window, float_ws, float_ws.xyhw),
             _ => {}
         }
         return;
@@ -415,19 +733,46 @@ fn setup_window(
     // Setup a window is workspace is `None`. This shouldn't really happen.
     window.tag = Some(1);
     if is_scratchpad(state, window) {
-        if let Some(scratchpad_tag) = state.tags.get_hidden_by_label("NSP") {
-            window.tag(&scratchpad_tag.id);
+        if let Some(scratchpad_tag) = state.scratchpad_tag() {
+            window.tag(&scratchpad_tag);
             window.set_floating(true);
         }
     }
 }
 
+/// Floating windows may be centered on the workspace under the cursor instead.
+fn float_workspace<'a>(state: &'a State, ws: &'a Workspace, xy: (i32, i32)) -> &'a Workspace {
+    if state.float_on_cursor_monitor {
+        state
+            .workspaces
+            .iter()
+            .filter(|ws| !ws.no_new_windows)
+            .find(|ws| ws.xyhw.contains_point(xy.0, xy.1))
+            .unwrap_or(ws)
+    } else {
+        ws
+    }
+}
+
+/// Returns true if the tag already has as many tiled windows as it allows.
+fn is_tag_full(state: &State, tag: Option<TagId>) -> bool {
+    if let Some(max) = tag.and_then(|tag| state.tags.get(tag)?.max_tiled_windows) {
+        let tiled = state
+            .windows
+            .iter()
+            .filter(|w| tag.iter().any(|tag| w.has_tag(tag)) && w.is_managed() && !w.floating())
+            .count();
+        return tiled >= max;
+    }
+    false
+}
+
 fn update_workspace_avoid_list(state: &mut State) {
     let mut avoid = vec![];
     state
         .windows
         .iter()
-        .filter(|w| w.r#type == WindowType::Dock)
+        .filter(|w| state.respect_dock_struts && w.r#type == WindowType::Dock)
         .filter_map(|w| w.strut.map(|strut| (w.handle, strut)))
         .for_each(|(handle, to_avoid)| {
             tracing::debug!("AVOID STRUT:[{:?}] {:?}", handle, to_avoid);
@@ -450,8 +795,27 @@ fn update_workspace_avoid_list(state: &mut State) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Screen;
+    use crate::command::Command;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::dto::ManagerState;
+    use crate::models::{BBox, FocusBehaviour, Gutter, Screen, Side, XyhwBuilder, XyhwChange};
+    use crate::state::LAUNCH_TIMEOUT;
     use crate::Manager;
+    use std::collections::HashMap;
+
+    #[test]
+    fn override_redirect_windows_are_not_managed() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        let mut tooltip = Window::new(WindowHandle::MockHandle(1), None, None);
+        tooltip.override_redirect = true;
+
+        assert!(!manager.window_created_handler(tooltip, -1, -1));
+        assert!(manager.state.windows.is_empty());
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert!(focused.is_none());
+    }
 
     #[test]
     fn insert_behavior_bottom_add_window_at_the_end_of_the_stack() {
@@ -565,6 +929,19 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn creating_a_known_window_again_does_not_duplicate_it() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let window = Window::new(WindowHandle::MockHandle(1), None, None);
+
+        assert!(manager.window_created_handler(window.clone(), -1, -1));
+        let actions = manager.state.actions.len();
+        assert!(!manager.window_created_handler(window, -1, -1));
+        assert_eq!(manager.state.windows.len(), 1);
+        assert_eq!(manager.state.actions.len(), actions);
+    }
+
     #[test]
     fn single_window_has_no_border() {
         let mut manager = Manager::new_test_with_border(vec![], 1);
@@ -599,6 +976,232 @@ mod tests {
         assert_eq!((manager.state.windows[1]).border(), 1);
     }
 
+    fn border_of(manager: &Manager<TestConfig, MockDisplayServer>, id: i32) -> i32 {
+        let handle = WindowHandle::MockHandle(id);
+        let window = manager.state.windows.iter().find(|w| w.handle == handle);
+        window.unwrap().border()
+    }
+
+    fn bordered_manager(
+        floating: Option<i32>,
+        tiled: Option<i32>,
+    ) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            border_width: 1,
+            floating_border_width: floating,
+            tiled_border_width: tiled,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+        manager
+            .state
+            .handle_window_focus(&WindowHandle::MockHandle(3));
+        manager.command_handler(&Command::ToggleFloating);
+        manager.update_windows();
+        manager
+    }
+
+    #[test]
+    fn windows_beyond_the_maximum_of_tiled_windows_float() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            max_tiled_windows: Some([("1".to_string(), 2)].into()),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=4 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+
+        let floating = |manager: &Manager<TestConfig, MockDisplayServer>, id| {
+            let handle = WindowHandle::MockHandle(id);
+            let window = manager.state.windows.iter().find(|w| w.handle == handle);
+            window.unwrap().floating()
+        };
+        assert!(!floating(&manager, 1));
+        assert!(!floating(&manager, 2));
+        assert!(floating(&manager, 3));
+        assert!(floating(&manager, 4));
+
+        // Tags without a maximum keep tiling.
+        manager.command_handler(&Command::GoToTag {
+            tag: 2,
+            swap: false,
+        });
+        for id in 5..=7 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+            assert!(!floating(&manager, id));
+        }
+    }
+
+    #[test]
+    fn closing_the_last_window_resets_the_layout_of_the_tag() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            default_layouts: Some([("2".to_string(), Layout::Monocle)].into()),
+            reset_layout_on_empty: true,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        let layouts = |manager: &Manager<TestConfig, MockDisplayServer>, tag| {
+            let tag_layout = manager.state.tags.get(tag).unwrap().layout;
+            (manager.state.workspaces[0].layout, tag_layout)
+        };
+        let create = |manager: &mut Manager<TestConfig, MockDisplayServer>, id| {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        };
+        let global_default = manager.state.layout_manager.new_layout(None);
+
+        create(&mut manager, 1);
+        manager.command_handler(&Command::SetLayout(Layout::Fibonacci));
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(1));
+        assert_eq!(layouts(&manager, 1), (global_default, global_default));
+
+        // Tags with a default layout go back to it, once all of their windows are closed.
+        manager.state.goto_tag_handler(2);
+        create(&mut manager, 2);
+        create(&mut manager, 3);
+        manager.command_handler(&Command::SetLayout(Layout::Fibonacci));
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+        assert_eq!(layouts(&manager, 2), (Layout::Fibonacci, Layout::Fibonacci));
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(3));
+        assert_eq!(layouts(&manager, 2), (Layout::Monocle, Layout::Monocle));
+    }
+
+    #[test]
+    fn child_windows_of_configured_classes_float_or_tile() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            transient_float: Some(
+                [("tiled", false), ("floated", true)]
+                    .map(|(c, f)| (c.to_string(), f))
+                    .into(),
+            ),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        let parent = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(parent, None, None), -1, -1);
+        // Whether the child floats, and whether it is placed over its parent.
+        let children = [
+            (2, WindowType::Normal, Some("tiled"), false, false),
+            (3, WindowType::Normal, None, true, true),
+            (4, WindowType::Utility, Some("floated"), true, true),
+            (5, WindowType::Utility, None, true, false),
+        ];
+        for (id, r#type, class, floating, placed) in children {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.transient = Some(parent);
+            window.r#type = r#type;
+            window.res_class = class.map(str::to_string);
+            manager.window_created_handler(window, -1, -1);
+            let handle = WindowHandle::MockHandle(id);
+            let window = manager.state.windows.iter().find(|w| w.handle == handle);
+            let window = window.unwrap();
+            assert_eq!(window.floating(), floating, "window {id}");
+            assert_eq!(
+                window.get_floating_offsets().is_some(),
+                placed,
+                "window {id}"
+            );
+        }
+    }
+
+    #[test]
+    fn new_windows_open_on_the_remembered_tag_of_their_class() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: (1..=4).map(|t| t.to_string()).collect(),
+            remember_app_tags: Some(1),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        let create = |manager: &mut Manager<TestConfig, MockDisplayServer>, id, class: &str| {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.res_class = Some(class.to_string());
+            manager.window_created_handler(window, -1, -1);
+            let handle = WindowHandle::MockHandle(id);
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == handle)
+                .unwrap()
+                .tag
+        };
+
+        manager.state.goto_tag_handler(3);
+        assert_eq!(create(&mut manager, 1, "browser"), Some(3));
+        manager.state.goto_tag_handler(1);
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(1));
+        assert_eq!(create(&mut manager, 2, "browser"), Some(3));
+        assert_eq!(create(&mut manager, 3, "terminal"), Some(1));
+
+        // Only the most recently closed class is remembered.
+        manager.state.goto_tag_handler(2);
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(3));
+        manager.state.goto_tag_handler(4);
+        assert_eq!(create(&mut manager, 4, "browser"), Some(4));
+        assert_eq!(create(&mut manager, 5, "terminal"), Some(1));
+    }
+
+    #[test]
+    fn floating_and_tiled_windows_get_their_own_border_width() {
+        let manager = bordered_manager(Some(4), Some(2));
+        assert_eq!(border_of(&manager, 1), 2);
+        assert_eq!(border_of(&manager, 2), 2);
+        assert_eq!(border_of(&manager, 3), 4);
+    }
+
+    #[test]
+    fn border_widths_per_type_fall_back_to_the_border_width() {
+        let manager = bordered_manager(None, Some(2));
+        assert_eq!(border_of(&manager, 1), 2);
+        assert_eq!(border_of(&manager, 3), 1);
+    }
+
+    #[test]
+    fn borderless_windows_have_no_border_in_their_geometry() {
+        let mut manager = Manager::new_test_with_border(vec!["1".to_string()], 1);
+        manager.screen_create_handler(Screen::default());
+        let mut borderless = Window::new(WindowHandle::MockHandle(1), None, None);
+        borderless.no_border = true;
+        manager.window_created_handler(borderless, -1, -1);
+        let window = Window::new(WindowHandle::MockHandle(2), None, None);
+        manager.window_created_handler(window, -1, -1);
+        manager.update_windows();
+
+        let borderless = &manager.state.windows[0];
+        assert_eq!(borderless.border(), 0);
+        assert_eq!(borderless.width(), borderless.normal.w());
+        let bordered = &manager.state.windows[1];
+        assert_eq!(bordered.border(), 1);
+        assert_eq!(bordered.width(), bordered.normal.w() - 2);
+    }
+
+    #[test]
+    fn windows_lose_their_border_when_asking_for_no_decorations() {
+        let mut manager = Manager::new_test_with_border(vec!["1".to_string()], 1);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            let window = Window::new(WindowHandle::MockHandle(id), None, None);
+            manager.window_created_handler(window, -1, -1);
+        }
+
+        let mut change = WindowChange::new(WindowHandle::MockHandle(1));
+        change.no_border = Some(true);
+        assert!(manager.window_changed_handler(change));
+        assert_eq!(border_of(&manager, 1), 0);
+        assert_eq!(border_of(&manager, 2), 1);
+    }
+
     #[test]
     fn remaining_single_window_has_no_border() {
         let mut manager = Manager::new_test_with_border(vec![], 1);
@@ -708,4 +1311,871 @@ mod tests {
         assert_eq!((manager.state.windows[0]).border(), 0);
         assert_eq!((manager.state.windows[1]).border(), 0);
     }
+
+    fn dialog_change(handle: WindowHandle) -> WindowChange {
+        let mut change = WindowChange::new(handle);
+        change.floating = Some(XyhwChange {
+            w: Some(100),
+            h: Some(50),
+            ..Default::default()
+        });
+        change
+    }
+
+    #[test]
+    fn changed_dialogs_are_centered_on_their_parent() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        let parent = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(parent, None, None), -1, -1);
+        manager.state.windows[0].set_floating(true);
+        manager.state.windows[0].set_floating_exact(
+            XyhwBuilder {
+                x: 100,
+                y: 100,
+                w: 400,
+                h: 300,
+                ..Default::default()
+            }
+            .into(),
+        );
+        let mut dialog = Window::new(WindowHandle::MockHandle(2), None, None);
+        dialog.r#type = WindowType::Dialog;
+        dialog.transient = Some(parent);
+        manager.window_created_handler(dialog, -1, -1);
+
+        assert!(manager.window_changed_handler(dialog_change(WindowHandle::MockHandle(2))));
+
+        let dialog = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(2))
+            .unwrap();
+        assert_eq!((dialog.x(), dialog.y()), (250, 225));
+    }
+
+    #[test]
+    fn changed_dialogs_without_parent_are_centered_on_their_workspace() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        let mut dialog = Window::new(WindowHandle::MockHandle(1), None, None);
+        dialog.r#type = WindowType::Dialog;
+        manager.window_created_handler(dialog, -1, -1);
+
+        assert!(manager.window_changed_handler(dialog_change(WindowHandle::MockHandle(1))));
+
+        let dialog = &manager.state.windows[0];
+        assert_eq!((dialog.x(), dialog.y()), (350, 275));
+    }
+
+    fn new_window_floats_after_a_floating_one(
+        inherit_floating_from_focused: bool,
+        always_float: Option<bool>,
+        rule_score: u8,
+    ) -> bool {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            inherit_floating_from_focused,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen {
+            always_float,
+            ..Screen::default()
+        });
+        let mut floating = Window::new(WindowHandle::MockHandle(1), None, None);
+        floating.set_floating(true);
+        manager.window_created_handler(floating, -1, -1);
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+
+        let mut window = Window::new(WindowHandle::MockHandle(2), None, None);
+        window.rule_score = rule_score;
+        manager.window_created_handler(window, -1, -1);
+        manager
+            .state
+            .windows
+            .iter()
+            .any(|w| w.handle == WindowHandle::MockHandle(2) && w.floating())
+    }
+
+    #[test]
+    fn new_windows_can_inherit_the_floating_of_the_focused_window() {
+        assert!(!new_window_floats_after_a_floating_one(false, None, 0));
+        assert!(new_window_floats_after_a_floating_one(true, None, 0));
+    }
+
+    #[test]
+    fn rules_and_workspaces_take_precedence_over_the_focused_window() {
+        assert!(!new_window_floats_after_a_floating_one(true, None, 1));
+        assert!(!new_window_floats_after_a_floating_one(
+            true,
+            Some(false),
+            0
+        ));
+    }
+
+    fn dual_monitor_manager(
+        float_on_cursor_monitor: bool,
+    ) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            float_on_cursor_monitor,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 800,
+            y: 0,
+            width: 800,
+            height: 600,
+        }));
+        let first = manager.state.workspaces[0].clone();
+        manager.state.focus_workspace(&first);
+        manager
+    }
+
+    fn create_dialog_at(
+        manager: &mut Manager<TestConfig, MockDisplayServer>,
+        x: i32,
+        y: i32,
+    ) -> (i32, i32) {
+        let mut dialog = Window::new(WindowHandle::MockHandle(1), None, None);
+        dialog.r#type = WindowType::Dialog;
+        manager.window_created_handler(dialog, x, y);
+        let dialog = &manager.state.windows[0];
+        (dialog.x(), dialog.y())
+    }
+
+    #[test]
+    fn new_floating_windows_are_centered_on_the_cursor_monitor() {
+        let mut manager = dual_monitor_manager(true);
+        assert_eq!(create_dialog_at(&mut manager, 1000, 300), (1000, 150));
+
+        let mut manager = dual_monitor_manager(true);
+        assert_eq!(create_dialog_at(&mut manager, 100, 300), (200, 150));
+    }
+
+    fn placed_floating_windows(placement: FloatingPlacement, count: i32) -> Vec<(i32, i32)> {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            floating_placement: placement,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        (1..=count)
+            .map(|id| {
+                let handle = WindowHandle::MockHandle(id);
+                let mut window = Window::new(handle, None, None);
+                window.set_floating(true);
+                manager.window_created_handler(window, 700, 500);
+                let window = manager.state.windows.iter().find(|w| w.handle == handle);
+                let window = window.unwrap();
+                (window.x(), window.y())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn floating_windows_are_placed_by_the_configured_placement() {
+        let centered = placed_floating_windows(FloatingPlacement::Center, 2);
+        assert_eq!(centered, [(200, 150), (200, 150)]);
+
+        let cascaded = placed_floating_windows(FloatingPlacement::Cascade, 3);
+        assert_eq!(cascaded, [(200, 150), (232, 182), (264, 214)]);
+
+        // Windows are kept on the workspace when centered on the cursor.
+        let on_cursor = placed_floating_windows(FloatingPlacement::Cursor, 1);
+        assert_eq!(on_cursor, [(400, 300)]);
+    }
+
+    #[test]
+    fn cascaded_windows_start_over_near_the_edge() {
+        let cascaded = placed_floating_windows(FloatingPlacement::Cascade, 8);
+        assert_eq!(cascaded[4], (328, 278));
+        assert_eq!(cascaded[5], (0, 0));
+        assert_eq!(cascaded[6], (32, 32));
+    }
+
+    #[test]
+    fn new_windows_skip_workspaces_excluded_from_new_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.state.focus_manager.behaviour = FocusBehaviour::Sloppy;
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen {
+            no_new_windows: true,
+            ..Screen::new(BBox {
+                x: 800,
+                y: 0,
+                width: 800,
+                height: 600,
+            })
+        });
+        let first = manager.state.workspaces[0].clone();
+        manager.state.focus_workspace(&first);
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            1000,
+            300,
+        );
+
+        assert_eq!(manager.state.windows[0].tag, first.tag);
+        assert_ne!(
+            manager.state.windows[0].tag,
+            manager.state.workspaces[1].tag
+        );
+    }
+
+    fn always_float_manager(always_float: bool) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            always_float: Some(always_float),
+            ..TestConfig::default()
+        });
+        manager.state.focus_manager.behaviour = FocusBehaviour::Sloppy;
+        manager.screen_create_handler(Screen {
+            always_float: Some(!always_float),
+            ..Screen::default()
+        });
+        manager.screen_create_handler(Screen::new(BBox {
+            x: 800,
+            y: 0,
+            width: 800,
+            height: 600,
+        }));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            400,
+            300,
+        );
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            1200,
+            300,
+        );
+        manager
+    }
+
+    fn is_floating(manager: &Manager<TestConfig, MockDisplayServer>, id: i32) -> bool {
+        manager
+            .state
+            .windows
+            .iter()
+            .any(|w| w.handle == WindowHandle::MockHandle(id) && w.floating())
+    }
+
+    #[test]
+    fn workspaces_can_make_new_windows_float() {
+        let manager = always_float_manager(false);
+        assert!(is_floating(&manager, 1));
+        assert!(!is_floating(&manager, 2));
+    }
+
+    #[test]
+    fn workspaces_can_make_new_windows_tile_despite_always_float() {
+        let manager = always_float_manager(true);
+        assert!(!is_floating(&manager, 1));
+        assert!(is_floating(&manager, 2));
+    }
+
+    #[test]
+    fn new_floating_windows_fall_back_to_the_tag_workspace() {
+        let mut manager = dual_monitor_manager(true);
+        assert_eq!(create_dialog_at(&mut manager, -100, -100), (200, 150));
+
+        let mut manager = dual_monitor_manager(false);
+        assert_eq!(create_dialog_at(&mut manager, 1000, 300), (200, 150));
+    }
+
+    #[test]
+    fn new_floating_windows_are_sized_to_their_size_hints() {
+        for can_resize in [true, false] {
+            let mut manager = Manager::new_test(vec!["1".to_string()]);
+            manager.screen_create_handler(Screen::default());
+            let mut dialog = Window::new(WindowHandle::MockHandle(1), None, None);
+            dialog.r#type = WindowType::Dialog;
+            dialog.can_resize = can_resize;
+            dialog.requested = Some(
+                XyhwBuilder {
+                    w: 300,
+                    h: 200,
+                    minw: 300,
+                    maxw: 300,
+                    minh: 200,
+                    maxh: 200,
+                    ..XyhwBuilder::default()
+                }
+                .into(),
+            );
+            manager.window_created_handler(dialog, -1, -1);
+
+            // The hints are for the window without its border.
+            let frame = manager.state.windows[0].exact_xyhw();
+            assert_eq!((frame.w(), frame.h()), (302, 202));
+        }
+    }
+
+    fn monocle_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::SetLayout(Layout::Monocle));
+        manager.update_windows();
+        manager
+    }
+
+    fn visible_window(manager: &Manager<TestConfig, MockDisplayServer>) -> WindowHandle {
+        let visible: Vec<WindowHandle> = manager
+            .state
+            .windows
+            .iter()
+            .filter(|w| w.visible())
+            .map(|w| w.handle)
+            .collect();
+        assert_eq!(visible.len(), 1);
+        visible[0]
+    }
+
+    #[test]
+    fn closing_the_top_monocle_window_focuses_the_next_one() {
+        let mut manager = monocle_manager();
+        let top = visible_window(&manager);
+        manager.state.focus_window(&top);
+
+        manager.window_destroyed_handler(&top);
+        manager.update_windows();
+
+        let next = visible_window(&manager);
+        assert_ne!(next, top);
+        assert_eq!(
+            manager
+                .state
+                .focus_manager
+                .window(&manager.state.windows)
+                .unwrap()
+                .handle,
+            next
+        );
+    }
+
+    #[test]
+    fn next_or_previous_handle_prefers_visible_windows() {
+        let mut manager = monocle_manager();
+        let top = visible_window(&manager);
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(4), None, None),
+            -1,
+            -1,
+        );
+        manager.command_handler(&Command::ToggleFloating);
+        manager.update_windows();
+        manager.state.focus_window(&top);
+
+        assert_eq!(
+            manager.get_next_or_previous_handle(&top),
+            Some(WindowHandle::MockHandle(4))
+        );
+    }
+
+    fn urgent_change(handle: WindowHandle, urgent: bool) -> WindowChange {
+        let mut change = WindowChange::new(handle);
+        change.urgent = Some(urgent);
+        change
+    }
+
+    #[test]
+    fn focus_urgent_jumps_to_the_oldest_urgent_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::SendWindowToTag {
+            window: Some(WindowHandle::MockHandle(2)),
+            tag: 2,
+        });
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+
+        manager.window_changed_handler(urgent_change(WindowHandle::MockHandle(2), true));
+        manager.window_changed_handler(urgent_change(WindowHandle::MockHandle(3), true));
+        assert_eq!(
+            manager.state.urgent_windows,
+            vec![WindowHandle::MockHandle(2), WindowHandle::MockHandle(3)]
+        );
+
+        assert!(manager.command_handler(&Command::FocusUrgent));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        let focused = manager
+            .state
+            .focus_manager
+            .window(&manager.state.windows)
+            .unwrap();
+        assert_eq!(focused.handle, WindowHandle::MockHandle(2));
+        assert!(!focused.urgent);
+        assert_eq!(
+            manager.state.urgent_windows,
+            vec![WindowHandle::MockHandle(3)]
+        );
+    }
+
+    #[test]
+    fn windows_stop_being_urgent_when_the_hint_is_removed() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.window_changed_handler(urgent_change(WindowHandle::MockHandle(1), true));
+        manager.window_changed_handler(urgent_change(WindowHandle::MockHandle(1), false));
+
+        assert!(manager.state.urgent_windows.is_empty());
+        assert!(!manager.command_handler(&Command::FocusUrgent));
+    }
+
+    #[test]
+    fn tags_with_windows_demanding_attention_are_flagged_until_focused() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::SendWindowToTag {
+            window: Some(WindowHandle::MockHandle(2)),
+            tag: 2,
+        });
+        let attention_tags = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            ManagerState::from(&manager.state).attention_tags
+        };
+        assert!(attention_tags(&manager).is_empty());
+
+        let mut change = WindowChange::new(WindowHandle::MockHandle(2));
+        change.demands_attention = Some(true);
+        assert!(manager.window_changed_handler(change));
+        assert_eq!(attention_tags(&manager), vec!["2".to_string()]);
+
+        manager.state.goto_tag_handler(2);
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        assert!(attention_tags(&manager).is_empty());
+    }
+
+    #[test]
+    fn dock_struts_shrink_the_usable_workspace_area() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.workspaces[0].gutters = vec![Gutter::new(Side::Left, 20, None)];
+
+        let mut dock = Window::new(WindowHandle::MockHandle(1), None, None);
+        dock.r#type = WindowType::Dock;
+        dock.strut = Some(
+            XyhwBuilder {
+                x: 0,
+                y: 0,
+                w: 800,
+                h: 30,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        manager.window_created_handler(dock, -1, -1);
+
+        let expected: Xyhw = XyhwBuilder {
+            x: 20,
+            y: 30,
+            w: 780,
+            h: 570,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        assert_eq!(manager.state.workspaces[0].usable_xyhw(), expected);
+    }
+
+    fn create_top_dock(manager: &mut Manager<TestConfig, MockDisplayServer>) {
+        let mut dock = Window::new(WindowHandle::MockHandle(1), None, None);
+        dock.r#type = WindowType::Dock;
+        dock.strut = Some(
+            XyhwBuilder {
+                x: 0,
+                y: 0,
+                w: 800,
+                h: 30,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        manager.window_created_handler(dock, -1, -1);
+    }
+
+    #[test]
+    fn dock_struts_are_only_avoided_when_respected() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        create_top_dock(&mut manager);
+        let usable = manager.state.workspaces[0].usable_xyhw();
+        assert_eq!((usable.y(), usable.h()), (30, 570));
+
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            ignore_dock_struts: true,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        create_top_dock(&mut manager);
+        let usable = manager.state.workspaces[0].usable_xyhw();
+        assert_eq!((usable.y(), usable.h()), (0, 600));
+    }
+
+    fn class_change(handle: WindowHandle, class: &str) -> WindowChange {
+        let mut change = WindowChange::new(handle);
+        change.res_class = Some(Some(class.to_string()));
+        change
+    }
+
+    #[test]
+    fn window_rules_apply_when_the_class_arrives_late() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+
+        assert!(manager.window_changed_handler(class_change(handle, "ShouldGoToTag2")));
+
+        assert_eq!(manager.state.windows[0].tag, Some(2));
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetWindowTag(h, Some(2)) if *h == handle
+        )));
+    }
+
+    #[test]
+    fn window_rules_do_not_move_windows_the_user_moved() {
+        let tags = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+        manager.command_handler(&Command::SendWindowToTag {
+            window: Some(handle),
+            tag: 3,
+        });
+
+        manager.window_changed_handler(class_change(handle, "ShouldGoToTag2"));
+
+        assert_eq!(manager.state.windows[0].tag, Some(3));
+        assert_eq!(
+            manager.state.windows[0].res_class,
+            Some("ShouldGoToTag2".to_string())
+        );
+    }
+
+    #[test]
+    fn never_focus_windows_do_not_take_the_focus_when_created() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.state.focus_manager.focus_new_windows = true;
+        manager.screen_create_handler(Screen::default());
+        let first = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(first, None, None), -1, -1);
+        let mut splash = Window::new(WindowHandle::MockHandle(2), None, None);
+        splash.never_focus = true;
+
+        manager.window_created_handler(splash, -1, -1);
+
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.map(|w| w.handle), Some(first));
+    }
+
+    #[test]
+    fn prevented_focus_stealing_keeps_the_focused_window() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            prevent_focus_stealing: true,
+            ..TestConfig::default()
+        });
+        manager.state.focus_manager.focus_new_windows = true;
+        manager.screen_create_handler(Screen::default());
+        let first = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(first, None, None), -1, -1);
+        let focused = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            let window = manager.state.focus_manager.window(&manager.state.windows);
+            window.map(|w| w.handle)
+        };
+        assert_eq!(focused(&manager), Some(first));
+
+        let background = Window::new(WindowHandle::MockHandle(2), None, None);
+        manager.window_created_handler(background, -1, -1);
+        assert_eq!(focused(&manager), Some(first));
+
+        // Windows demanding attention still take the focus.
+        let mut urgent = Window::new(WindowHandle::MockHandle(3), None, None);
+        urgent.urgent = true;
+        manager.window_created_handler(urgent, -1, -1);
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(3)));
+    }
+
+    fn last_close_manager(
+        focus_after_last_close: FocusAfterLastClose,
+    ) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string()],
+            focus_after_last_close,
+            ..TestConfig::default()
+        });
+        manager.state.focus_manager.behaviour = FocusBehaviour::Sloppy;
+        manager.state.focus_manager.sloppy_mouse_follows_focus = true;
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        manager.state.goto_tag_handler(2);
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        manager.state.actions.clear();
+        manager
+    }
+
+    #[test]
+    fn closing_the_last_window_can_go_back_to_the_previous_tag() {
+        let mut manager = last_close_manager(FocusAfterLastClose::PreviousTag);
+        // Sloppy focus would leave picking the window to the display server.
+        manager.state.focus_manager.behaviour = FocusBehaviour::ClickTo;
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.map(|w| w.handle), Some(WindowHandle::MockHandle(1)));
+    }
+
+    #[test]
+    fn closing_the_last_window_can_leave_nothing_focused() {
+        let mut manager = last_close_manager(FocusAfterLastClose::None);
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert!(focused.is_none());
+        assert!(!manager
+            .state
+            .actions
+            .iter()
+            .any(|a| matches!(a, DisplayAction::FocusWindowUnderCursor)));
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::Unfocus(Some(WindowHandle::MockHandle(2)), _)
+        )));
+    }
+
+    #[test]
+    fn closing_the_last_window_follows_the_focus_behaviour_by_default() {
+        let under_cursor = |manager: &Manager<TestConfig, MockDisplayServer>| {
+            manager
+                .state
+                .actions
+                .iter()
+                .any(|a| matches!(a, DisplayAction::FocusWindowUnderCursor))
+        };
+        let mut manager = last_close_manager(FocusAfterLastClose::default());
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+        assert!(under_cursor(&manager));
+
+        let mut manager = last_close_manager(FocusAfterLastClose::default());
+        manager.state.focus_manager.behaviour = FocusBehaviour::ClickTo;
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+        assert!(!under_cursor(&manager));
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::Unfocus(Some(WindowHandle::MockHandle(2)), _)
+        )));
+    }
+
+    #[test]
+    fn closing_the_last_window_can_focus_the_window_under_the_cursor() {
+        let mut manager = last_close_manager(FocusAfterLastClose::UnderCursor);
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(2));
+
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        assert!(manager
+            .state
+            .actions
+            .iter()
+            .any(|a| matches!(a, DisplayAction::FocusWindowUnderCursor)));
+    }
+
+    fn spawn_next_to_fullscreen(
+        keep_fullscreen_on_new_window: bool,
+    ) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string()],
+            keep_fullscreen_on_new_window,
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.windows[0].set_states(vec![WindowState::Fullscreen]);
+        manager.state.actions.clear();
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager
+    }
+
+    fn fullscreen_ended(manager: &Manager<TestConfig, MockDisplayServer>) -> bool {
+        manager.state.actions.iter().any(|a| {
+            matches!(
+                a,
+                DisplayAction::SetState(
+                    WindowHandle::MockHandle(1),
+                    false,
+                    WindowState::Fullscreen
+                )
+            )
+        })
+    }
+
+    #[test]
+    fn new_windows_end_the_fullscreen_of_other_windows() {
+        let manager = spawn_next_to_fullscreen(false);
+        assert!(fullscreen_ended(&manager));
+    }
+
+    #[test]
+    fn new_windows_can_open_behind_a_fullscreen_window() {
+        let manager = spawn_next_to_fullscreen(true);
+        assert!(!fullscreen_ended(&manager));
+        let handles: Vec<WindowHandle> = manager.state.windows.iter().map(|w| w.handle).collect();
+        assert_eq!(
+            handles,
+            vec![WindowHandle::MockHandle(1), WindowHandle::MockHandle(2)]
+        );
+    }
+
+    fn launched_window(startup_id: Option<String>) -> Window {
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.startup_id = startup_id;
+        window
+    }
+
+    #[test]
+    fn launched_windows_open_on_the_tag_they_were_launched_from() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.goto_tag_handler(2);
+        manager.command_handler(&Command::Execute("true".to_string()));
+        let startup_id = Some(manager.state.launches[0].startup_id.clone());
+        assert_eq!(manager.state.launches[0].tag, 2);
+
+        manager.state.goto_tag_handler(1);
+        manager.window_created_handler(launched_window(startup_id), -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(2));
+        assert!(manager.state.launches.is_empty());
+    }
+
+    #[test]
+    fn unknown_startup_ids_open_on_the_focused_tag() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager
+            .state
+            .launches
+            .push(Launch::new(2, "launched".to_string(), 100));
+
+        let startup_id = Some("someone-else_TIME0".to_string());
+        manager.window_created_handler(launched_window(startup_id), -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+        assert_eq!(manager.state.launches.len(), 1);
+    }
+
+    #[test]
+    fn expired_launches_are_forgotten() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut expired = Launch::new(2, "expired".to_string(), 100);
+        expired.launched_at -= LAUNCH_TIMEOUT;
+        let state = &mut manager.state;
+        state.launches.push(expired);
+        state
+            .launches
+            .push(Launch::new(2, "recent".to_string(), 200));
+
+        state.expire_launches();
+        let ids: Vec<&str> = state
+            .launches
+            .iter()
+            .map(|l| l.startup_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["recent"]);
+
+        let startup_id = Some("expired".to_string());
+        manager.window_created_handler(launched_window(startup_id), -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+    }
+
+    #[test]
+    fn launches_are_found_by_startup_id_or_process_ancestry() {
+        let launches = vec![
+            Launch::new(2, "first".to_string(), 100),
+            Launch::new(3, "second".to_string(), 500),
+        ];
+        let parents: HashMap<u32, u32> = [(300, 200), (200, 100), (100, 1), (400, 1)].into();
+        let parent_of = |pid: u32| parents.get(&pid).copied();
+
+        assert_eq!(find_launch(&launches, None, Some(100), parent_of), Some(0));
+        assert_eq!(find_launch(&launches, None, Some(300), parent_of), Some(0));
+        assert_eq!(find_launch(&launches, None, Some(400), parent_of), None);
+        assert_eq!(find_launch(&launches, None, None, parent_of), None);
+        assert_eq!(
+            find_launch(&launches, Some("second"), Some(300), parent_of),
+            Some(1)
+        );
+        assert_eq!(
+            find_launch(&launches, Some("unknown"), Some(300), parent_of),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn spawned_windows_open_on_the_requested_tag() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.command_handler(&Command::Spawn {
+            command: "true".to_string(),
+            tag: Some(2),
+        });
+        let launch = &manager.state.launches[0];
+        assert_eq!(launch.tag, 2);
+
+        let window = Window::new(WindowHandle::MockHandle(1), None, Some(launch.pid));
+        manager.window_created_handler(window, -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(2));
+        assert!(manager.state.launches.is_empty());
+    }
 }
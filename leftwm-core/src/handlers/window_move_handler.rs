@@ -11,19 +11,56 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         offset_y: i32,
     ) -> bool {
         let disable_snap = &self.config.disable_window_snap();
-        match self.state.windows.iter_mut().find(|w| w.handle == *handle) {
+        let constrain = self.config.constrain_floating_to_workspace();
+        let (delta_x, delta_y) = match self.state.windows.iter_mut().find(|w| w.handle == *handle) {
             Some(w) => {
+                let before = w.calculated_xyhw();
                 process_window(w, offset_x, offset_y);
-                if !disable_snap && snap_to_workspace(w, &self.state.workspaces) {
+                let snapped = !disable_snap && snap_to_workspace(w, &self.state.workspaces);
+                if !snapped && constrain {
+                    if let Some(area) = usable_area(w, &self.state.workspaces) {
+                        w.constrain_floating_to(area, false);
+                    }
+                }
+                let after = w.calculated_xyhw();
+                if snapped {
                     self.state.sort_windows();
                 }
-                true
+                (after.x() - before.x(), after.y() - before.y())
             }
-            None => false,
-        }
+            None => return false,
+        };
+        move_transient_children(&mut self.state.windows, handle, delta_x, delta_y);
+        true
+    }
+}
+
+// Keep the floating dialogs of a moved window at the same position relative to it.
+fn move_transient_children(windows: &mut [Window], parent: &WindowHandle, x: i32, y: i32) {
+    if x == 0 && y == 0 {
+        return;
+    }
+    for child in windows
+        .iter_mut()
+        .filter(|w| w.transient == Some(*parent) && w.floating())
+    {
+        let mut offset = child.get_floating_offsets().unwrap_or_default();
+        offset.set_x(offset.x() + x);
+        offset.set_y(offset.y() + y);
+        child.set_floating_offsets(Some(offset));
     }
 }
 
+/// The usable area of the workspace the window is on.
+pub(crate) fn usable_area(window: &Window, workspaces: &[Workspace]) -> Option<Xyhw> {
+    let (x, y) = window.calculated_xyhw().center();
+    workspaces
+        .iter()
+        .find(|ws| ws.is_displaying(window))
+        .or_else(|| workspaces.iter().find(|ws| ws.contains_point(x, y)))
+        .map(Workspace::usable_xyhw)
+}
+
 fn process_window(window: &mut Window, offset_x: i32, offset_y: i32) {
     let mut offset = window.get_floating_offsets().unwrap_or_default();
     let start = window.start_loc.unwrap_or_default();
@@ -56,7 +93,7 @@ fn should_snap(window: &mut Window, workspace: &Workspace, loc: Xyhw) -> bool {
     let win_top = loc.y();
     let win_bottom = win_top + window.height();
     // Check for close edge.
-    let dist = 10;
+    let dist = workspace.scaled(10);
     let ws_left = workspace.x();
     let ws_right = workspace.x() + workspace.width();
     let ws_top = workspace.y();
@@ -74,3 +111,94 @@ fn should_snap(window: &mut Window, workspace: &Workspace, loc: Xyhw) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
+    use crate::models::{Screen, XyhwBuilder};
+    use crate::Manager;
+
+    fn start_move(windows: &mut [Window]) {
+        for window in windows {
+            window.start_loc = window.get_floating_offsets();
+        }
+    }
+
+    fn position(windows: &[Window], handle: WindowHandle) -> (i32, i32) {
+        let window = windows.iter().find(|w| w.handle == handle).unwrap();
+        (window.x(), window.y())
+    }
+
+    #[test]
+    fn moving_a_parent_moves_its_dialogs_by_the_same_offset() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        let parent = WindowHandle::MockHandle(1);
+        let child = WindowHandle::MockHandle(2);
+        let mut window = Window::new(parent, None, None);
+        window.set_floating(true);
+        manager.window_created_handler(window, -1, -1);
+        let mut dialog = Window::new(child, None, None);
+        dialog.transient = Some(parent);
+        manager.window_created_handler(dialog, -1, -1);
+
+        let parent_before = position(&manager.state.windows, parent);
+        let child_before = position(&manager.state.windows, child);
+        start_move(&mut manager.state.windows);
+        assert!(manager.window_move_handler(&parent, 30, 40));
+
+        let parent_after = position(&manager.state.windows, parent);
+        let child_after = position(&manager.state.windows, child);
+        assert_eq!(parent_after, (parent_before.0 + 30, parent_before.1 + 40));
+        assert_eq!(child_after, (child_before.0 + 30, child_before.1 + 40));
+    }
+
+    #[test]
+    fn moving_is_constrained_to_the_workspace_when_enabled() {
+        for constrain in [false, true] {
+            let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+                tags: vec!["1".to_string()],
+                constrain_floating_to_workspace: constrain,
+                ..TestConfig::default()
+            });
+            manager.screen_create_handler(Screen::default());
+            let handle = WindowHandle::MockHandle(1);
+            let mut window = Window::new(handle, None, None);
+            window.set_floating(true);
+            manager.window_created_handler(window, -1, -1);
+            start_move(&mut manager.state.windows);
+
+            assert!(manager.window_move_handler(&handle, 5000, -5000));
+            let area = manager.state.workspaces[0].usable_xyhw();
+            let window = &manager.state.windows[0];
+            let border = 2 * window.border();
+            if constrain {
+                assert_eq!(window.x() + window.width() + border, area.x() + area.w());
+                assert_eq!(window.y(), area.y());
+            } else {
+                assert!(window.x() > area.x() + area.w());
+            }
+        }
+    }
+
+    #[test]
+    fn snap_distance_grows_with_the_scale() {
+        for (scale, snaps) in [(1.0, false), (2.0, true)] {
+            let mut workspace =
+                Workspace::new(None, Screen::default().bbox, Layout::default(), None);
+            workspace.scale = scale;
+            let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+            window.set_floating(true);
+            let loc: Xyhw = XyhwBuilder {
+                x: workspace.x() + 100,
+                y: workspace.y() + 15,
+                ..XyhwBuilder::default()
+            }
+            .into();
+            assert_eq!(should_snap(&mut window, &workspace, loc), snaps);
+        }
+    }
+}
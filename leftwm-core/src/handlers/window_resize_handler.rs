@@ -1,3 +1,4 @@
+use super::window_move_handler::usable_area;
 use super::{Manager, Window, WindowHandle};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
@@ -11,6 +12,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     ) -> bool {
         if let Some(w) = self.state.windows.iter_mut().find(|w| &w.handle == handle) {
             process_window(w, offset_w, offset_h);
+            if self.config.constrain_floating_to_workspace() {
+                if let Some(area) = usable_area(w, &self.state.workspaces) {
+                    w.constrain_floating_to(area, true);
+                }
+            }
             return true;
         }
         false
@@ -21,8 +27,126 @@ fn process_window(window: &mut Window, offset_w: i32, offset_h: i32) {
     window.set_floating(true);
     let mut offset = window.get_floating_offsets().unwrap_or_default();
     let start = window.start_loc.unwrap_or_default();
-    //offset.clear_minmax();
-    offset.set_w(start.w() + offset_w);
-    offset.set_h(start.h() + offset_h);
+    // Keep the size within the size hints, which don't include the border.
+    let border = 2 * window.border();
+    let (w, h) = window.keep_aspect_ratio(
+        window.normal.w() + start.w() + offset_w - border,
+        window.normal.h() + start.h() + offset_h - border,
+        offset_w.abs() >= offset_h.abs(),
+    );
+    let (w, h) = window.clamp_to_size_hints(w, h);
+    offset.set_w(w + border - window.normal.w());
+    offset.set_h(h + border - window.normal.h());
     window.set_floating_offsets(Some(offset));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::XyhwBuilder;
+    use crate::Manager;
+
+    fn hinted_window() -> Window {
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.normal = XyhwBuilder {
+            w: 300,
+            h: 200,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        window.requested = Some(
+            XyhwBuilder {
+                w: 298,
+                h: 198,
+                minw: 200,
+                maxw: 400,
+                minh: 100,
+                maxh: 300,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        window.set_floating(true);
+        window.start_loc = window.get_floating_offsets();
+        window
+    }
+
+    #[test]
+    fn resizing_is_clamped_to_the_size_hints() {
+        let mut manager = Manager::new_test(vec![]);
+        let handle = WindowHandle::MockHandle(1);
+        manager.state.windows.push(hinted_window());
+
+        assert!(manager.window_resize_handler(&handle, 500, 500));
+        let window = &manager.state.windows[0];
+        assert_eq!((window.width(), window.height()), (400, 300));
+
+        assert!(manager.window_resize_handler(&handle, -500, -500));
+        let window = &manager.state.windows[0];
+        assert_eq!((window.width(), window.height()), (200, 100));
+    }
+
+    #[test]
+    fn resizing_within_the_size_hints_is_not_clamped() {
+        let mut manager = Manager::new_test(vec![]);
+        let handle = WindowHandle::MockHandle(1);
+        manager.state.windows.push(hinted_window());
+
+        assert!(manager.window_resize_handler(&handle, 50, -50));
+        let window = &manager.state.windows[0];
+        assert_eq!((window.width(), window.height()), (348, 148));
+    }
+
+    fn aspect_manager() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec![]);
+        let mut window = hinted_window();
+        window.requested = None;
+        window.aspect = Some((16, 9));
+        manager.state.windows.push(window);
+        manager
+    }
+
+    #[test]
+    fn resizing_the_width_keeps_the_aspect_ratio() {
+        let mut manager = aspect_manager();
+        assert!(manager.window_resize_handler(&WindowHandle::MockHandle(1), 322, 0));
+        let window = &manager.state.windows[0];
+        assert_eq!((window.width(), window.height()), (620, 348));
+    }
+
+    #[test]
+    fn resizing_the_height_keeps_the_aspect_ratio() {
+        let mut manager = aspect_manager();
+        assert!(manager.window_resize_handler(&WindowHandle::MockHandle(1), 10, 172));
+        let window = &manager.state.windows[0];
+        assert_eq!((window.width(), window.height()), (657, 370));
+    }
+
+    #[test]
+    fn resizing_is_constrained_to_the_workspace_when_enabled() {
+        for constrain in [false, true] {
+            let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+                tags: vec!["1".to_string()],
+                constrain_floating_to_workspace: constrain,
+                ..TestConfig::default()
+            });
+            manager.screen_create_handler(crate::models::Screen::default());
+            let handle = WindowHandle::MockHandle(1);
+            let mut window = Window::new(handle, None, None);
+            window.set_floating(true);
+            manager.window_created_handler(window, -1, -1);
+            let window = &mut manager.state.windows[0];
+            window.start_loc = window.get_floating_offsets();
+
+            assert!(manager.window_resize_handler(&handle, 2000, 2000));
+            let area = manager.state.workspaces[0].usable_xyhw();
+            let xyhw = manager.state.windows[0].calculated_xyhw();
+            let border = 2 * manager.state.windows[0].border();
+            let fits = xyhw.x() + xyhw.w() + border <= area.x() + area.w()
+                && xyhw.y() + xyhw.h() + border <= area.y() + area.h();
+            assert_eq!(fits, constrain);
+        }
+    }
+}
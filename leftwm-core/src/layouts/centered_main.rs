@@ -0,0 +1,73 @@
+use crate::models::Tag;
+use crate::models::Window;
+use crate::models::Workspace;
+
+/// Layout which keeps the first window in a centered column, as wide as the main width of the tag.
+/// The other windows alternate between a stack on the left and one on the right.
+///
+/// 2 windows
+/// ```text
+/// +-----+-----------+-----+
+/// |     |           |     |
+/// |     |           |     |
+/// |  2  |     1     |     |
+/// |     |           |     |
+/// |     |           |     |
+/// +-----+-----------+-----+
+/// ```
+/// 5 windows
+/// ```text
+/// +-----+-----------+-----+
+/// |  2  |           |  3  |
+/// |     |           |     |
+/// +-----+     1     +-----+
+/// |  4  |           |  5  |
+/// |     |           |     |
+/// +-----+-----------+-----+
+/// ```
+pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
+    let window_count = windows.len();
+    if window_count == 0 {
+        return;
+    }
+
+    let workspace_width = workspace.width_limited(3);
+    let workspace_x = workspace.x_limited(3);
+
+    let ratio = tag.main_width_percentage().min(100.0);
+    let stack_width = (workspace_width as f32 / 100.0 * (100.0 - ratio) / 2.0).floor() as i32;
+    // Both stacks are equally wide, so the main window gets any rounding leftovers.
+    let primary_width = workspace_width - 2 * stack_width;
+    let primary_x = workspace_x + stack_width;
+
+    let (left_x, right_x) = if tag.flipped_horizontal {
+        (primary_x + primary_width, workspace_x)
+    } else {
+        (workspace_x, primary_x + primary_width)
+    };
+
+    let mut iter = windows.iter_mut();
+    if let Some(first) = iter.next() {
+        first.set_height(workspace.height());
+        first.set_width(primary_width);
+        first.set_x(primary_x);
+        first.set_y(workspace.y());
+    }
+
+    // The left stack gets the odd window out.
+    let stacked = window_count - 1;
+    let right_count = stacked / 2;
+    let left_count = stacked - right_count;
+    for (index, w) in iter.enumerate() {
+        let (x, count, position) = if index % 2 == 0 {
+            (left_x, left_count, index / 2)
+        } else {
+            (right_x, right_count, index / 2)
+        };
+        let height = workspace.height() / count as i32;
+        w.set_height(height);
+        w.set_width(stack_width);
+        w.set_x(x);
+        w.set_y(workspace.y() + height * position as i32);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::models::Tag;
+use crate::models::Window;
+use crate::models::Workspace;
+
+/// Layout which places the windows in a grid with a fixed number of columns, flowing into rows.
+/// Example arrangement (5 windows, 2 columns):
+/// ```text
+/// +---+---+
+/// |   |   |
+/// +---+---+
+/// |   |   |
+/// +---+---+
+/// |   |
+/// +---+
+/// ```
+pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
+    let window_count = windows.len();
+    if window_count == 0 {
+        return;
+    }
+
+    // Don't leave empty columns when there are fewer windows than columns.
+    let num_cols = tag.grid_columns.clamp(1, window_count);
+    let num_rows = (window_count as f32 / num_cols as f32).ceil() as usize;
+
+    let win_width = workspace.width_limited(num_cols) / num_cols as i32;
+    let win_height = workspace.height() / num_rows as i32;
+
+    for (index, win) in windows.iter_mut().enumerate() {
+        let (col, row) = (index % num_cols, index / num_cols);
+        let pos_x = if tag.flipped_horizontal {
+            num_cols - col - 1
+        } else {
+            col
+        };
+        let pos_y = if tag.flipped_vertical {
+            num_rows - row - 1
+        } else {
+            row
+        };
+
+        win.set_height(win_height);
+        win.set_width(win_width);
+        win.set_x(workspace.x_limited(num_cols) + win_width * pos_x as i32);
+        win.set_y(workspace.y() + win_height * pos_y as i32);
+    }
+}
@@ -71,3 +71,88 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
         }
     }
 }
+
+/// The deck is left of the main window when flipped, so it comes first.
+pub fn visual_order(tag: &Tag, count: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..count).collect();
+    if tag.flipped_horizontal && count > 1 {
+        order.rotate_left(1);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
+    use crate::models::{Screen, WindowHandle};
+    use crate::Manager;
+
+    fn deck_manager(flipped: bool) -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for id in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.command_handler(&Command::SetLayout(Layout::MainAndDeck));
+        manager.state.tags.get_mut(1).unwrap().flipped_horizontal = flipped;
+        manager.update_windows();
+        manager
+    }
+
+    fn stack(manager: &Manager<TestConfig, MockDisplayServer>) -> Vec<WindowHandle> {
+        manager.state.windows.iter().map(|w| w.handle).collect()
+    }
+
+    fn float_last_window(manager: &mut Manager<TestConfig, MockDisplayServer>) -> WindowHandle {
+        let handle = manager.state.windows[2].handle;
+        manager.state.focus_window(&handle);
+        manager.command_handler(&Command::ToggleFloating);
+        manager.update_windows();
+        handle
+    }
+
+    #[test]
+    fn flipped_deck_comes_before_the_main_window() {
+        let mut tag = Tag::new(1, "1", Layout::MainAndDeck);
+        assert_eq!(visual_order(&tag, 3), vec![0, 1, 2]);
+        tag.flipped_horizontal = true;
+        assert_eq!(visual_order(&tag, 3), vec![1, 2, 0]);
+        assert_eq!(visual_order(&tag, 1), vec![0]);
+    }
+
+    #[test]
+    fn focus_traversal_follows_the_visual_order() {
+        let mut manager = deck_manager(false);
+        let stack = stack(&manager);
+        let floating = float_last_window(&mut manager);
+        // Floating windows come first, then the main window left of the deck.
+        assert_eq!(
+            manager.get_next_or_previous_handle(&floating),
+            Some(stack[0])
+        );
+        assert_eq!(
+            manager.get_next_or_previous_handle(&stack[0]),
+            Some(stack[1])
+        );
+
+        let mut manager = deck_manager(true);
+        let floating = float_last_window(&mut manager);
+        // The deck is left of the main window now.
+        assert_eq!(
+            manager.get_next_or_previous_handle(&floating),
+            Some(stack[1])
+        );
+        assert_eq!(
+            manager.get_next_or_previous_handle(&stack[1]),
+            Some(stack[0])
+        );
+    }
+}
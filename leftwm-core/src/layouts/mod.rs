@@ -1,6 +1,6 @@
 use super::models::Window;
 use super::models::Workspace;
-use crate::models::Tag;
+use crate::models::{Side, Tag};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use thiserror::Error;
@@ -8,9 +8,11 @@ use thiserror::Error;
 mod center_main;
 mod center_main_balanced;
 mod center_main_fluid;
+mod centered_main;
 mod even_horizontal;
 mod even_vertical;
 mod fibonacci;
+mod grid;
 mod grid_horizontal;
 mod left_main;
 mod main_and_deck;
@@ -35,6 +37,8 @@ pub enum Layout {
     Monocle,
     RightWiderLeftStack,
     LeftWiderRightStack,
+    Grid,
+    CenteredMain,
 }
 
 pub const LAYOUTS: &[Layout] = &[
@@ -52,6 +56,8 @@ pub const LAYOUTS: &[Layout] = &[
     Layout::Monocle,
     Layout::RightWiderLeftStack,
     Layout::LeftWiderRightStack,
+    Layout::Grid,
+    Layout::CenteredMain,
 ];
 
 impl Default for Layout {
@@ -62,11 +68,13 @@ impl Default for Layout {
 
 // This is tedious, but simple and effective.
 impl Layout {
+    /// The number of columns of a `Grid` layout, unless configured otherwise.
+    pub const GRID_COLUMNS: usize = 2;
+
     pub fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
         match self {
-            Self::MainAndVertStack | Self::LeftWiderRightStack => {
-                main_and_vert_stack::update(workspace, tag, windows);
-            }
+            Self::MainAndVertStack => main_at_position(workspace, tag, windows),
+            Self::LeftWiderRightStack => main_and_vert_stack::update(workspace, tag, windows),
             Self::MainAndHorizontalStack => {
                 main_and_horizontal_stack::update(workspace, tag, windows);
             }
@@ -83,9 +91,25 @@ impl Layout {
             Self::RightWiderLeftStack => {
                 right_main_and_vert_stack::update(workspace, tag, windows);
             }
+            Self::Grid => grid::update(workspace, tag, windows),
+            Self::CenteredMain => centered_main::update(workspace, tag, windows),
+        }
+    }
+
+    /// Returns the stack indices of `count` tiled windows in the order they appear on screen.
+    /// Focus traversal follows this order, the stack order is kept for everything else.
+    pub fn visual_order(&self, tag: &Tag, count: usize) -> Vec<usize> {
+        match self {
+            Self::MainAndDeck => main_and_deck::visual_order(tag, count),
+            _ => (0..count).collect(),
         }
     }
 
+    /// Returns true if both are the same layout, regardless of their parameters.
+    pub fn is_same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
     pub const fn main_width(&self) -> u8 {
         match self {
             Self::RightWiderLeftStack | Self::LeftWiderRightStack => 75,
@@ -97,7 +121,7 @@ impl Layout {
     pub fn rotations(&self) -> Vec<(bool, bool)> {
         match self {
             //Layouts that can be flipped both ways
-            Self::Fibonacci | Self::GridHorizontal => {
+            Self::Fibonacci | Self::GridHorizontal | Self::Grid => {
                 [(false, false), (true, false), (true, true), (false, true)].to_vec()
             }
             //Layouts that can be flipped vertically
@@ -131,11 +155,30 @@ impl FromStr for Layout {
             "Monocle" => Ok(Self::Monocle),
             "RightWiderLeftStack" => Ok(Self::RightWiderLeftStack),
             "LeftWiderRightStack" => Ok(Self::LeftWiderRightStack),
+            "Grid" => Ok(Self::Grid),
+            "CenteredMain" => Ok(Self::CenteredMain),
             _ => Err(ParseLayoutError(s.to_string())),
         }
     }
 }
 
+/// Places the main window on the side chosen for the tag, stacking the others on the opposite side.
+fn main_at_position(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
+    if let Some(side) = &tag.main_position {
+        let tag = Tag {
+            flipped_horizontal: side == &Side::Right,
+            flipped_vertical: side == &Side::Bottom,
+            ..tag.clone()
+        };
+        match side {
+            Side::Left | Side::Right => main_and_vert_stack::update(workspace, &tag, windows),
+            Side::Top | Side::Bottom => main_and_horizontal_stack::update(workspace, &tag, windows),
+        }
+    } else {
+        main_and_vert_stack::update(workspace, tag, windows);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,7 +216,7 @@ mod tests {
 
     #[test]
     fn test_from_str() {
-        let layout_strs: [&str; 14] = [
+        let layout_strs: [&str; 16] = [
             "MainAndVertStack",
             "MainAndHorizontalStack",
             "MainAndDeck",
@@ -188,6 +231,8 @@ mod tests {
             "Monocle",
             "RightWiderLeftStack",
             "LeftWiderRightStack",
+            "Grid",
+            "CenteredMain",
         ];
 
         assert_eq!(layout_strs.len(), LAYOUTS.len());
@@ -199,4 +244,135 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn grid_places_four_windows_in_two_columns_as_two_by_two() {
+        let mut ws = Workspace::new(
+            None,
+            BBox {
+                width: 800,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::Grid,
+            None,
+        );
+        ws.margin = Margins::new(0);
+        ws.update_avoided_areas();
+        let mut tag = Tag::new(1, "1", Layout::Grid);
+        tag.grid_columns = 2;
+        let mut windows: Vec<Window> = (1..=4)
+            .map(|id| {
+                let mut w = Window::new(WindowHandle::MockHandle(id), None, None);
+                w.border = 0;
+                w.margin = Margins::new(0);
+                w
+            })
+            .collect();
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        Layout::Grid.update_windows(&ws, &mut refs, &tag);
+
+        let geometry: Vec<(i32, i32, i32, i32)> = windows
+            .iter()
+            .map(|w| (w.x(), w.y(), w.width(), w.height()))
+            .collect();
+        assert_eq!(
+            geometry,
+            vec![
+                (0, 0, 400, 300),
+                (400, 0, 400, 300),
+                (0, 300, 400, 300),
+                (400, 300, 400, 300),
+            ]
+        );
+    }
+
+    #[test]
+    fn main_position_places_the_main_window_on_each_side() {
+        let mut ws = Workspace::new(
+            None,
+            BBox {
+                width: 800,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::MainAndVertStack,
+            None,
+        );
+        ws.margin = Margins::new(0);
+        ws.update_avoided_areas();
+        let cases = [
+            (Side::Left, (0, 0, 400, 600), (400, 0, 400, 600)),
+            (Side::Right, (400, 0, 400, 600), (0, 0, 400, 600)),
+            (Side::Top, (0, 0, 800, 300), (0, 300, 800, 300)),
+            (Side::Bottom, (0, 300, 800, 300), (0, 0, 800, 300)),
+        ];
+        for (side, main, stack) in cases {
+            let mut tag = Tag::new(1, "1", Layout::MainAndVertStack);
+            tag.main_position = Some(side.clone());
+            let mut windows: Vec<Window> = (1..=2)
+                .map(|id| {
+                    let mut w = Window::new(WindowHandle::MockHandle(id), None, None);
+                    w.border = 0;
+                    w.margin = Margins::new(0);
+                    w
+                })
+                .collect();
+            let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+            Layout::MainAndVertStack.update_windows(&ws, &mut refs, &tag);
+
+            let geometry: Vec<(i32, i32, i32, i32)> = windows
+                .iter()
+                .map(|w| (w.x(), w.y(), w.width(), w.height()))
+                .collect();
+            assert_eq!(geometry, vec![main, stack], "main on the {side:?}");
+        }
+    }
+
+    #[test]
+    fn centered_main_keeps_the_main_window_centered() {
+        let layout = Layout::CenteredMain;
+        let mut ws = Workspace::new(
+            None,
+            BBox {
+                width: 1000,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            layout,
+            None,
+        );
+        ws.margin = Margins::new(10);
+        ws.gutters = vec![crate::models::Gutter::new(
+            crate::models::Side::Left,
+            30,
+            None,
+        )];
+        let tag = Tag::new(1, "1", layout);
+
+        for count in [2, 3, 4, 5] {
+            let mut windows: Vec<Window> = (1..=count)
+                .map(|id| {
+                    let mut w = Window::new(WindowHandle::MockHandle(id), None, None);
+                    w.border = 0;
+                    w.margin = Margins::new(0);
+                    w
+                })
+                .collect();
+            let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+            layout.update_windows(&ws, &mut refs, &tag);
+
+            let main = &windows[0];
+            let left_margin = main.x() - ws.x();
+            let right_margin = ws.x() + ws.width() - (main.x() + main.width());
+            assert_eq!(left_margin, right_margin, "{count} windows");
+            assert!(windows[1].x() < main.x(), "{count} windows");
+            if count > 2 {
+                assert!(windows[2].x() > main.x(), "{count} windows");
+            }
+        }
+    }
 }
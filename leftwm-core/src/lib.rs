@@ -35,11 +35,12 @@ pub use config::Config;
 pub use display_action::DisplayAction;
 pub use display_event::DisplayEvent;
 pub use display_servers::DisplayServer;
+pub use handlers::command_handler::execute_command;
 pub use models::Manager;
 pub use models::Mode;
 pub use models::Window;
 pub use models::Workspace;
 pub use state::State;
 pub use utils::child_process;
-pub use utils::command_pipe::CommandPipe;
+pub use utils::command_pipe::{parse_external_command, CommandPipe};
 pub use utils::state_socket::StateSocket;
@@ -1,6 +1,9 @@
+use crate::config::InsertBehavior;
 use crate::layouts::Layout;
+use crate::models::{Window, WindowHandle};
 use crate::state::State;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Viewport {
@@ -20,6 +23,114 @@ pub struct ManagerState {
     pub active_desktop: Vec<String>,
     pub working_tags: Vec<String>,
     pub urgent_tags: Vec<String>,
+    // The tags with a window demanding attention, by label.
+    #[serde(default)]
+    pub attention_tags: Vec<String>,
+    // The number of windows on each tag, by label.
+    #[serde(default)]
+    pub window_counts: HashMap<String, usize>,
+    // Where new windows are inserted.
+    #[serde(default)]
+    pub insert_behavior: InsertBehavior,
+}
+
+/// The focused window, as reported by `QueryFocusedWindow`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FocusedWindow {
+    pub handle: WindowHandle,
+    pub x: i32,
+    pub y: i32,
+    pub h: i32,
+    pub w: i32,
+    pub floating: bool,
+    pub tag: Option<String>,
+    pub class: Option<String>,
+}
+
+impl FocusedWindow {
+    #[must_use]
+    pub fn from_state(state: &State) -> Option<Self> {
+        let window = state.focus_manager.window(&state.windows)?;
+        let xyhw = window.calculated_xyhw();
+        Some(Self {
+            handle: window.handle,
+            x: xyhw.x(),
+            y: xyhw.y(),
+            h: xyhw.h(),
+            w: xyhw.w(),
+            floating: window.floating(),
+            tag: window
+                .tag
+                .and_then(|tag| state.tags.get(tag))
+                .map(|tag| tag.label.clone()),
+            class: window.res_class.clone(),
+        })
+    }
+}
+
+/// A visible window, as logged by `DumpLayout`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WindowGeometry {
+    pub handle: WindowHandle,
+    pub class: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub h: i32,
+    pub w: i32,
+}
+
+/// The layout of a workspace and the windows it shows, as logged by `DumpLayout`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WorkspaceLayout {
+    pub id: Option<i32>,
+    pub tag: Option<String>,
+    pub layout: Layout,
+    // The usable area of the workspace.
+    pub x: i32,
+    pub y: i32,
+    pub h: i32,
+    pub w: i32,
+    pub windows: Vec<WindowGeometry>,
+}
+
+impl WorkspaceLayout {
+    #[must_use]
+    pub fn from_state(state: &State) -> Vec<Self> {
+        state
+            .workspaces
+            .iter()
+            .map(|ws| {
+                let tag = ws.tag.and_then(|tag| state.tags.get(tag));
+                let usable = ws.usable_xyhw();
+                let windows = state
+                    .windows
+                    .iter()
+                    .filter(|w| w.visible() && ws.is_displaying(w))
+                    .map(|w| {
+                        let xyhw = w.calculated_xyhw();
+                        WindowGeometry {
+                            handle: w.handle,
+                            class: w.res_class.clone(),
+                            x: xyhw.x(),
+                            y: xyhw.y(),
+                            h: xyhw.h(),
+                            w: xyhw.w(),
+                        }
+                    })
+                    .collect();
+                Self {
+                    id: ws.id,
+                    tag: tag.map(|tag| tag.label.clone()),
+                    layout: tag.map_or(ws.layout, |tag| tag.layout),
+                    x: usable.x(),
+                    y: usable.y(),
+                    h: usable.h(),
+                    w: usable.w(),
+                    windows,
+                }
+            })
+            .collect()
+    }
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -31,7 +142,9 @@ pub struct TagsForWorkspace {
     pub visible: bool,
     pub focused: bool,
     pub urgent: bool,
+    pub attention: bool,
     pub busy: bool,
+    pub windows: usize,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DisplayWorkspace {
@@ -46,6 +159,7 @@ pub struct DisplayWorkspace {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DisplayState {
     pub window_title: String,
+    pub insert_behavior: InsertBehavior,
     pub workspaces: Vec<DisplayWorkspace>,
 }
 
@@ -56,35 +170,24 @@ impl From<ManagerState> for DisplayState {
             .viewports
             .iter()
             .enumerate()
-            .map(|(i, vp)| {
-                viewport_into_display_workspace(
-                    &m.desktop_names,
-                    &m.active_desktop,
-                    &visible,
-                    &m.working_tags,
-                    &m.urgent_tags,
-                    vp,
-                    i,
-                )
-            })
+            .map(|(i, vp)| viewport_into_display_workspace(&m, &visible, vp, i))
             .collect();
         Self {
             workspaces,
             window_title: m.window_title.unwrap_or_default(),
+            insert_behavior: m.insert_behavior,
         }
     }
 }
 
 fn viewport_into_display_workspace(
-    all_tags: &[String],
-    focused: &[String],
+    m: &ManagerState,
     visible: &[String],
-    working_tags: &[String],
-    urgent_tags: &[String],
     viewport: &Viewport,
     ws_index: usize,
 ) -> DisplayWorkspace {
-    let tags: Vec<TagsForWorkspace> = all_tags
+    let tags: Vec<TagsForWorkspace> = m
+        .desktop_names
         .iter()
         .enumerate()
         .map(|(index, t)| TagsForWorkspace {
@@ -92,9 +195,11 @@ fn viewport_into_display_workspace(
             index,
             mine: viewport.tag == *t,
             visible: visible.contains(t),
-            focused: focused.contains(t),
-            urgent: urgent_tags.contains(t),
-            busy: working_tags.contains(t),
+            focused: m.active_desktop.contains(t),
+            urgent: m.urgent_tags.contains(t),
+            attention: m.attention_tags.contains(t),
+            busy: m.working_tags.contains(t),
+            windows: m.window_counts.get(t).copied().unwrap_or_default(),
         })
         .collect();
     DisplayWorkspace {
@@ -126,6 +231,16 @@ impl From<&State> for ManagerState {
             .filter(|tag| state.windows.iter().any(|w| w.has_tag(&tag.id) && w.urgent))
             .map(|t| t.label.clone())
             .collect();
+        let attention_tags = state
+            .tags
+            .all()
+            .iter()
+            .filter(|tag| {
+                let demanding = |w: &Window| w.has_tag(&tag.id) && w.demands_attention;
+                state.windows.iter().any(demanding)
+            })
+            .map(|t| t.label.clone())
+            .collect();
         for ws in &state.workspaces {
             let tag_label = ws
                 .tag
@@ -154,6 +269,11 @@ impl From<&State> for ManagerState {
             Some(win) => win.name.clone(),
             None => None,
         };
+        let window_counts = state
+            .window_count_by_tag()
+            .into_iter()
+            .filter_map(|(id, count)| Some((state.tags.get(id)?.label.clone(), count)))
+            .collect();
         Self {
             window_title,
             desktop_names: state
@@ -165,7 +285,10 @@ impl From<&State> for ManagerState {
             viewports,
             active_desktop,
             urgent_tags,
+            attention_tags,
             working_tags,
+            window_counts,
+            insert_behavior: state.insert_behavior,
         }
     }
 }
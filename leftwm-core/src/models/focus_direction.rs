@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A direction on the screen, used to find the window next to another one.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Error)]
+#[error("Could not parse direction: {0}")]
+pub struct ParseFocusDirectionError(String);
+
+impl FromStr for FocusDirection {
+    type Err = ParseFocusDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            "Left" => Ok(Self::Left),
+            "Right" => Ok(Self::Right),
+            _ => Err(ParseFocusDirectionError(s.to_string())),
+        }
+    }
+}
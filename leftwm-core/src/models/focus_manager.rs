@@ -33,10 +33,53 @@ impl FocusBehaviour {
     }
 }
 
+/// What happens to the focused window when switching to a tag without windows.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyTagFocus {
+    /// Unfocus the window, nothing is focused on the empty tag.
+    Clear,
+    /// Keep the last focused window focused, even though it isn't visible anymore.
+    KeepLast,
+}
+
+// Deriving needs `#[default]`, which is newer than the supported Rust version.
+#[allow(clippy::derivable_impls)]
+impl Default for EmptyTagFocus {
+    fn default() -> Self {
+        Self::Clear
+    }
+}
+
+/// What gets focused after the last window of a workspace has been closed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusAfterLastClose {
+    /// Focus the window under the cursor when sloppy focus follows the mouse, otherwise unfocus.
+    FollowFocusBehaviour,
+    /// Go back to the previously viewed tag and focus its last focused window.
+    PreviousTag,
+    /// Unfocus the window, nothing is focused.
+    None,
+    /// Focus whatever window is under the cursor.
+    UnderCursor,
+}
+
+// Deriving needs `#[default]`, which is newer than the supported Rust version.
+#[allow(clippy::derivable_impls)]
+impl Default for FocusAfterLastClose {
+    fn default() -> Self {
+        Self::FollowFocusBehaviour
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FocusManager {
     pub behaviour: FocusBehaviour,
     pub focus_new_windows: bool,
+    #[serde(default)]
+    pub prevent_focus_stealing: bool,
+    pub focus_on_empty_tag: EmptyTagFocus,
+    #[serde(default)]
+    pub focus_after_last_close: FocusAfterLastClose,
     pub workspace_history: VecDeque<usize>,
     pub window_history: VecDeque<MaybeWindowHandle>,
     pub tag_history: VecDeque<TagId>,
@@ -50,6 +93,9 @@ impl FocusManager {
         Self {
             behaviour: config.focus_behaviour(),
             focus_new_windows: config.focus_new_windows(),
+            prevent_focus_stealing: config.prevent_focus_stealing(),
+            focus_on_empty_tag: config.focus_on_empty_tag(),
+            focus_after_last_close: config.focus_after_last_close(),
             workspace_history: Default::default(),
             window_history: Default::default(),
             tag_history: Default::default(),
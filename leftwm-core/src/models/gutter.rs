@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
 
 type WorkSpaceID = i32;
 
@@ -10,6 +12,24 @@ pub enum Side {
     Right,
 }
 
+#[derive(Debug, Error)]
+#[error("Could not parse side: {0}")]
+pub struct ParseSideError(String);
+
+impl FromStr for Side {
+    type Err = ParseSideError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Top" => Ok(Self::Top),
+            "Bottom" => Ok(Self::Bottom),
+            "Left" => Ok(Self::Left),
+            "Right" => Ok(Self::Right),
+            _ => Err(ParseSideError(s.to_string())),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
 pub struct Gutter {
     pub side: Side,
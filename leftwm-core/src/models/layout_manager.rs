@@ -20,6 +20,9 @@ pub struct LayoutManager {
     pub mode: LayoutMode,
     pub layouts: Vec<Layout>,
     pub layouts_per_workspaces: HashMap<i32, Vec<Layout>>,
+    /// Layouts of tags which have not been shown yet, keyed by tag label.
+    #[serde(default)]
+    pub default_layouts: HashMap<String, Layout>,
 }
 
 impl LayoutManager {
@@ -40,9 +43,15 @@ impl LayoutManager {
             mode: config.layout_mode(),
             layouts: config.layouts(),
             layouts_per_workspaces,
+            default_layouts: config.default_layouts().unwrap_or_default(),
         }
     }
 
+    /// Returns the configured layout of a tag the first time it is shown.
+    pub fn take_default_layout(&mut self, label: &str) -> Option<Layout> {
+        self.default_layouts.remove(label)
+    }
+
     pub fn new_layout(&self, workspace_id: Option<i32>) -> Layout {
         *self
             .layouts(workspace_id)
@@ -53,7 +62,10 @@ impl LayoutManager {
     pub fn next_layout(&self, workspace: &Workspace) -> Layout {
         let layouts = self.layouts(workspace.id);
 
-        let next = match layouts.iter().position(|&x| x == workspace.layout) {
+        let next = match layouts
+            .iter()
+            .position(|x| x.is_same_kind(&workspace.layout))
+        {
             Some(index) if index == layouts.len() - 1 => layouts.first(),
             Some(index) => layouts.get(index + 1),
             None => None,
@@ -67,7 +79,10 @@ impl LayoutManager {
     pub fn previous_layout(&self, workspace: &Workspace) -> Layout {
         let layouts = self.layouts(workspace.id);
 
-        let next = match layouts.iter().position(|&x| x == workspace.layout) {
+        let next = match layouts
+            .iter()
+            .position(|x| x.is_same_kind(&workspace.layout))
+        {
             Some(index) if index == 0 => layouts.last(),
             Some(index) => layouts.get(index - 1),
             None => None,
@@ -79,12 +94,16 @@ impl LayoutManager {
     }
 
     pub fn update_layouts(
-        &self,
+        &mut self,
         workspaces: &mut Vec<Workspace>,
         mut tags: Vec<&mut Tag>,
     ) -> Option<bool> {
         for workspace in workspaces {
             let tag = tags.iter_mut().find(|t| Some(t.id) == workspace.tag)?;
+            if let Some(layout) = self.take_default_layout(&tag.label) {
+                tag.layout = layout;
+                workspace.layout = layout;
+            }
             match self.mode {
                 LayoutMode::Workspace => {
                     tag.set_layout(workspace.layout, workspace.main_width_percentage);
@@ -1,8 +1,15 @@
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::models::TagId;
 use crate::state::State;
 use crate::utils::child_process::Children;
+#[cfg(not(test))]
+use crate::utils::child_process::{exec_shell, exec_shell_for_tag};
 use std::sync::{atomic::AtomicBool, Arc};
+use std::time::{Duration, Instant};
+
+/// How long a tag has to keep the focus, in milliseconds, before its focus commands run.
+pub(crate) const TAG_FOCUS_DEBOUNCE_MS: u64 = 200;
 
 /// Maintains current program state.
 #[derive(Debug)]
@@ -13,6 +20,13 @@ pub struct Manager<C, SERVER> {
     pub(crate) children: Children,
     pub(crate) reap_requested: Arc<AtomicBool>,
     pub(crate) reload_requested: bool,
+    // The focused tag the tag focus commands were last run for.
+    pub(crate) cmd_focused_tag: Option<TagId>,
+    // The tag focused since the last tag focus commands ran, and since when.
+    pub(crate) tag_focus_change: Option<(Option<TagId>, Instant)>,
+    // The shell commands run by `run_shell`, with their tag, which tests note down instead.
+    #[cfg(test)]
+    pub(crate) shell_cmds: Vec<(String, Option<String>)>,
     pub display_server: SERVER,
 }
 
@@ -29,6 +43,10 @@ where
             children: Default::default(),
             reap_requested: Default::default(),
             reload_requested: false,
+            cmd_focused_tag: None,
+            tag_focus_change: None,
+            #[cfg(test)]
+            shell_cmds: vec![],
         }
     }
 }
@@ -51,8 +69,66 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         self.display_server
             .load_config(&self.config, focused, &self.state.windows);
         self.state.load_config(&self.config);
+        self.run_reload_cmd();
         true
     }
+
+    /// Runs the `on_reload` command of the config, if there is one.
+    pub(crate) fn run_reload_cmd(&mut self) {
+        if let Some(cmd) = &self.config.on_reload_cmd() {
+            self.run_shell(cmd, None);
+        }
+    }
+
+    /// Runs the commands of the tags which lost and got the focus since they last ran,
+    /// once the focused tag kept the focus for [`TAG_FOCUS_DEBOUNCE_MS`].
+    /// The tags passed through while switching rapidly run nothing.
+    pub(crate) fn run_tag_focus_cmds(&mut self) {
+        let focused = self.state.focus_manager.tag(0);
+        if focused == self.cmd_focused_tag {
+            self.tag_focus_change = None;
+            return;
+        }
+        match self.tag_focus_change {
+            Some((tag, since)) if tag == focused => {
+                if since.elapsed() < Duration::from_millis(TAG_FOCUS_DEBOUNCE_MS) {
+                    return;
+                }
+            }
+            _ => {
+                self.tag_focus_change = Some((focused, Instant::now()));
+                return;
+            }
+        }
+        self.tag_focus_change = None;
+        let unfocused = std::mem::replace(&mut self.cmd_focused_tag, focused);
+        let cmds = [
+            (unfocused, self.config.on_tag_unfocus_cmd()),
+            (focused, self.config.on_tag_focus_cmd()),
+        ];
+        for (tag, cmds) in cmds {
+            let label = tag
+                .and_then(|tag| self.state.tags.get(tag))
+                .map(|tag| tag.label.clone());
+            if let (Some(label), Some(cmds)) = (label, cmds) {
+                if let Some(cmd) = cmds.get(&label) {
+                    self.run_shell(cmd, Some(&label));
+                }
+            }
+        }
+    }
+
+    /// Runs `cmd` in a shell, with the label of `tag` in `LEFTWM_TAG` if there is one.
+    fn run_shell(&mut self, cmd: &str, tag: Option<&str>) {
+        #[cfg(not(test))]
+        match tag {
+            Some(tag) => exec_shell_for_tag(cmd, tag, &mut self.children),
+            None => exec_shell(cmd, &mut self.children),
+        };
+        #[cfg(test)]
+        self.shell_cmds
+            .push((cmd.to_string(), tag.map(ToString::to_string)));
+    }
 }
 
 #[cfg(test)]
@@ -75,3 +151,83 @@ impl Manager<crate::config::tests::TestConfig, crate::display_servers::MockDispl
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::Screen;
+    use std::collections::HashMap;
+
+    #[test]
+    fn reloading_runs_the_reload_command_once_per_reload() {
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            on_reload_cmd: Some("true".to_string()),
+            ..TestConfig::default()
+        });
+        assert!(manager.shell_cmds.is_empty());
+
+        manager.reload_config();
+        assert_eq!(manager.shell_cmds, vec![("true".to_string(), None)]);
+        manager.reload_config();
+        assert_eq!(manager.shell_cmds.len(), 2);
+    }
+
+    // Lets the pending tag focus change settle as if the debounce time had passed.
+    fn settle_tag_focus(manager: &mut Manager<TestConfig, MockDisplayServer>) {
+        manager.run_tag_focus_cmds();
+        if let Some((_, since)) = &mut manager.tag_focus_change {
+            *since -= Duration::from_millis(TAG_FOCUS_DEBOUNCE_MS);
+        }
+        manager.run_tag_focus_cmds();
+    }
+
+    #[test]
+    fn tag_focus_commands_run_once_per_focus_change() {
+        let cmds = |cmd: &str| Some(HashMap::from([("2".to_string(), cmd.to_string())]));
+        let mut manager: Manager<TestConfig, MockDisplayServer> = Manager::new(TestConfig {
+            tags: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            on_tag_focus_cmd: cmds("focus"),
+            on_tag_unfocus_cmd: cmds("unfocus"),
+            ..TestConfig::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        settle_tag_focus(&mut manager);
+        assert!(manager.shell_cmds.is_empty());
+
+        manager.state.goto_tag_handler(2);
+        manager.run_tag_focus_cmds();
+        // Nothing runs until the tag kept the focus for a while.
+        assert!(manager.shell_cmds.is_empty());
+        settle_tag_focus(&mut manager);
+        settle_tag_focus(&mut manager);
+        let focus = ("focus".to_string(), Some("2".to_string()));
+        assert_eq!(manager.shell_cmds, vec![focus.clone()]);
+
+        // Switching away and back at once doesn't run anything.
+        manager.state.goto_tag_handler(3);
+        manager.run_tag_focus_cmds();
+        manager.state.goto_tag_handler(2);
+        settle_tag_focus(&mut manager);
+        assert_eq!(manager.shell_cmds.len(), 1);
+
+        // Passing through a tag with commands before they settle doesn't run them either.
+        manager.state.goto_tag_handler(3);
+        settle_tag_focus(&mut manager);
+        let unfocus = ("unfocus".to_string(), Some("2".to_string()));
+        assert_eq!(manager.shell_cmds, vec![focus.clone(), unfocus.clone()]);
+        manager.state.goto_tag_handler(2);
+        manager.run_tag_focus_cmds();
+        manager.state.goto_tag_handler(1);
+        settle_tag_focus(&mut manager);
+        assert_eq!(manager.shell_cmds, vec![focus, unfocus]);
+    }
+
+    #[test]
+    fn reloading_without_a_reload_command_starts_nothing() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.reload_config();
+        assert!(manager.shell_cmds.is_empty());
+    }
+}
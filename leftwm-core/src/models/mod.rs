@@ -1,11 +1,13 @@
 //! Objects (such as windows) used to develop `LeftWM`.
 mod dock_area;
+mod focus_direction;
 mod focus_manager;
 mod gutter;
 mod layout_manager;
 mod manager;
 mod margins;
 mod mode;
+mod overview;
 mod scratchpad;
 mod screen;
 mod size;
@@ -22,6 +24,9 @@ pub mod dto;
 use crate::layouts;
 
 pub use dock_area::DockArea;
+pub use focus_direction::FocusDirection;
+pub use focus_manager::EmptyTagFocus;
+pub use focus_manager::FocusAfterLastClose;
 pub use focus_manager::FocusBehaviour;
 pub use focus_manager::FocusManager;
 pub use gutter::Gutter;
@@ -29,9 +34,11 @@ pub use gutter::Side;
 pub use layout_manager::LayoutManager;
 pub use layout_manager::LayoutMode;
 pub use manager::Manager;
+pub(crate) use manager::TAG_FOCUS_DEBOUNCE_MS;
 pub use margins::Margins;
 pub use mode::Mode;
-pub use scratchpad::{ScratchPad, ScratchPadName};
+pub use overview::Overview;
+pub use scratchpad::{ScratchPad, ScratchPadEdge, ScratchPadName};
 pub use screen::{BBox, Screen};
 pub use size::Size;
 pub use window::Window;
@@ -39,7 +46,7 @@ pub use window::WindowHandle;
 pub use window_change::WindowChange;
 pub use window_state::WindowState;
 pub use window_type::WindowType;
-pub use workspace::Workspace;
+pub use workspace::{Gaps, Workspace};
 pub use xyhw::Xyhw;
 pub use xyhw::XyhwBuilder;
 pub use xyhw_change::XyhwChange;
@@ -0,0 +1,17 @@
+use crate::layouts::Layout;
+use crate::models::{TagId, WindowHandle};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Windows of all tags gathered on one tag, so they can be looked at side by side.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Overview {
+    /// The tag the windows are gathered on.
+    pub tag: TagId,
+    /// The layout the tag had before the overview was shown.
+    pub layout: Layout,
+    /// The number of grid columns the tag had before the overview was shown.
+    pub grid_columns: usize,
+    /// The tag each gathered window came from.
+    pub window_tags: HashMap<WindowHandle, Option<TagId>>,
+}
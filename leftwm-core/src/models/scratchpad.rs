@@ -15,15 +15,50 @@ pub struct ScratchPad {
     pub height: Option<Size>,
     // relative width of scratchpad, 50 means 50% of workspace width
     pub width: Option<Size>,
+    // hide the scratchpad when one of its windows loses the focus
+    #[serde(default)]
+    pub hide_on_focus_loss: bool,
+    // windows of this WM_CLASS belong to the scratchpad, besides the ones of the started process
+    #[serde(default)]
+    pub class: Option<String>,
+    // edge of the workspace the scratchpad rests against, overriding its x or y
+    #[serde(default)]
+    pub position: ScratchPadEdge,
+}
+
+/// Where a scratchpad rests on its workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScratchPadEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    /// Placed by its `x` and `y`, which center it by default.
+    Center,
+}
+
+// Deriving needs `#[default]`, which is newer than the supported Rust version.
+#[allow(clippy::derivable_impls)]
+impl Default for ScratchPadEdge {
+    fn default() -> Self {
+        Self::Center
+    }
 }
 
 impl ScratchPad {
     // Get size and position of scratchpad from config and workspace size.
     pub fn xyhw(&self, xyhw: &Xyhw) -> Xyhw {
-        let x_sane = sane_dimension(self.x, 0.25, xyhw.w());
-        let y_sane = sane_dimension(self.y, 0.25, xyhw.h());
+        let mut x_sane = sane_dimension(self.x, 0.25, xyhw.w());
+        let mut y_sane = sane_dimension(self.y, 0.25, xyhw.h());
         let height_sane = sane_dimension(self.height, 0.50, xyhw.h());
         let width_sane = sane_dimension(self.width, 0.50, xyhw.w());
+        match self.position {
+            ScratchPadEdge::Top => y_sane = 0,
+            ScratchPadEdge::Bottom => y_sane = xyhw.h() - height_sane,
+            ScratchPadEdge::Left => x_sane = 0,
+            ScratchPadEdge::Right => x_sane = xyhw.w() - width_sane,
+            ScratchPadEdge::Center => {}
+        }
 
         XyhwBuilder {
             x: xyhw.x() + x_sane,
@@ -74,3 +109,42 @@ fn sane_dimension(config_value: Option<Size>, default_ratio: f32, max_pixel: i32
         _ => Size::Ratio(default_ratio).into_absolute(max_pixel),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratchpad(position: ScratchPadEdge) -> ScratchPad {
+        ScratchPad {
+            name: "dropdown".into(),
+            value: "alacritty".to_string(),
+            x: None,
+            y: None,
+            height: Some(Size::Ratio(0.4)),
+            width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position,
+        }
+    }
+
+    #[test]
+    fn scratchpads_rest_against_their_edge() {
+        let workspace: Xyhw = XyhwBuilder {
+            x: 100,
+            y: 50,
+            h: 600,
+            w: 800,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        let top = scratchpad(ScratchPadEdge::Top).xyhw(&workspace);
+        let bottom = scratchpad(ScratchPadEdge::Bottom).xyhw(&workspace);
+        let center = scratchpad(ScratchPadEdge::Center).xyhw(&workspace);
+
+        assert_eq!((top.x(), top.y(), top.h(), top.w()), (300, 50, 240, 400));
+        assert_eq!((bottom.x(), bottom.y()), (300, 410));
+        assert_eq!((center.x(), center.y()), (300, 200));
+        assert_eq!(bottom.h(), top.h());
+    }
+}
@@ -1,3 +1,4 @@
+use super::workspace::default_scale;
 use super::{DockArea, Size, WindowHandle};
 use crate::config::Workspace;
 use serde::{Deserialize, Serialize};
@@ -11,10 +12,20 @@ pub struct Screen {
     pub bbox: BBox,
     pub wsid: Option<i32>,
     pub max_window_width: Option<Size>,
+    #[serde(default)]
+    pub no_new_windows: bool,
+    #[serde(default)]
+    pub always_float: Option<bool>,
+    // The output scale, pixel based sizes are multiplied by it.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    // The tag to show when the workspace is created.
+    #[serde(default)]
+    pub default_tag: Option<usize>,
 }
 
 /// Screen Bounding Box
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct BBox {
     pub x: i32,
     pub y: i32,
@@ -30,6 +41,10 @@ impl Screen {
             bbox,
             wsid: None,
             max_window_width: None,
+            no_new_windows: false,
+            always_float: None,
+            scale: 1.0,
+            default_tag: None,
         }
     }
 
@@ -60,22 +75,36 @@ impl Screen {
     }
 }
 
-impl From<&Workspace> for Screen {
-    fn from(wsc: &Workspace) -> Self {
+impl Screen {
+    /// Creates the screen for a workspace defined in the config.
+    /// Geometry left on `auto` is taken from the `detected` screen.
+    #[must_use]
+    pub fn from_workspace_config(wsc: &Workspace, detected: Option<&BBox>) -> Self {
+        let detected = detected.copied().unwrap_or_default();
         Self {
             root: WindowHandle::MockHandle(0),
             bbox: BBox {
-                height: wsc.height,
-                width: wsc.width,
-                x: wsc.x,
-                y: wsc.y,
+                height: wsc.height.unwrap_or(detected.height),
+                width: wsc.width.unwrap_or(detected.width),
+                x: wsc.x.unwrap_or(detected.x),
+                y: wsc.y.unwrap_or(detected.y),
             },
             wsid: wsc.id,
             max_window_width: wsc.max_window_width,
+            no_new_windows: wsc.no_new_windows,
+            always_float: wsc.always_float,
+            scale: wsc.valid_scale().unwrap_or_else(default_scale),
+            default_tag: wsc.default_tag,
         }
     }
 }
 
+impl From<&Workspace> for Screen {
+    fn from(wsc: &Workspace) -> Self {
+        Self::from_workspace_config(wsc, None)
+    }
+}
+
 impl From<&xlib::XWindowAttributes> for Screen {
     fn from(root: &xlib::XWindowAttributes) -> Self {
         Self {
@@ -88,6 +117,10 @@ impl From<&xlib::XWindowAttributes> for Screen {
             },
             wsid: None,
             max_window_width: None,
+            no_new_windows: false,
+            always_float: None,
+            scale: 1.0,
+            default_tag: None,
         }
     }
 }
@@ -104,6 +137,10 @@ impl From<&x11_dl::xinerama::XineramaScreenInfo> for Screen {
             },
             wsid: None,
             max_window_width: None,
+            no_new_windows: false,
+            always_float: None,
+            scale: 1.0,
+            default_tag: None,
         }
     }
 }
@@ -120,6 +157,47 @@ impl Default for Screen {
             },
             wsid: None,
             max_window_width: None,
+            no_new_windows: false,
+            always_float: None,
+            scale: 1.0,
+            default_tag: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETECTED: BBox = BBox {
+        x: 1920,
+        y: 0,
+        width: 3440,
+        height: 1440,
+    };
+
+    #[test]
+    fn auto_workspace_geometry_is_taken_from_the_detected_screen() {
+        let screen = Screen::from_workspace_config(&Workspace::default(), Some(&DETECTED));
+        assert_eq!(screen.bbox, DETECTED);
+    }
+
+    #[test]
+    fn explicit_workspace_geometry_overrides_the_detected_screen() {
+        let wsc = Workspace {
+            x: Some(3640),
+            width: Some(1720),
+            id: Some(1),
+            ..Workspace::default()
+        };
+        let screen = Screen::from_workspace_config(&wsc, Some(&DETECTED));
+        let expected = BBox {
+            x: 3640,
+            y: 0,
+            width: 1720,
+            height: 1440,
+        };
+        assert_eq!(screen.bbox, expected);
+        assert_eq!(screen.wsid, Some(1));
+    }
+}
@@ -1,4 +1,4 @@
-use super::TagId;
+use super::{Side, TagId};
 use crate::{layouts::Layout, Window, Workspace};
 use serde::{Deserialize, Serialize};
 
@@ -28,7 +28,8 @@ use serde::{Deserialize, Serialize};
 /// A hidden tag is a tag that is invisible and unknown to the user.
 /// Those tags are created in the source code and can be used for
 /// various purposes. The Scratchpad (NSP) feature is an example which uses
-/// a hidden Tag called "NSP" to hide away the scratchpad until its summoned again.
+/// a hidden Tag called "NSP" (see `scratchpad_tag_label`) to hide away the scratchpad until its
+/// summoned again.
 /// You can think of a hidden tag as an invisible window storage. It is not possible
 /// for a user to display a hidden tag in a workspace.
 ///
@@ -72,6 +73,37 @@ impl Tags {
         self.add_new(next_id.to_string().as_str(), layout)
     }
 
+    /// Move the normal tag with ID `from` to the position of ID `to`,
+    /// shifting the tags in between and re-numbering all normal tags.
+    /// Returns `false` if either ID does not belong to a normal tag.
+    ///
+    /// Use [`Tags::moved_id`] to update references to the old IDs.
+    pub fn move_tag(&mut self, from: TagId, to: TagId) -> bool {
+        let ids = 1..=self.normal.len();
+        if !ids.contains(&from) || !ids.contains(&to) {
+            return false;
+        }
+        let tag = self.normal.remove(from - 1);
+        self.normal.insert(to - 1, tag);
+        for (index, tag) in self.normal.iter_mut().enumerate() {
+            tag.id = index + 1; // tag id starts at 1
+        }
+        true
+    }
+
+    /// The ID a tag has after [`Tags::move_tag`] moved the tag `from` to `to`.
+    pub const fn moved_id(id: TagId, from: TagId, to: TagId) -> TagId {
+        if id == from {
+            to
+        } else if from < id && id <= to {
+            id - 1
+        } else if to <= id && id < from {
+            id + 1
+        } else {
+            id
+        }
+    }
+
     // todo: add_new_at(position, label, layout)
     // -> shifting all one to the right and re-number them (vec.insert)
 
@@ -179,6 +211,7 @@ impl Default for Tags {
 /// all Workspaces, this means there aren't multiple instances of
 /// the same Tag on different Screens.
 #[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Tag {
     /// Unique identifier for the tag,
     /// this is automatically assigned by `LeftWM`.
@@ -222,6 +255,31 @@ pub struct Tag {
     pub flipped_horizontal: bool,
     pub flipped_vertical: bool,
     pub layout_rotation: usize,
+
+    /// New windows float once this
+    /// many windows are tiled on the tag.
+    #[serde(default)]
+    pub max_tiled_windows: Option<usize>,
+
+    /// Only the main window is shown
+    /// while the stack is hidden.
+    #[serde(default)]
+    pub stack_hidden: bool,
+
+    /// The side the main window of
+    /// `MainAndVertStack` is placed on,
+    /// instead of following the flips.
+    #[serde(default)]
+    pub main_position: Option<Side>,
+
+    /// The number of columns
+    /// of the `Grid` layout.
+    #[serde(default = "default_grid_columns")]
+    pub grid_columns: usize,
+}
+
+const fn default_grid_columns() -> usize {
+    Layout::GRID_COLUMNS
 }
 
 impl Tag {
@@ -236,6 +294,10 @@ impl Tag {
             flipped_horizontal: false,
             flipped_vertical: false,
             layout_rotation: 0,
+            max_tiled_windows: None,
+            stack_hidden: false,
+            main_position: None,
+            grid_columns: Layout::GRID_COLUMNS,
         }
     }
 
@@ -268,8 +330,13 @@ impl Tag {
                 .iter_mut()
                 .filter(|w| w.has_tag(&self.id) && w.is_managed() && !w.floating())
                 .collect();
-            self.layout
-                .update_windows(workspace, &mut managed_nonfloat, self);
+            // With the stack hidden the main window is shown alone, keeping the layout.
+            let layout = if self.stack_hidden {
+                Layout::Monocle
+            } else {
+                self.layout
+            };
+            layout.update_windows(workspace, &mut managed_nonfloat, self);
             for w in &mut managed_nonfloat {
                 w.container_size = Some(workspace.xyhw);
             }
@@ -446,4 +513,36 @@ mod tests {
         let second_retrieve = tags.get_mut(2).unwrap();
         assert_eq!(second_retrieve.label, String::from("code"));
     }
+
+    #[test]
+    fn moving_a_tag_shifts_and_renumbers_the_tags_in_between() {
+        let mut tags = Tags::new();
+        tags.add_new("home", Layout::default());
+        tags.add_new("chat", Layout::default());
+        tags.add_new("surf", Layout::default());
+        tags.add_new("code", Layout::default());
+        let nsp = tags.add_new_hidden("NSP").unwrap();
+
+        assert!(tags.move_tag(1, 3));
+
+        let labels: Vec<&str> = tags.normal().iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(labels, vec!["chat", "surf", "home", "code"]);
+        let ids: Vec<usize> = tags.normal().iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(Tags::moved_id(1, 1, 3), 3);
+        assert_eq!(Tags::moved_id(3, 1, 3), 2);
+        assert_eq!(Tags::moved_id(4, 1, 3), 4);
+        assert_eq!(Tags::moved_id(nsp, 1, 3), nsp);
+    }
+
+    #[test]
+    fn moving_a_tag_out_of_range_does_nothing() {
+        let mut tags = Tags::new();
+        tags.add_new("home", Layout::default());
+        tags.add_new("chat", Layout::default());
+
+        assert!(!tags.move_tag(0, 1));
+        assert!(!tags.move_tag(1, 3));
+        assert_eq!(tags.get(1).unwrap().label, "home");
+    }
 }
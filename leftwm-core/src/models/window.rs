@@ -13,7 +13,7 @@ use x11_dl::xlib;
 
 type MockHandle = i32;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum WindowHandle {
     MockHandle(MockHandle),
     XlibHandle(xlib::Window),
@@ -55,11 +55,16 @@ pub struct Window {
     pub pid: Option<u32>,
     pub r#type: WindowType,
     pub tag: Option<TagId>,
+    // Further tags the window belongs to, it is shown on them when they are displayed.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub border: i32,
     pub margin: Margins,
     pub margin_multiplier: f32,
     states: Vec<WindowState>,
     pub requested: Option<Xyhw>,
+    // The aspect ratio (width, height) the window asks to keep.
+    pub aspect: Option<(u32, u32)>,
     pub normal: Xyhw,
     pub start_loc: Option<Xyhw>,
     pub container_size: Option<Xyhw>,
@@ -67,6 +72,37 @@ pub struct Window {
     // Two strings that are within a XClassHint, kept separate for simpler comparing.
     pub res_name: Option<String>,
     pub res_class: Option<String>,
+    // How well the applied window rule matched, 0 if none was applied.
+    #[serde(default)]
+    pub rule_score: u8,
+    // The tag the window was placed on by the window rules, to notice when the user moved it.
+    #[serde(default)]
+    pub spawn_tag: Option<TagId>,
+    // The `_NET_STARTUP_ID` of the launch the window belongs to.
+    #[serde(default)]
+    pub startup_id: Option<String>,
+    // The window asked for no decorations, e.g. through `_MOTIF_WM_HINTS`.
+    #[serde(default)]
+    pub no_border: bool,
+    // A child window configured to tile instead of floating over its parent.
+    #[serde(default)]
+    pub tile_transient: bool,
+    // The floating state, offsets and position among the tiled windows to restore once the
+    // window is no longer maximized.
+    #[serde(default)]
+    pub maximized_from: Option<(bool, Option<Xyhw>, usize)>,
+    // Tooltips, menus and other windows placing themselves, they are never managed.
+    #[serde(default)]
+    pub override_redirect: bool,
+    // The window asked for attention through `_NET_WM_STATE_DEMANDS_ATTENTION`.
+    #[serde(default)]
+    pub demands_attention: bool,
+    // Kept floating and out of the layout by `ToggleIgnoreInLayout`.
+    #[serde(default)]
+    pub ignore_in_layout: bool,
+    // Left out when cycling the focus, set for `_NET_WM_STATE_SKIP_TASKBAR`.
+    #[serde(default)]
+    pub skip_focus_cycle: bool,
 }
 
 impl Window {
@@ -87,18 +123,30 @@ impl Window {
             legacy_name: None,
             r#type: WindowType::Normal,
             tag: None,
+            extra_tags: vec![],
             border: 1,
             margin: Margins::new(10),
             margin_multiplier: 1.0,
             states: vec![],
             normal: XyhwBuilder::default().into(),
             requested: None,
+            aspect: None,
             floating: None,
             start_loc: None,
             container_size: None,
             strut: None,
             res_name: None,
             res_class: None,
+            rule_score: 0,
+            spawn_tag: None,
+            startup_id: None,
+            no_border: false,
+            tile_transient: false,
+            maximized_from: None,
+            override_redirect: false,
+            demands_attention: false,
+            ignore_in_layout: false,
+            skip_focus_cycle: false,
         }
     }
 
@@ -132,6 +180,25 @@ impl Window {
         self.floating
     }
 
+    /// Keeps the floating window within the area. When `resize` is set, the window is shrunk to
+    /// fit, otherwise it is moved.
+    pub fn constrain_floating_to(&mut self, area: Xyhw, resize: bool) {
+        if let Some(mut offset) = self.floating {
+            let outer = self.normal + offset;
+            let (right, bottom) = (area.x() + area.w(), area.y() + area.h());
+            if resize {
+                offset.set_w(outer.w().min(right - outer.x()) - self.normal.w());
+                offset.set_h(outer.h().min(bottom - outer.y()) - self.normal.h());
+            } else {
+                let x = outer.x().min(right - outer.w()).max(area.x());
+                let y = outer.y().min(bottom - outer.h()).max(area.y());
+                offset.set_x(x - self.normal.x());
+                offset.set_y(y - self.normal.y());
+            }
+            self.floating = Some(offset);
+        }
+    }
+
     pub fn reset_float_offset(&mut self) {
         let mut new_value = Xyhw::default();
         new_value.clear_minmax();
@@ -161,10 +228,29 @@ impl Window {
         self.states.contains(&WindowState::Sticky)
     }
 
+    /// The window asked to be kept above other windows (`_NET_WM_STATE_ABOVE`).
+    #[must_use]
+    pub fn is_above(&self) -> bool {
+        self.states.contains(&WindowState::Above)
+    }
+
+    /// The window asked to be kept below other windows (`_NET_WM_STATE_BELOW`).
+    #[must_use]
+    pub fn is_below(&self) -> bool {
+        self.states.contains(&WindowState::Below)
+    }
+
+    #[must_use]
+    pub fn is_modal_dialog(&self) -> bool {
+        self.transient.is_some()
+            && (self.states.contains(&WindowState::Modal) || self.r#type == WindowType::Dialog)
+    }
+
     #[must_use]
     pub fn must_float(&self) -> bool {
         self.must_float
-            || self.transient.is_some()
+            || self.ignore_in_layout
+            || (self.transient.is_some() && !self.tile_transient)
             || !self.is_managed()
             || self.r#type == WindowType::Splash
     }
@@ -226,11 +312,11 @@ impl Window {
             value = self.normal.w();
         } else if self.floating() && self.floating.is_some() {
             let relative = self.normal + self.floating.unwrap_or_default();
-            value = relative.w() - (self.border * 2);
+            value = relative.w() - (self.border() * 2);
         } else {
             value = self.normal.w()
                 - (((self.margin.left + self.margin.right) as f32) * self.margin_multiplier) as i32
-                - (self.border * 2);
+                - (self.border() * 2);
         }
         let limit = match self.requested {
             Some(requested) if requested.minw() > 0 && self.floating() => requested.minw(),
@@ -249,11 +335,11 @@ impl Window {
             value = self.normal.h();
         } else if self.floating() && self.floating.is_some() {
             let relative = self.normal + self.floating.unwrap_or_default();
-            value = relative.h() - (self.border * 2);
+            value = relative.h() - (self.border() * 2);
         } else {
             value = self.normal.h()
                 - (((self.margin.top + self.margin.bottom) as f32) * self.margin_multiplier) as i32
-                - (self.border * 2);
+                - (self.border() * 2);
         }
         let limit = match self.requested {
             Some(requested) if requested.minh() > 0 && self.floating() => requested.minh(),
@@ -274,7 +360,7 @@ impl Window {
 
     #[must_use]
     pub fn border(&self) -> i32 {
-        if self.is_fullscreen() {
+        if self.is_fullscreen() || self.no_border {
             0
         } else {
             self.border
@@ -317,6 +403,40 @@ impl Window {
         .into()
     }
 
+    /// Clamps a size to the min and max size hints of the window.
+    /// The hints don't include the border, so neither should the size.
+    #[must_use]
+    pub fn clamp_to_size_hints(&self, width: i32, height: i32) -> (i32, i32) {
+        // A max size of 0 is sometimes sent to mean no max size.
+        let clamp = |value: i32, min: i32, max: i32| {
+            let max = if max > 0 { max } else { i32::MAX };
+            value.min(max).max(min)
+        };
+        self.requested.map_or((width, height), |requested| {
+            (
+                clamp(width, requested.minw(), requested.maxw()),
+                clamp(height, requested.minh(), requested.maxh()),
+            )
+        })
+    }
+
+    /// Adjusts a size to the aspect ratio of the window, if it has one.
+    /// The width is kept if `keep_width` is true, otherwise the height is kept.
+    #[must_use]
+    pub fn keep_aspect_ratio(&self, width: i32, height: i32, keep_width: bool) -> (i32, i32) {
+        let (aspect_w, aspect_h) = match self.aspect {
+            Some((w, h)) if w > 0 && h > 0 => (i64::from(w), i64::from(h)),
+            _ => return (width, height),
+        };
+        if keep_width {
+            let height = i64::from(width) * aspect_h / aspect_w;
+            (width, i32::try_from(height).unwrap_or(i32::MAX))
+        } else {
+            let width = i64::from(height) * aspect_w / aspect_h;
+            (i32::try_from(width).unwrap_or(i32::MAX), height)
+        }
+    }
+
     #[must_use]
     pub fn exact_xyhw(&self) -> Xyhw {
         if self.floating() && self.floating.is_some() {
@@ -335,9 +455,10 @@ impl Window {
         self.tag = Some(*tag);
     }
 
+    /// Returns true if the window is on the tag, as its tag or one of its extra tags.
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
-        self.tag == Some(*tag)
+        self.tag == Some(*tag) || self.extra_tags.contains(tag)
     }
 
     pub fn untag(&mut self) {
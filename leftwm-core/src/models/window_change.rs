@@ -14,12 +14,18 @@ pub struct WindowChange {
     pub transient: Option<MaybeWindowHandle>,
     pub never_focus: Option<bool>,
     pub urgent: Option<bool>,
+    pub demands_attention: Option<bool>,
+    pub skip_focus_cycle: Option<bool>,
     pub name: Option<MaybeName>,
     pub r#type: Option<WindowType>,
     pub floating: Option<XyhwChange>,
     pub strut: Option<XyhwChange>,
     pub requested: Option<Xyhw>,
+    pub aspect: Option<Option<(u32, u32)>>,
     pub states: Option<Vec<WindowState>>,
+    pub res_name: Option<MaybeName>,
+    pub res_class: Option<MaybeName>,
+    pub no_border: Option<bool>,
 }
 
 impl WindowChange {
@@ -32,10 +38,16 @@ impl WindowChange {
             name: None,
             r#type: None,
             urgent: None,
+            demands_attention: None,
+            skip_focus_cycle: None,
             floating: None,
             strut: None,
             requested: None,
+            aspect: None,
             states: None,
+            res_name: None,
+            res_class: None,
+            no_border: None,
         }
     }
 
@@ -51,22 +63,42 @@ impl WindowChange {
             changed = changed || changed_name;
             window.name = name.clone();
         }
+        if let Some(res_name) = &self.res_name {
+            changed = changed || &window.res_name != res_name;
+            window.res_name.clone_from(res_name);
+        }
+        if let Some(res_class) = &self.res_class {
+            changed = changed || &window.res_class != res_class;
+            window.res_class.clone_from(res_class);
+        }
         if let Some(nf) = self.never_focus {
             let changed_nf = window.never_focus != nf;
             changed = changed || changed_nf;
             window.never_focus = nf;
         }
+        if let Some(no_border) = self.no_border {
+            changed = changed || window.no_border != no_border;
+            window.no_border = no_border;
+        }
         if let Some(urgent) = self.urgent {
             let changed_urgent = window.urgent != urgent;
             changed = changed || changed_urgent;
             window.urgent = urgent;
         }
+        if let Some(demands_attention) = self.demands_attention {
+            changed = changed || window.demands_attention != demands_attention;
+            window.demands_attention = demands_attention;
+        }
+        if let Some(skip_focus_cycle) = self.skip_focus_cycle {
+            changed = changed || window.skip_focus_cycle != skip_focus_cycle;
+            window.skip_focus_cycle = skip_focus_cycle;
+        }
         if let Some(mut floating_change) = self.floating {
             // Reposition if dialog or modal.
             if let Some(outer) = container {
                 let mut xyhw = Xyhw::default();
                 floating_change.update(&mut xyhw);
-                xyhw.center_relative(outer, window.border);
+                xyhw.center_relative(outer, window.border());
                 floating_change.x = Some(xyhw.x());
                 floating_change.y = Some(xyhw.y());
             }
@@ -80,6 +112,9 @@ impl WindowChange {
         if let Some(requested) = self.requested {
             window.requested = Some(requested);
         }
+        if let Some(aspect) = self.aspect {
+            window.aspect = aspect;
+        }
         if let Some(r#type) = &self.r#type {
             let changed_type = &window.r#type != r#type;
             changed = changed || changed_type;
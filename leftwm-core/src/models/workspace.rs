@@ -20,6 +20,30 @@ pub struct Workspace {
     pub xyhw: Xyhw,
     xyhw_avoided: Xyhw,
     pub max_window_width: Option<Size>,
+    /// New windows are never placed on this workspace.
+    #[serde(default)]
+    pub no_new_windows: bool,
+    /// New windows on this workspace float, unless it is `None` and the global setting is used.
+    #[serde(default)]
+    pub always_float: Option<bool>,
+    /// The gaps put aside while they are hidden by `ToggleGaps`.
+    #[serde(default)]
+    pub hidden_gaps: Option<Gaps>,
+    /// The output scale, pixel based defaults like the floating size are multiplied by it.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+}
+
+pub(crate) const fn default_scale() -> f64 {
+    1.0
+}
+
+/// Margins and gutters of a workspace.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Gaps {
+    pub margin: Margins,
+    pub gutters: Vec<Gutter>,
+    pub margin_multiplier: f32,
 }
 
 impl fmt::Debug for Workspace {
@@ -75,12 +99,47 @@ impl Workspace {
             }
             .into(),
             max_window_width,
+            no_new_windows: false,
+            always_float: None,
+            hidden_gaps: None,
+            scale: default_scale(),
         }
     }
 
     pub fn load_config(&mut self, config: &impl Config) {
-        self.margin = config.workspace_margin().unwrap_or_else(|| Margins::new(0));
-        self.gutters = self.get_gutters_for_theme(config);
+        let margin = config.workspace_margin().unwrap_or_else(|| Margins::new(0));
+        let gutters = self.get_gutters_for_theme(config);
+        // Hidden gaps stay hidden, they come back with the new values.
+        if let Some(gaps) = &mut self.hidden_gaps {
+            gaps.margin = margin;
+            gaps.gutters = gutters;
+        } else {
+            self.margin = margin;
+            self.gutters = gutters;
+        }
+    }
+
+    /// Removes the margins and gutters of the workspace, or puts them back if they were removed.
+    pub fn toggle_gaps(&mut self) {
+        if let Some(gaps) = self.hidden_gaps.take() {
+            self.margin = gaps.margin;
+            self.gutters = gaps.gutters;
+            self.margin_multiplier = gaps.margin_multiplier;
+            return;
+        }
+        self.hidden_gaps = Some(Gaps {
+            margin: self.margin,
+            gutters: std::mem::take(&mut self.gutters),
+            margin_multiplier: self.margin_multiplier,
+        });
+        self.margin = Margins::new(0);
+        self.margin_multiplier = 0.0;
+    }
+
+    /// Converts a size in pixels to the scale of the workspace, rounding to the nearest pixel.
+    #[must_use]
+    pub fn scaled(&self, pixels: i32) -> i32 {
+        (f64::from(pixels) * self.scale).round() as i32
     }
 
     pub fn get_gutters_for_theme(&mut self, config: &impl Config) -> Vec<Gutter> {
@@ -118,10 +177,7 @@ impl Workspace {
     /// Returns true if the workspace is displays a given window.
     #[must_use]
     pub fn is_displaying(&self, window: &Window) -> bool {
-        if let Some(tag) = &window.tag {
-            return self.has_tag(tag);
-        }
-        false
+        matches!(self.tag, Some(tag) if window.has_tag(&tag))
     }
 
     /// Returns true if the workspace is to update the locations info of this window.
@@ -187,6 +243,19 @@ impl Workspace {
         }
     }
 
+    /// Returns the area windows can be placed in, after docks, gutters and margins are removed.
+    #[must_use]
+    pub fn usable_xyhw(&self) -> Xyhw {
+        XyhwBuilder {
+            x: self.x(),
+            y: self.y(),
+            h: self.height(),
+            w: self.width(),
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+
     fn get_gutter(&self, side: &Side) -> i32 {
         match self.gutters.iter().find(|g| &g.side == side) {
             Some(g) => g.value,
@@ -281,4 +350,75 @@ mod tests {
         w.tag(&TAG_ID);
         assert!(subject.is_displaying(&w), "workspace should include window");
     }
+
+    #[test]
+    fn toggling_gaps_frees_the_whole_workspace_and_restores_it() {
+        let mut subject = Workspace::new(
+            None,
+            BBox {
+                width: 800,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::default(),
+            None,
+        );
+        subject.margin = Margins::new(5);
+        subject.gutters = vec![Gutter::new(Side::Left, 20, None)];
+        let usable = subject.usable_xyhw();
+
+        subject.toggle_gaps();
+        assert_eq!(subject.usable_xyhw(), subject.xyhw);
+        subject.toggle_gaps();
+        assert_eq!(subject.usable_xyhw(), usable);
+        assert!(subject.hidden_gaps.is_none());
+    }
+
+    #[test]
+    fn scaled_sizes_round_to_the_nearest_pixel() {
+        let mut subject = Workspace::new(None, BBox::default(), Layout::default(), None);
+        assert_eq!(subject.scaled(10), 10);
+        subject.scale = 2.0;
+        assert_eq!(subject.scaled(10), 20);
+        subject.scale = 1.25;
+        assert_eq!(subject.scaled(10), 13);
+        assert_eq!(subject.scaled(-10), -13);
+    }
+
+    #[test]
+    fn usable_xyhw_removes_docks_gutters_and_margins() {
+        let mut subject = Workspace::new(
+            None,
+            BBox {
+                width: 800,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::default(),
+            None,
+        );
+        subject.margin = Margins::new(5);
+        subject.gutters = vec![Gutter::new(Side::Left, 20, None)];
+        subject.avoid = vec![XyhwBuilder {
+            x: 0,
+            y: 0,
+            w: 800,
+            h: 30,
+            ..XyhwBuilder::default()
+        }
+        .into()];
+        subject.update_avoided_areas();
+
+        let expected: Xyhw = XyhwBuilder {
+            x: 25,
+            y: 35,
+            w: 770,
+            h: 560,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        assert_eq!(subject.usable_xyhw(), expected);
+    }
 }
@@ -1,17 +1,22 @@
 //! Save and restore manager state.
 
 use crate::child_process::ChildID;
-use crate::config::{Config, InsertBehavior, ScratchPad};
+use crate::config::{
+    Config, DisableTileDrag, FloatingPlacement, InsertBehavior, Mousebind, ScratchPad,
+};
+use crate::handlers::window_handler::is_scratchpad;
 use crate::layouts::Layout;
 use crate::models::{
-    FocusManager, LayoutManager, Mode, ScratchPadName, Screen, Size, Tags, Window, WindowHandle,
-    WindowType, Workspace,
+    FocusManager, LayoutManager, Mode, Overview, ScratchPadName, Screen, Size, TagId, Tags, Window,
+    WindowHandle, WindowType, Workspace, Xyhw,
 };
 use crate::DisplayAction;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 
 #[derive(Serialize, Deserialize, Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct State {
     pub screens: Vec<Screen>,
     pub windows: Vec<Window>,
@@ -25,22 +30,100 @@ pub struct State {
     pub actions: VecDeque<DisplayAction>,
     pub tags: Tags, // List of all known tags.
     pub mousekey: Vec<String>,
+    #[serde(default)]
+    pub mousebinds: Vec<Mousebind>,
     pub max_window_width: Option<Size>,
     pub default_width: i32,
     pub default_height: i32,
-    pub disable_tile_drag: bool,
+    pub disable_tile_drag: DisableTileDrag,
     pub insert_behavior: InsertBehavior,
+    #[serde(default)]
+    pub floating_placement: FloatingPlacement,
+    // Where the last cascaded floating window was placed on each workspace, by workspace id.
+    #[serde(skip)]
+    pub cascade_positions: HashMap<Option<i32>, (i32, i32)>,
     pub single_window_border: bool,
+    #[serde(default)]
+    pub follow_modal_dialogs: bool,
+    #[serde(default)]
+    pub float_on_cursor_monitor: bool,
+    #[serde(default)]
+    pub inherit_floating_from_focused: bool,
+    // Whether child windows float or tile, keyed by `WM_CLASS`.
+    #[serde(default)]
+    pub transient_float: HashMap<String, bool>,
+    #[serde(default)]
+    pub keep_fullscreen_on_new_window: bool,
+    // Whether the struts of docks are avoided, false for auto-hiding bars.
+    #[serde(default = "default_respect_dock_struts")]
+    pub respect_dock_struts: bool,
+    // Whether docks are stacked above dialogs and floating windows, or below all windows.
+    #[serde(default)]
+    pub docks_on_top: bool,
+    // Added to the border widths of the theme by `IncreaseBorder` and `DecreaseBorder`.
+    #[serde(default)]
+    pub border_width_offset: i32,
+    // Label of the hidden tag for scratchpads.
+    #[serde(default)]
+    pub scratchpad_tag_label: String,
+    // Scratchpad geometries saved at runtime, relative to the workspace.
+    #[serde(default)]
+    pub scratchpad_geometries: HashMap<ScratchPadName, Xyhw>,
+    // Windows marked with a key to jump back to them.
+    #[serde(default)]
+    pub marks: HashMap<char, WindowHandle>,
+    // Number of window classes whose last tag is remembered, `None` if disabled.
+    #[serde(default)]
+    pub remember_app_tags: Option<usize>,
+    // The tags the last windows of a class were on when closed, most recent first.
+    #[serde(default)]
+    pub app_tags: VecDeque<(String, TagId)>,
+    // Windows gathered on one tag by `ToggleOverview`, while it is shown.
+    #[serde(default)]
+    pub overview: Option<Overview>,
+    // Whether the windows of the overlay tag are shown above the focused workspace.
+    #[serde(default)]
+    pub overlay_shown: bool,
+    // Label of the hidden tag for the windows of the overlay.
+    #[serde(default)]
+    pub overlay_tag_label: String,
+    // The tags modal dialogs were on before they started following the focused tag.
+    #[serde(skip)]
+    pub modal_dialog_tags: HashMap<WindowHandle, Option<TagId>>,
+    // Apps launched by `Execute` and `Spawn` which have not opened their window yet.
+    #[serde(skip)]
+    pub launches: Vec<Launch>,
+    // Urgent windows, in the order they became urgent.
+    #[serde(skip)]
+    pub urgent_windows: Vec<WindowHandle>,
 }
 
 impl State {
     pub(crate) fn new(config: &impl Config) -> Self {
         let layout_manager = LayoutManager::new(config);
         let mut tags = Tags::new();
-        config.create_list_of_tag_labels().iter().for_each(|label| {
-            tags.add_new(label.as_str(), layout_manager.new_layout(None));
-        });
-        tags.add_new_hidden("NSP");
+        let labels = config.create_list_of_tag_labels();
+        let max_tiled_windows = config.max_tiled_windows().unwrap_or_default();
+        for label in &labels {
+            let id = tags.add_new(label.as_str(), layout_manager.new_layout(None));
+            if let Some(tag) = tags.get_mut(id) {
+                tag.max_tiled_windows = max_tiled_windows.get(label).copied();
+            }
+        }
+        for label in layout_manager.default_layouts.keys() {
+            if !labels.contains(label) {
+                tracing::warn!("Default layout given for unknown tag: {}", label);
+            }
+        }
+        for label in max_tiled_windows.keys() {
+            if !labels.contains(label) {
+                tracing::warn!("Maximum of tiled windows given for unknown tag: {}", label);
+            }
+        }
+        let scratchpad_tag_label = config.scratchpad_tag_label();
+        tags.add_new_hidden(&scratchpad_tag_label);
+        let overlay_tag_label = config.overlay_tag_label();
+        tags.add_new_hidden(&overlay_tag_label);
 
         Self {
             focus_manager: FocusManager::new(config),
@@ -56,15 +139,52 @@ impl State {
             tags,
             max_window_width: config.max_window_width(),
             mousekey: config.mousekey(),
+            mousebinds: config.mousebinds(),
             default_width: config.default_width(),
             default_height: config.default_height(),
             disable_tile_drag: config.disable_tile_drag(),
             insert_behavior: config.insert_behavior(),
+            floating_placement: config.floating_placement(),
+            cascade_positions: Default::default(),
             single_window_border: config.single_window_border(),
+            follow_modal_dialogs: config.follow_modal_dialogs(),
+            float_on_cursor_monitor: config.float_on_cursor_monitor(),
+            inherit_floating_from_focused: config.inherit_floating_from_focused(),
+            transient_float: config.transient_float().unwrap_or_default(),
+            keep_fullscreen_on_new_window: config.keep_fullscreen_on_new_window(),
+            respect_dock_struts: config.respect_dock_struts(),
+            docks_on_top: config.docks_on_top(),
+            border_width_offset: 0,
+            scratchpad_tag_label,
+            scratchpad_geometries: Default::default(),
+            marks: Default::default(),
+            remember_app_tags: config.remember_app_tags(),
+            app_tags: Default::default(),
+            overview: Default::default(),
+            overlay_shown: false,
+            overlay_tag_label,
+            modal_dialog_tags: Default::default(),
+            launches: Default::default(),
+            urgent_windows: Default::default(),
         }
     }
 
-    // Sorts the windows and puts them in order of importance.
+    /// Sorts the windows and puts them in order of importance.
+    ///
+    /// The windows are grouped into layers, from top to bottom:
+    /// 1. children of fullscreen windows,
+    /// 2. fullscreen windows,
+    /// 3. docks, with `docks_on_top`,
+    /// 4. dialogs, splashes, utilities and menus,
+    /// 5. windows which asked to be kept above others,
+    /// 6. floating windows,
+    /// 7. tiled windows,
+    /// 8. windows which asked to be kept below others,
+    /// 9. everything else, e.g. desktop windows and docks without `docks_on_top`.
+    ///
+    /// The sort is stable, windows within the same layer keep their relative order. Sorting an
+    /// already sorted list therefore never changes it, so this only needs to be called when a
+    /// window changes layer or is added.
     pub fn sort_windows(&mut self) {
         // The windows we are managing should be behind unmanaged windows. Unless they are
         // fullscreen, or their children.
@@ -78,9 +198,19 @@ impl State {
                 level2.contains(&w.transient.unwrap_or_else(|| 0.into()))
             });
 
+        // Windows on the shown overlay.
+        let (overlay_level, overlay, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+            partition_windows(other.iter(), |w| self.is_on_shown_overlay(w));
+
         // Left over managed windows.
+        // Docks, unless they stay below everything with the other windows left over.
+        let (level3, docks, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+            partition_windows(other.iter(), |w| {
+                self.docks_on_top && w.r#type == WindowType::Dock
+            });
+
         // Dialogs and modals.
-        let (level3, dialogs, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+        let (level4, dialogs, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
             partition_windows(other.iter(), |w| {
                 w.r#type == WindowType::Dialog
                     || w.r#type == WindowType::Splash
@@ -88,63 +218,92 @@ impl State {
                     || w.r#type == WindowType::Menu
             });
 
+        // Windows asking to be above others.
+        let (level5, above, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+            partition_windows(other.iter(), |w| {
+                w.r#type == WindowType::Normal && w.is_above()
+            });
+
+        // Windows asking to be below others.
+        let (level8, below, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+            partition_windows(other.iter(), |w| {
+                w.r#type == WindowType::Normal && w.is_below()
+            });
+
         // Floating windows.
-        let (level4, floating, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+        let (level6, floating, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
             partition_windows(other.iter(), |w| {
                 w.r#type == WindowType::Normal && w.floating()
             });
 
         // Tiled windows.
-        let (level5, tiled, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
+        let (level7, tiled, other): (Vec<WindowHandle>, Vec<Window>, Vec<Window>) =
             partition_windows(other.iter(), |w| w.r#type == WindowType::Normal);
 
-        // Last docks.
-        let level6: Vec<WindowHandle> = other.iter().map(|w| w.handle).collect();
+        // Last everything else.
+        let level9: Vec<WindowHandle> = other.iter().map(|w| w.handle).collect();
 
         self.windows = [
             fullscreen_children,
             fullscreen_windows,
+            overlay,
+            docks,
             dialogs,
+            above,
             floating,
             tiled,
+            below,
             other,
         ]
         .concat();
 
         let fullscreen: Vec<WindowHandle> = [level1, level2].concat();
-        let handles: Vec<WindowHandle> = [level3, level4, level5, level6].concat();
+        let handles: Vec<WindowHandle> = [
+            overlay_level,
+            level3,
+            level4,
+            level5,
+            level6,
+            level7,
+            level8,
+            level9,
+        ]
+        .concat();
         let act = DisplayAction::SetWindowOrder(fullscreen, handles);
         self.actions.push_back(act);
     }
 
-    pub fn handle_single_border(&mut self, border_width: i32) {
-        if self.single_window_border {
-            return;
-        }
-
+    /// Gives the windows the border width for floating or tiled windows,
+    /// unless they are alone on their tag and `single_window_border` is off.
+    /// Only the windows whose border changes are touched, returns whether there were any.
+    pub fn update_borders(&mut self, config: &impl Config) -> bool {
+        let floating_width = (config.floating_border_width() + self.border_width_offset).max(0);
+        let tiled_width = (config.tiled_border_width() + self.border_width_offset).max(0);
+        let mut changed = false;
         for tag in self.tags.normal() {
             let mut windows_on_tag: Vec<&mut Window> = self
                 .windows
                 .iter_mut()
                 .filter(|w| w.tag.unwrap_or(0) == tag.id && w.r#type == WindowType::Normal)
                 .collect();
+            let borderless = !self.single_window_border
+                && (tag.layout == Layout::Monocle || windows_on_tag.len() == 1);
 
-            if tag.layout == Layout::Monocle {
-                windows_on_tag.iter_mut().for_each(|w| w.border = 0);
-                continue;
-            }
-
-            if windows_on_tag.len() == 1 {
-                if let Some(w) = windows_on_tag.first_mut() {
-                    w.border = 0;
+            for w in &mut windows_on_tag {
+                let border = if borderless {
+                    0
+                } else if w.floating() {
+                    floating_width
+                } else {
+                    tiled_width
+                };
+                if w.border != border {
+                    w.border = border;
+                    changed = true;
                 }
-                continue;
             }
-
-            windows_on_tag
-                .iter_mut()
-                .for_each(|w| w.border = border_width);
         }
+        changed
     }
 
     pub fn move_to_top(&mut self, handle: &WindowHandle) -> Option<()> {
@@ -155,6 +314,117 @@ impl State {
         Some(())
     }
 
+    /// The managed windows placed by the layout of a workspace, see [`Workspace::is_managed`].
+    pub fn windows_on_workspace<'a>(
+        &'a self,
+        workspace: &'a Workspace,
+    ) -> impl DoubleEndedIterator<Item = &'a Window> + 'a {
+        self.windows.iter().filter(move |w| workspace.is_managed(w))
+    }
+
+    /// The managed windows on a tag, whether it is shown or not.
+    pub fn windows_on_tag(&self, tag: TagId) -> impl DoubleEndedIterator<Item = &Window> {
+        self.windows
+            .iter()
+            .filter(move |w| w.has_tag(&tag) && w.is_managed())
+    }
+
+    /// Updates every tag id kept in the state after [`Tags::move_tag`] moved the tag `from` to
+    /// `to`, so everything stays with the tag it was on.
+    pub(crate) fn remap_tag(&mut self, from: TagId, to: TagId) {
+        let moved = |id: TagId| Tags::moved_id(id, from, to);
+        for window in &mut self.windows {
+            for tag in &mut window.extra_tags {
+                *tag = moved(*tag);
+            }
+            window.spawn_tag = window.spawn_tag.map(moved);
+            let tag = window.tag.map(moved);
+            if tag != window.tag {
+                window.tag = tag;
+                let act = DisplayAction::SetWindowTag(window.handle, tag);
+                self.actions.push_back(act);
+            }
+        }
+        for workspace in &mut self.workspaces {
+            workspace.tag = workspace.tag.map(moved);
+        }
+        for tag in self.modal_dialog_tags.values_mut() {
+            *tag = tag.map(moved);
+        }
+        for (_, tag) in &mut self.app_tags {
+            *tag = moved(*tag);
+        }
+        for launch in &mut self.launches {
+            launch.tag = moved(launch.tag);
+        }
+        if let Some(overview) = &mut self.overview {
+            overview.tag = moved(overview.tag);
+            for tag in overview.window_tags.values_mut() {
+                *tag = tag.map(moved);
+            }
+        }
+        let focus_manager = &mut self.focus_manager;
+        for tag in &mut focus_manager.tag_history {
+            *tag = moved(*tag);
+        }
+        focus_manager.tags_last_window = focus_manager
+            .tags_last_window
+            .drain()
+            .map(|(tag, handle)| (moved(tag), handle))
+            .collect();
+
+        let act = DisplayAction::SetCurrentTags(focus_manager.tag(0));
+        self.actions.push_back(act);
+    }
+
+    /// Counts the managed windows on each normal tag, leaving out scratchpads.
+    /// Sticky windows are shown on every tag, so they count on all of them.
+    pub fn window_count_by_tag(&self) -> HashMap<TagId, usize> {
+        let mut counts: HashMap<TagId, usize> =
+            self.tags.normal().iter().map(|tag| (tag.id, 0)).collect();
+        for window in &self.windows {
+            if !window.is_managed() || is_scratchpad(self, window) {
+                continue;
+            }
+            for (tag, count) in &mut counts {
+                if window.is_sticky() || window.has_tag(tag) {
+                    *count += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Remembers the tag the last window of `class` was on, forgetting the least recent class
+    /// once more than `remember_app_tags` are remembered.
+    pub(crate) fn remember_app_tag(&mut self, class: &str, tag: TagId) {
+        let max = match self.remember_app_tags {
+            Some(max) if max > 0 => max,
+            _ => return,
+        };
+        self.app_tags.retain(|(c, _)| c != class);
+        self.app_tags.push_front((class.to_owned(), tag));
+        self.app_tags.truncate(max);
+    }
+
+    /// Returns the tag the last window of `class` was on, if it is remembered.
+    #[must_use]
+    pub fn app_tag(&self, class: &str) -> Option<TagId> {
+        self.remember_app_tags?;
+        self.app_tags
+            .iter()
+            .find(|(c, _)| c == class)
+            .map(|(_, tag)| *tag)
+    }
+
+    /// Returns the ID of the hidden tag scratchpads are kept on while hidden.
+    #[must_use]
+    pub fn scratchpad_tag(&self) -> Option<TagId> {
+        self.tags
+            .get_hidden_by_label(&self.scratchpad_tag_label)
+            .map(|tag| tag.id)
+    }
+
     pub fn update_static(&mut self) {
         let workspaces = self.workspaces.clone();
         self.windows
@@ -173,6 +443,7 @@ impl State {
 
     pub(crate) fn load_config(&mut self, config: &impl Config) {
         self.mousekey = config.mousekey();
+        self.mousebinds = config.mousebinds();
         self.max_window_width = config.max_window_width();
         for win in &mut self.windows {
             config.load_window(win);
@@ -182,8 +453,21 @@ impl State {
         }
     }
 
+    /// Forgets the launched apps which did not open their window in time, so their ids can't
+    /// place unrelated windows later on.
+    pub(crate) fn expire_launches(&mut self) {
+        self.launches.retain(|launch| !launch.is_expired());
+    }
+
     /// Apply saved state to a running manager.
+    #[allow(clippy::too_many_lines)]
     pub fn restore_state(&mut self, old_state: &Self) {
+        // Tags which were already shown keep their restored layout.
+        let pending = &old_state.layout_manager.default_layouts;
+        self.layout_manager
+            .default_layouts
+            .retain(|label, _| pending.contains_key(label));
+
         // Restore tags.
         for old_tag in old_state.tags.all() {
             if let Some(tag) = self.tags.get_mut(old_tag.id) {
@@ -193,6 +477,9 @@ impl State {
                 tag.flipped_vertical = old_tag.flipped_vertical;
                 tag.flipped_horizontal = old_tag.flipped_horizontal;
                 tag.main_width_percentage = old_tag.main_width_percentage;
+                tag.stack_hidden = old_tag.stack_hidden;
+                tag.main_position.clone_from(&old_tag.main_position);
+                tag.grid_columns = old_tag.grid_columns;
             }
         }
 
@@ -213,6 +500,7 @@ impl State {
 
                 new_window.set_floating(old_window.floating());
                 new_window.set_floating_offsets(old_window.get_floating_offsets());
+                new_window.ignore_in_layout = old_window.ignore_in_layout;
                 new_window.apply_margin_multiplier(old_window.margin_multiplier);
                 new_window.pid = old_window.pid;
                 new_window.normal = old_window.normal;
@@ -228,6 +516,13 @@ impl State {
                     new_window.untag();
                     new_tag.iter().for_each(|&tag_id| new_window.tag(&tag_id));
                 }
+                // Keep the other tags the window was shown on, as long as they still exist.
+                new_window.extra_tags = old_window
+                    .extra_tags
+                    .iter()
+                    .copied()
+                    .filter(|&tag| self.tags.get(tag).is_some() && Some(tag) != new_window.tag)
+                    .collect();
                 new_window.strut = old_window.strut;
                 new_window.set_states(old_window.states());
                 ordered.push(new_window.clone());
@@ -242,6 +537,10 @@ impl State {
             self.update_static();
         }
         self.windows.append(&mut ordered);
+        // The display server doesn't keep the stacking order across a reload, so it is sent again.
+        self.sort_windows();
+        self.restore_scratchpads(old_state);
+        self.restore_app_tags(old_state);
 
         // This is needed due to mutable/immutable borrows.
         let all_tags = &self.tags;
@@ -253,6 +552,11 @@ impl State {
                 workspace.layout = old_workspace.layout;
                 workspace.main_width_percentage = old_workspace.main_width_percentage;
                 workspace.margin_multiplier = old_workspace.margin_multiplier;
+                // Hide the gaps again, putting aside the ones of the current config.
+                if let Some(gaps) = &old_workspace.hidden_gaps {
+                    workspace.margin_multiplier = gaps.margin_multiplier;
+                    workspace.toggle_gaps();
+                }
                 if are_tags_equal {
                     workspace.tag = old_workspace.tag;
                 } else {
@@ -269,12 +573,24 @@ impl State {
             }
         }
 
-        // Restore scratchpads.
-        for (scratchpad, id) in &old_state.active_scratchpads {
-            self.active_scratchpads
-                .insert(scratchpad.clone(), id.clone());
+        self.border_width_offset = old_state.border_width_offset;
+
+        // Restore marks of windows which are still around.
+        self.marks.clone_from(&old_state.marks);
+        let windows = &self.windows;
+        self.marks
+            .retain(|_, handle| windows.iter().any(|w| &w.handle == handle));
+
+        // Keep the overview, so the gathered windows can still be sent back.
+        self.overview.clone_from(&old_state.overview);
+        if let Some(overview) = &mut self.overview {
+            overview
+                .window_tags
+                .retain(|handle, _| windows.iter().any(|w| &w.handle == handle));
         }
 
+        self.overlay_shown = old_state.overlay_shown;
+
         // Restore focus.
         self.focus_manager.tags_last_window = old_state.focus_manager.tags_last_window.clone();
         self.focus_manager
@@ -294,6 +610,84 @@ impl State {
         };
         self.focus_tag(&tag_id);
     }
+
+    /// Restores the remembered tags of closed apps, as long as the tags still exist.
+    fn restore_app_tags(&mut self, old_state: &Self) {
+        self.app_tags.clone_from(&old_state.app_tags);
+        let len_normal = self.tags.len_normal();
+        self.app_tags.retain(|(_, tag)| *tag <= len_normal);
+        self.app_tags
+            .truncate(self.remember_app_tags.unwrap_or_default());
+    }
+
+    /// Reconnect the scratchpads to their still running windows,
+    /// dropping the ones whose windows did not survive.
+    fn restore_scratchpads(&mut self, old_state: &Self) {
+        for (scratchpad, pids) in &old_state.active_scratchpads {
+            let mut alive = VecDeque::new();
+            for &pid in pids {
+                if self.windows.iter().any(|w| w.pid == Some(pid)) {
+                    alive.push_back(pid);
+                } else {
+                    tracing::info!("Dropping dead scratchpad window: PID: {}", pid);
+                }
+            }
+            if !alive.is_empty() {
+                self.active_scratchpads.insert(scratchpad.clone(), alive);
+            }
+        }
+        self.scratchpad_geometries
+            .clone_from(&old_state.scratchpad_geometries);
+        self.apply_scratchpad_geometries();
+    }
+
+    /// Makes the scratchpads use the geometries saved for them at runtime.
+    pub(crate) fn apply_scratchpad_geometries(&mut self) {
+        for scratchpad in &mut self.scratchpads {
+            if let Some(xyhw) = self.scratchpad_geometries.get(&scratchpad.name) {
+                scratchpad.x = Some(Size::Pixel(xyhw.x()));
+                scratchpad.y = Some(Size::Pixel(xyhw.y()));
+                scratchpad.height = Some(Size::Pixel(xyhw.h()));
+                scratchpad.width = Some(Size::Pixel(xyhw.w()));
+            }
+        }
+    }
+}
+
+/// How long a launched app may take to open its window on the tag it was launched from.
+pub(crate) const LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An app launched by leftwm which has not opened its window yet.
+#[derive(Debug, Clone)]
+pub struct Launch {
+    /// The tag its window opens on.
+    pub tag: TagId,
+    /// Put on its windows by apps supporting startup notification.
+    pub startup_id: String,
+    /// The shell it was launched by.
+    pub pid: ChildID,
+    pub launched_at: Instant,
+}
+
+impl Launch {
+    #[must_use]
+    pub fn new(tag: TagId, startup_id: String, pid: ChildID) -> Self {
+        Self {
+            tag,
+            startup_id,
+            pid,
+            launched_at: Instant::now(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.launched_at.elapsed() >= LAUNCH_TIMEOUT
+    }
+}
+
+const fn default_respect_dock_struts() -> bool {
+    true
 }
 
 fn partition_windows<'a, I, F>(windows: I, f: F) -> (Vec<WindowHandle>, Vec<Window>, Vec<Window>)
@@ -324,3 +718,389 @@ where
     windows.fold((), extend(f, &mut handles, &mut left, &mut right));
     (handles, left, right)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{ScratchPadEdge, Side, WindowState};
+    use crate::Manager;
+
+    fn window(id: i32, r#type: WindowType, floating: bool) -> Window {
+        let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+        window.r#type = r#type;
+        window.set_floating(floating);
+        window
+    }
+
+    fn handles(windows: &[Window]) -> Vec<WindowHandle> {
+        windows.iter().map(|w| w.handle).collect()
+    }
+
+    #[test]
+    fn sort_windows_orders_windows_by_layer() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.docks_on_top = true;
+        let mut fullscreen = window(7, WindowType::Normal, false);
+        fullscreen.set_states(vec![WindowState::Fullscreen]);
+        manager.state.windows = vec![
+            window(1, WindowType::Normal, false),
+            window(2, WindowType::Desktop, false),
+            window(3, WindowType::Normal, true),
+            window(4, WindowType::Dock, false),
+            window(5, WindowType::Dialog, true),
+            window(6, WindowType::Normal, false),
+            fullscreen,
+            window(8, WindowType::Normal, true),
+            window(9, WindowType::Dock, false),
+        ];
+
+        manager.state.sort_windows();
+
+        let expected: Vec<WindowHandle> = [7, 4, 9, 5, 3, 8, 1, 6, 2]
+            .into_iter()
+            .map(WindowHandle::MockHandle)
+            .collect();
+        assert_eq!(handles(&manager.state.windows), expected);
+        assert!(matches!(
+            manager.state.actions.back(),
+            Some(DisplayAction::SetWindowOrder(fullscreen, windows))
+                if fullscreen == &expected[..1] && windows == &expected[1..]
+        ));
+    }
+
+    fn window_with_state(id: i32, floating: bool, state: WindowState) -> Window {
+        let mut window = window(id, WindowType::Normal, floating);
+        window.set_states(vec![state]);
+        window
+    }
+
+    #[test]
+    fn sort_windows_keeps_above_windows_over_floating_windows() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.windows = vec![
+            window(1, WindowType::Normal, true),
+            window(2, WindowType::Normal, false),
+            window_with_state(3, false, WindowState::Above),
+            window(4, WindowType::Dialog, true),
+            window_with_state(5, true, WindowState::Above),
+        ];
+
+        manager.state.sort_windows();
+
+        let expected: Vec<WindowHandle> = [4, 3, 5, 1, 2]
+            .into_iter()
+            .map(WindowHandle::MockHandle)
+            .collect();
+        assert_eq!(handles(&manager.state.windows), expected);
+    }
+
+    #[test]
+    fn sort_windows_keeps_below_windows_under_tiled_windows() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.windows = vec![
+            window(1, WindowType::Desktop, false),
+            window_with_state(2, true, WindowState::Below),
+            window(3, WindowType::Normal, false),
+            window(4, WindowType::Normal, true),
+        ];
+
+        manager.state.sort_windows();
+
+        let expected: Vec<WindowHandle> = [4, 3, 2, 1]
+            .into_iter()
+            .map(WindowHandle::MockHandle)
+            .collect();
+        assert_eq!(handles(&manager.state.windows), expected);
+    }
+
+    #[test]
+    fn sort_windows_keeps_docks_below_windows_by_default() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.windows = vec![
+            window(1, WindowType::Dock, false),
+            window(2, WindowType::Normal, false),
+            window(3, WindowType::Desktop, false),
+            window(4, WindowType::Dialog, true),
+            window(5, WindowType::Normal, true),
+        ];
+
+        manager.state.sort_windows();
+
+        let expected: Vec<WindowHandle> = [4, 5, 2, 1, 3]
+            .into_iter()
+            .map(WindowHandle::MockHandle)
+            .collect();
+        assert_eq!(handles(&manager.state.windows), expected);
+    }
+
+    #[test]
+    fn sort_windows_does_not_reorder_sorted_windows() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.windows = vec![
+            window(2, WindowType::Normal, true),
+            window(3, WindowType::Normal, true),
+            window(4, WindowType::Normal, false),
+            window(5, WindowType::Normal, false),
+            window(6, WindowType::Normal, false),
+            window(1, WindowType::Dock, false),
+        ];
+        let before = handles(&manager.state.windows);
+
+        manager.state.sort_windows();
+        manager.state.sort_windows();
+
+        assert_eq!(handles(&manager.state.windows), before);
+    }
+
+    #[test]
+    fn restore_state_keeps_saved_scratchpad_geometries() {
+        let scratchpad = ScratchPad {
+            name: "Alacritty".into(),
+            value: "scratchpad".to_string(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
+        };
+        let geometry: Xyhw = crate::models::XyhwBuilder {
+            x: 10,
+            y: 20,
+            h: 300,
+            w: 400,
+            ..Default::default()
+        }
+        .into();
+        let mut old_manager = Manager::new_test(vec!["1".to_string()]);
+        old_manager
+            .state
+            .scratchpad_geometries
+            .insert("Alacritty".into(), geometry);
+        let old_state: State =
+            serde_json::from_str(&serde_json::to_string(&old_manager.state).unwrap()).unwrap();
+
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.state.scratchpads.push(scratchpad);
+        manager.state.restore_state(&old_state);
+
+        let screen: Xyhw = crate::models::XyhwBuilder {
+            h: 600,
+            w: 800,
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(manager.state.scratchpads[0].xyhw(&screen), geometry);
+    }
+
+    #[test]
+    fn restore_state_keeps_the_order_and_tags_of_the_windows() {
+        let create_windows = |manager: &mut Manager<TestConfig, MockDisplayServer>| {
+            manager.screen_create_handler(Screen::default());
+            for id in 1..=3 {
+                manager.window_created_handler(
+                    Window::new(WindowHandle::MockHandle(id), None, None),
+                    -1,
+                    -1,
+                );
+            }
+        };
+        let tags = || (1..=3).map(|t| t.to_string()).collect();
+        let mut old_manager = Manager::new_test(tags());
+        create_windows(&mut old_manager);
+        old_manager.state.windows[0].extra_tags = vec![2, 3];
+        let order = vec![
+            WindowHandle::MockHandle(2),
+            WindowHandle::MockHandle(3),
+            WindowHandle::MockHandle(1),
+        ];
+        old_manager
+            .state
+            .windows
+            .sort_by_key(|w| order.iter().position(|h| h == &w.handle));
+        let old_state: State =
+            serde_json::from_str(&serde_json::to_string(&old_manager.state).unwrap()).unwrap();
+
+        let mut manager = Manager::new_test(tags());
+        create_windows(&mut manager);
+        manager.state.actions.clear();
+        manager.state.restore_state(&old_state);
+        assert_eq!(handles(&manager.state.windows), order);
+        assert_eq!(manager.state.windows[2].extra_tags, vec![2, 3]);
+        let sent_order = manager.state.actions.iter().find_map(|act| match act {
+            DisplayAction::SetWindowOrder(_, handles) => Some(handles.clone()),
+            _ => None,
+        });
+        assert_eq!(sent_order, Some(order));
+    }
+
+    #[test]
+    fn restore_state_keeps_the_main_position_of_the_tags() {
+        let mut old_manager = Manager::new_test(vec!["1".to_string()]);
+        old_manager.state.tags.get_mut(1).unwrap().main_position = Some(Side::Bottom);
+        let old_state: State =
+            serde_json::from_str(&serde_json::to_string(&old_manager.state).unwrap()).unwrap();
+
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.state.restore_state(&old_state);
+        let tag = manager.state.tags.get(1).unwrap();
+        assert_eq!(tag.main_position, Some(Side::Bottom));
+    }
+
+    #[test]
+    fn restore_state_reconnects_running_scratchpads() {
+        let mut old_manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        old_manager.screen_create_handler(Screen::default());
+        let nsp_tag = old_manager.state.scratchpad_tag().unwrap();
+        for pid in [1, 2] {
+            old_manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(pid as i32), None, Some(pid)),
+                -1,
+                -1,
+            );
+        }
+        for window in &mut old_manager.state.windows {
+            if window.pid == Some(1) {
+                window.tag(&nsp_tag);
+            }
+        }
+        old_manager
+            .state
+            .active_scratchpads
+            .insert(ScratchPadName::from("Hidden"), VecDeque::from([1, 3]));
+        old_manager
+            .state
+            .active_scratchpads
+            .insert(ScratchPadName::from("Visible"), VecDeque::from([2]));
+        old_manager
+            .state
+            .active_scratchpads
+            .insert(ScratchPadName::from("Dead"), VecDeque::from([4]));
+        let old_state: State =
+            serde_json::from_str(&serde_json::to_string(&old_manager.state).unwrap()).unwrap();
+
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for pid in [1, 2] {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(pid as i32), None, Some(pid)),
+                -1,
+                -1,
+            );
+        }
+        manager.state.restore_state(&old_state);
+
+        let scratchpads = &manager.state.active_scratchpads;
+        assert_eq!(scratchpads.len(), 2);
+        assert_eq!(scratchpads[&ScratchPadName::from("Hidden")], [1]);
+        assert_eq!(scratchpads[&ScratchPadName::from("Visible")], [2]);
+        let tag_of = |pid| {
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.pid == Some(pid))
+                .unwrap()
+                .tag
+        };
+        assert_eq!(tag_of(1), Some(nsp_tag));
+        assert_eq!(tag_of(2), Some(1));
+    }
+
+    #[test]
+    fn window_count_by_tag_counts_managed_windows() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let tags = [Some(1), Some(1), Some(2), Some(1), Some(2), None];
+        for (id, tag) in (1..).zip(tags) {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, Some(id as u32));
+            window.tag = tag;
+            manager.state.windows.push(window);
+        }
+        // A sticky window, a dock and a scratchpad.
+        manager.state.windows[3].set_states(vec![WindowState::Sticky]);
+        manager.state.windows[4].r#type = WindowType::Dock;
+        manager
+            .state
+            .active_scratchpads
+            .insert(ScratchPadName::from("Alacritty"), VecDeque::from([2]));
+        // Scratchpads are found by their class as well.
+        let mut term = Window::new(WindowHandle::MockHandle(7), None, None);
+        term.tag = Some(3);
+        term.res_class = Some("Term".to_string());
+        manager.state.windows.push(term);
+        manager.state.scratchpads.push(ScratchPad {
+            name: "Term".into(),
+            value: String::new(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+            hide_on_focus_loss: false,
+            class: Some("Term".to_string()),
+            position: ScratchPadEdge::Center,
+        });
+
+        let counts = manager.state.window_count_by_tag();
+        let expected: HashMap<TagId, usize> = [(1, 2), (2, 2), (3, 1)].into();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn windows_on_workspace_and_tag_leave_out_unmanaged_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        for (id, tag) in [(1, 1), (2, 2), (3, 1), (4, 2), (5, 1)] {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.tag = Some(tag);
+            manager.state.windows.push(window);
+        }
+        manager.state.windows[4].r#type = WindowType::Dock;
+        let state = &manager.state;
+
+        let on_tag = |tag| handles(&state.windows_on_tag(tag).cloned().collect::<Vec<_>>());
+        assert_eq!(
+            on_tag(1),
+            vec![WindowHandle::MockHandle(1), WindowHandle::MockHandle(3)]
+        );
+        assert_eq!(
+            on_tag(2),
+            vec![WindowHandle::MockHandle(2), WindowHandle::MockHandle(4)]
+        );
+
+        let second: Vec<Window> = state
+            .windows_on_workspace(&state.workspaces[1])
+            .cloned()
+            .collect();
+        assert_eq!(handles(&second), on_tag(2));
+    }
+
+    #[test]
+    fn windows_on_several_tags_count_on_each_of_them() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.tag = Some(1);
+        window.extra_tags = vec![3];
+        manager.state.windows.push(window);
+        let mut window = Window::new(WindowHandle::MockHandle(2), None, None);
+        window.tag = Some(3);
+        manager.state.windows.push(window);
+        let state = &manager.state;
+
+        let expected: HashMap<TagId, usize> = [(1, 1), (2, 0), (3, 2)].into();
+        assert_eq!(state.window_count_by_tag(), expected);
+        let on_tag = |tag| handles(&state.windows_on_tag(tag).cloned().collect::<Vec<_>>());
+        assert_eq!(on_tag(1), vec![WindowHandle::MockHandle(1)]);
+        assert_eq!(
+            on_tag(3),
+            vec![WindowHandle::MockHandle(1), WindowHandle::MockHandle(2)]
+        );
+    }
+}
@@ -10,7 +10,8 @@ use std::fs;
 use std::iter::{Extend, FromIterator};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 use tracing::error;
 use xdg::BaseDirectories;
 
@@ -332,9 +333,38 @@ pub fn register_child_hook(flag: Arc<AtomicBool>) {
 /// Sends command to shell for execution
 /// Assumes STDIN/STDOUT unwanted.
 pub fn exec_shell(command: &str, children: &mut Children) -> Option<ChildID> {
-    let child = Command::new("sh")
-        .arg("-c")
-        .arg(command)
+    spawn_shell(Command::new("sh").arg("-c").arg(command), children)
+}
+
+/// Sends command to shell for execution, with a startup id in `DESKTOP_STARTUP_ID`.
+/// Apps supporting startup notification put it on their windows as `_NET_STARTUP_ID`.
+pub fn exec_shell_with_startup_id(
+    command: &str,
+    startup_id: &str,
+    children: &mut Children,
+) -> Option<ChildID> {
+    spawn_shell(
+        Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("DESKTOP_STARTUP_ID", startup_id),
+        children,
+    )
+}
+
+/// Sends command to shell for execution, with the label of a tag in `LEFTWM_TAG`.
+pub fn exec_shell_for_tag(command: &str, tag: &str, children: &mut Children) -> Option<ChildID> {
+    spawn_shell(
+        Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("LEFTWM_TAG", tag),
+        children,
+    )
+}
+
+fn spawn_shell(command: &mut Command, children: &mut Children) -> Option<ChildID> {
+    let child = command
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .spawn()
@@ -344,6 +374,13 @@ pub fn exec_shell(command: &str, children: &mut Children) -> Option<ChildID> {
     Some(pid)
 }
 
+/// Returns a startup id which is unique for this leftwm process.
+pub fn new_startup_id() -> String {
+    static LAUNCHES: AtomicU32 = AtomicU32::new(0);
+    let launch = LAUNCHES.fetch_add(1, Ordering::Relaxed);
+    format!("leftwm-{}-{}_TIME0", std::process::id(), launch)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1,6 +1,7 @@
 //! Creates a pipe to listen for external commands.
+use crate::errors::LeftError;
 use crate::layouts::Layout;
-use crate::models::TagId;
+use crate::models::{FocusDirection, Side, TagId};
 use crate::{Command, ReleaseScratchPadOption};
 use std::env;
 use std::path::{Path, PathBuf};
@@ -74,7 +75,7 @@ async fn read_from_pipe(pipe_file: &Path, tx: &mpsc::UnboundedSender<Command>) -
     let mut lines = BufReader::new(file).lines();
 
     while let Some(line) = lines.next_line().await.ok()? {
-        let cmd = match parse_command(&line) {
+        let cmd = match parse_external_command(&line) {
             Ok(cmd) => cmd,
             Err(err) => {
                 tracing::error!("An error occurred while parsing the command: {}", err);
@@ -87,23 +88,51 @@ async fn read_from_pipe(pipe_file: &Path, tx: &mpsc::UnboundedSender<Command>) -
     Some(())
 }
 
+/// Parses a command in the format used by the command pipe, e.g. `GoToTag 2`.
+///
+/// # Errors
+///
+/// Will error if the command is unknown or its parameter is missing or invalid.
+pub fn parse_external_command(s: &str) -> crate::errors::Result<Command> {
+    parse_command(s).map_err(|err| LeftError::CommandParse(err.to_string()))
+}
+
 fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let (head, rest) = s.split_once(' ').unwrap_or((s, ""));
     match head {
         // Move Window
         "MoveWindowDown" => Ok(Command::MoveWindowDown),
         "MoveWindowTop" => build_move_window_top(rest),
+        "PromoteToMaster" => Ok(Command::PromoteToMaster),
         "MoveWindowUp" => Ok(Command::MoveWindowUp),
+        "SwapWindowDirection" => build_swap_window_direction(rest),
+        "SplitFocusedWith" => build_split_focused_with(rest),
         "MoveWindowToNextTag" => build_move_window_to_next_tag(rest),
         "MoveWindowToPreviousTag" => build_move_window_to_previous_tag(rest),
         "MoveWindowToLastWorkspace" => Ok(Command::MoveWindowToLastWorkspace),
         "MoveWindowToNextWorkspace" => Ok(Command::MoveWindowToNextWorkspace),
         "MoveWindowToPreviousWorkspace" => Ok(Command::MoveWindowToPreviousWorkspace),
+        "MoveWindowToMonitor" => build_move_window_to_monitor(rest),
         "SendWindowToTag" => build_send_window_to_tag(rest),
+        "MoveWindowToTagAndFollow" => build_move_window_to_tag_and_follow(rest),
+        "ToggleWindowTag" => build_toggle_window_tag(rest),
+        "SetWindowTags" => build_set_window_tags(rest),
+        "MarkWindow" => Ok(Command::MarkWindow {
+            key: build_mark_key(rest)?,
+        }),
+        "GotoMark" => Ok(Command::GotoMark {
+            key: build_mark_key(rest)?,
+        }),
         // Focus Navigation
         "FocusWindowDown" => Ok(Command::FocusWindowDown),
         "FocusWindowTop" => build_focus_window_top(rest),
+        "FocusBySize" => build_focus_by_size(rest),
         "FocusWindowUp" => Ok(Command::FocusWindowUp),
+        "FocusWindowDirection" => build_focus_window_direction(rest),
+        "FocusUrgent" => Ok(Command::FocusUrgent),
+        "FocusNextOfClass" => Ok(Command::FocusNextOfClass),
+        "FocusNextTiled" => Ok(Command::FocusNextTiled),
+        "FocusPrevTiled" => Ok(Command::FocusPrevTiled),
         "FocusNextTag" => Ok(Command::FocusNextTag),
         "FocusPreviousTag" => Ok(Command::FocusPreviousTag),
         "FocusWorkspaceNext" => Ok(Command::FocusWorkspaceNext),
@@ -111,14 +140,29 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         // Layout
         "DecreaseMainWidth" => build_decrease_main_width(rest),
         "IncreaseMainWidth" => build_increase_main_width(rest),
+        "ChangeMainWidth" => build_change_main_width(rest),
+        "ResetMainWidth" => Ok(Command::ResetMainWidth),
+        "IncreaseBorder" => build_increase_border(rest),
+        "DecreaseBorder" => build_decrease_border(rest),
+        "IncreaseGridColumns" => Ok(Command::IncreaseGridColumns),
+        "DecreaseGridColumns" => Ok(Command::DecreaseGridColumns),
+        "SetGridColumns" => build_set_grid_columns(rest),
+        "ToggleOverview" => Ok(Command::ToggleOverview),
+        "CycleInsertBehavior" => Ok(Command::CycleInsertBehavior),
+        "ToggleOverlay" => Ok(Command::ToggleOverlay),
+        "ToggleWindowOverlay" => Ok(Command::ToggleWindowOverlay),
         "NextLayout" => Ok(Command::NextLayout),
         "PreviousLayout" => Ok(Command::PreviousLayout),
         "RotateTag" => Ok(Command::RotateTag),
+        "ToggleStackVisibility" => Ok(Command::ToggleStackVisibility),
+        "SetMainPosition" => build_set_main_position(rest),
         "SetLayout" => build_set_layout(rest),
         "SetMarginMultiplier" => build_set_margin_multiplier(rest),
+        "ToggleGaps" => Ok(Command::ToggleGaps),
         // Scratchpad
         "ToggleScratchPad" => build_toggle_scratchpad(rest),
         "AttachScratchPad" => build_attach_scratchpad(rest),
+        "SaveScratchPadGeometry" => build_save_scratchpad_geometry(rest),
         "ReleaseScratchPad" => Ok(build_release_scratchpad(rest)),
         "NextScratchPadWindow" => Ok(Command::NextScratchPadWindow {
             scratchpad: rest.to_owned().into(),
@@ -130,17 +174,29 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         "FloatingToTile" => Ok(Command::FloatingToTile),
         "TileToFloating" => Ok(Command::TileToFloating),
         "ToggleFloating" => Ok(Command::ToggleFloating),
+        "TileAllFloating" => Ok(Command::TileAllFloating),
+        "ToggleIgnoreInLayout" => Ok(Command::ToggleIgnoreInLayout),
         // Workspace/Tag
         "GoToTag" => build_go_to_tag(rest),
-        "ReturnToLastTag" => Ok(Command::ReturnToLastTag),
+        "ReturnToLastTag" => build_return_to_last_tag(rest),
         "SendWorkspaceToTag" => build_send_workspace_to_tag(rest),
         "SwapScreens" => Ok(Command::SwapScreens),
+        "SwapMonitorTags" => build_swap_monitor_tags(rest),
+        "MoveTag" => build_move_tag(rest),
+        "SwapTagContents" => build_swap_tag_contents(rest),
         "ToggleFullScreen" => Ok(Command::ToggleFullScreen),
         "ToggleSticky" => Ok(Command::ToggleSticky),
+        "ToggleMaximize" => Ok(Command::ToggleMaximize),
         // General
         "CloseWindow" => Ok(Command::CloseWindow),
+        "CloseAllOnTag" => Ok(Command::CloseAllOnTag),
         "CloseAllOtherWindows" => Ok(Command::CloseAllOtherWindows),
+        "QueryFocusedWindow" => Ok(Command::QueryFocusedWindow),
+        "DumpLayout" => Ok(Command::DumpLayout),
         "SoftReload" => Ok(Command::SoftReload),
+        "Execute" => build_execute(rest),
+        "Spawn" => build_spawn(rest),
+        "Chain" => build_chain(rest),
         _ => Ok(Command::Other(s.into())),
     }
 }
@@ -185,19 +241,71 @@ fn build_toggle_scratchpad(raw: &str) -> Result<Command, Box<dyn std::error::Err
     Ok(Command::ToggleScratchPad(name.into()))
 }
 
+fn build_save_scratchpad_geometry(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument scratchpad's name".into());
+    }
+    Ok(Command::SaveScratchPadGeometry { name: raw.into() })
+}
+
+fn build_execute(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.trim().is_empty() {
+        return Err("missing command".into());
+    }
+    Ok(Command::Execute(raw.to_owned()))
+}
+
+fn build_spawn(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let raw = raw.trim();
+    // An optional tag comes before the command.
+    let (tag, command) = match raw.split_once(' ') {
+        Some((tag, command)) if tag.parse::<TagId>().is_ok() => (Some(tag.parse()?), command),
+        _ => (None, raw),
+    };
+    if command.trim().is_empty() {
+        return Err("missing command".into());
+    }
+    Ok(Command::Spawn {
+        command: command.to_owned(),
+        tag,
+    })
+}
+
+// The chained commands are a JSON list of commands, e.g. `["GoToTag 2 false", "Execute st"]`.
+fn build_chain(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let lines: Vec<String> = serde_json::from_str(raw)?;
+    if lines.is_empty() {
+        return Err("missing commands".into());
+    }
+    let commands = lines
+        .iter()
+        .map(|line| parse_command(line))
+        .collect::<Result<_, _>>()?;
+    Ok(Command::Chain(commands))
+}
+
 fn build_go_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let headless = without_head(raw, "GoToTag ");
-    let parts: Vec<&str> = headless.split(' ').collect();
+    let parts: Vec<&str> = headless.split_whitespace().collect();
     let tag: TagId = parts.first().ok_or("missing argument tag_id")?.parse()?;
     let swap: bool = parts.get(1).ok_or("missing argument swap")?.parse()?;
     Ok(Command::GoToTag { tag, swap })
 }
 
+fn build_return_to_last_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let swap = if raw.is_empty() {
+        true
+    } else {
+        bool::from_str(raw)?
+    };
+    Ok(Command::ReturnToLastTag { swap })
+}
+
 fn build_send_window_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let tag_id = if raw.is_empty() {
         return Err("missing argument tag_id".into());
     } else {
-        TagId::from_str(raw)?
+        TagId::from_str(raw.trim())?
     };
     Ok(Command::SendWindowToTag {
         window: None,
@@ -205,6 +313,33 @@ fn build_send_window_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Er
     })
 }
 
+fn build_toggle_window_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let tag = if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    } else {
+        TagId::from_str(raw.trim())?
+    };
+    Ok(Command::ToggleWindowTag { tag })
+}
+
+fn build_set_window_tags(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let tags = raw
+        .split_whitespace()
+        .map(TagId::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+    if tags.is_empty() {
+        return Err("missing argument tag_id".into());
+    }
+    Ok(Command::SetWindowTags { tags })
+}
+
+fn build_mark_key(raw: &str) -> Result<char, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument mark key".into());
+    }
+    Ok(char::from_str(raw.trim())?)
+}
+
 fn build_send_workspace_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     if raw.is_empty() {
         return Err("missing argument workspace index".into());
@@ -218,13 +353,61 @@ fn build_send_workspace_to_tag(raw: &str) -> Result<Command, Box<dyn std::error:
     Ok(Command::SendWorkspaceToTag(ws_index, tag_index))
 }
 
+fn build_swap_monitor_tags(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut parts = raw.split_whitespace();
+    let a: usize = parts
+        .next()
+        .ok_or("missing argument workspace index")?
+        .parse()?;
+    let b: usize = parts
+        .next()
+        .ok_or("missing argument workspace index")?
+        .parse()?;
+    Ok(Command::SwapMonitorTags { a, b })
+}
+
+fn build_move_window_to_tag_and_follow(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut parts = raw.split_whitespace();
+    let tag: TagId = parts.next().ok_or("missing argument tag_id")?.parse()?;
+    let swap: bool = parts.next().map_or(Ok(false), str::parse)?;
+    Ok(Command::MoveWindowToTagAndFollow { tag, swap })
+}
+
+fn build_move_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut parts = raw.split_whitespace();
+    let from: TagId = parts.next().ok_or("missing argument tag_id")?.parse()?;
+    let to: TagId = parts.next().ok_or("missing argument tag_id")?.parse()?;
+    Ok(Command::MoveTag { from, to })
+}
+
+fn build_swap_tag_contents(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut parts = raw.split_whitespace();
+    let a: TagId = parts.next().ok_or("missing argument tag_id")?.parse()?;
+    let b: TagId = parts.next().ok_or("missing argument tag_id")?.parse()?;
+    Ok(Command::SwapTagContents { a, b })
+}
+
+// A `Grid` may be followed by its number of columns, e.g. `SetLayout Grid 3`.
 fn build_set_layout(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
-    let layout_name = if raw.is_empty() {
-        return Err("missing layout name".into());
+    let mut parts = raw.split_whitespace();
+    let layout = Layout::from_str(parts.next().ok_or("missing layout name")?)?;
+    match parts.next() {
+        Some(columns) if layout == Layout::Grid => Ok(Command::Chain(vec![
+            Command::SetLayout(layout),
+            build_set_grid_columns(columns)?,
+        ])),
+        Some(_) => Err("only the Grid layout takes a number of columns".into()),
+        None => Ok(Command::SetLayout(layout)),
+    }
+}
+
+fn build_set_grid_columns(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let columns = if raw.is_empty() {
+        return Err("missing argument columns".into());
     } else {
-        raw
+        usize::from_str(raw.trim())?
     };
-    Ok(Command::SetLayout(Layout::from_str(layout_name)?))
+    Ok(Command::SetGridColumns(columns))
 }
 
 fn build_set_margin_multiplier(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
@@ -245,6 +428,50 @@ fn build_focus_window_top(raw: &str) -> Result<Command, Box<dyn std::error::Erro
     Ok(Command::FocusWindowTop { swap })
 }
 
+fn build_focus_by_size(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument largest".into());
+    }
+    let largest = bool::from_str(raw)?;
+    Ok(Command::FocusBySize { largest })
+}
+
+fn build_focus_window_direction(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let dir = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        FocusDirection::from_str(raw)?
+    };
+    Ok(Command::FocusWindowDirection { dir })
+}
+
+fn build_swap_window_direction(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let dir = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        FocusDirection::from_str(raw)?
+    };
+    Ok(Command::SwapWindowDirection { dir })
+}
+
+fn build_split_focused_with(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let dir = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        FocusDirection::from_str(raw)?
+    };
+    Ok(Command::SplitFocusedWith { dir })
+}
+
+fn build_move_window_to_monitor(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let dir = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        FocusDirection::from_str(raw)?
+    };
+    Ok(Command::MoveWindowToMonitor { dir })
+}
+
 fn build_move_window_top(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let swap = if raw.is_empty() {
         true
@@ -286,6 +513,36 @@ fn build_decrease_main_width(raw: &str) -> Result<Command, Box<dyn std::error::E
     Ok(Command::DecreaseMainWidth(change))
 }
 
+fn build_set_main_position(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let side = match raw {
+        "" => return Err("missing argument side".into()),
+        "Reset" => None,
+        _ => Some(Side::from_str(raw)?),
+    };
+    Ok(Command::SetMainPosition { side })
+}
+
+fn build_change_main_width(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let headless = without_head(raw, "ChangeMainWidth ");
+    let parts: Vec<&str> = headless.split(' ').collect();
+    let delta: i32 = parts.first().ok_or("missing argument delta")?.parse()?;
+    Ok(Command::ChangeMainWidth { delta })
+}
+
+fn build_increase_border(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let headless = without_head(raw, "IncreaseBorder ");
+    let parts: Vec<&str> = headless.split(' ').collect();
+    let change: i32 = parts.first().ok_or("missing argument change")?.parse()?;
+    Ok(Command::IncreaseBorder(change))
+}
+
+fn build_decrease_border(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let headless = without_head(raw, "DecreaseBorder ");
+    let parts: Vec<&str> = headless.split(' ').collect();
+    let change: i32 = parts.first().ok_or("missing argument change")?.parse()?;
+    Ok(Command::DecreaseBorder(change))
+}
+
 fn without_head<'a, 'b>(s: &'a str, head: &'b str) -> &'a str {
     if !s.starts_with(head) {
         return s;
@@ -300,6 +557,51 @@ mod test {
     use tokio::io::AsyncWriteExt;
     use tokio::time;
 
+    #[test]
+    fn parse_external_command_builds_typed_commands() {
+        let cases = [
+            ("SoftReload", Command::SoftReload),
+            ("NextLayout", Command::NextLayout),
+            (
+                "GoToTag 2 false",
+                Command::GoToTag {
+                    tag: 2,
+                    swap: false,
+                },
+            ),
+            ("SetLayout Monocle", Command::SetLayout(Layout::Monocle)),
+            (
+                "SetLayout Grid 3",
+                Command::Chain(vec![
+                    Command::SetLayout(Layout::Grid),
+                    Command::SetGridColumns(3),
+                ]),
+            ),
+            (
+                "ReturnToLastTag false",
+                Command::ReturnToLastTag { swap: false },
+            ),
+            ("MarkWindow a", Command::MarkWindow { key: 'a' }),
+            (
+                "ToggleScratchPad term",
+                Command::ToggleScratchPad("term".into()),
+            ),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(parse_external_command(raw).unwrap(), expected, "{raw}");
+        }
+    }
+
+    #[test]
+    fn parse_external_command_rejects_bad_input() {
+        for raw in ["GoToTag", "GoToTag x false", "SetLayout Nope", "MarkWindow"] {
+            assert!(
+                matches!(parse_external_command(raw), Err(LeftError::CommandParse(_))),
+                "{raw}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn read_good_command() {
         let pipe_file = temp_path().await.unwrap();
@@ -375,26 +677,200 @@ mod test {
         assert!(build_toggle_scratchpad("").is_err());
     }
 
+    #[test]
+    fn build_save_scratchpad_geometry_without_parameter() {
+        assert!(build_save_scratchpad_geometry("").is_err());
+    }
+
     #[test]
     fn build_send_window_to_tag_without_parameter() {
         assert!(build_send_window_to_tag("").is_err());
     }
 
+    #[test]
+    fn build_toggle_window_tag_without_parameter() {
+        assert!(build_toggle_window_tag("").is_err());
+        assert_eq!(
+            build_toggle_window_tag("2").unwrap(),
+            Command::ToggleWindowTag { tag: 2 }
+        );
+    }
+
+    #[test]
+    fn build_set_window_tags_without_parameter() {
+        assert!(build_set_window_tags("").is_err());
+        assert!(build_set_window_tags("1 x").is_err());
+        assert_eq!(
+            build_set_window_tags("1 2 4").unwrap(),
+            Command::SetWindowTags {
+                tags: vec![1, 2, 4]
+            }
+        );
+    }
+
+    #[test]
+    fn build_mark_key_without_parameter() {
+        assert!(build_mark_key("").is_err());
+        assert!(build_mark_key("ab").is_err());
+        assert_eq!(build_mark_key("a").unwrap(), 'a');
+    }
+
     #[test]
     fn build_send_workspace_to_tag_without_parameter() {
         assert!(build_send_workspace_to_tag("").is_err());
     }
 
+    #[test]
+    fn build_execute_without_parameter() {
+        assert!(build_execute("").is_err());
+        assert_eq!(
+            build_execute("firefox --new-window").unwrap(),
+            Command::Execute("firefox --new-window".to_string())
+        );
+    }
+
+    #[test]
+    fn build_spawn_with_and_without_tag() {
+        assert!(build_spawn("").is_err());
+        assert_eq!(
+            build_spawn("3 firefox --new-window").unwrap(),
+            Command::Spawn {
+                command: "firefox --new-window".to_string(),
+                tag: Some(3),
+            }
+        );
+        assert_eq!(
+            build_spawn("firefox").unwrap(),
+            Command::Spawn {
+                command: "firefox".to_string(),
+                tag: None,
+            }
+        );
+    }
+
+    #[test]
+    fn build_chain_parses_each_command() {
+        assert!(build_chain("").is_err());
+        assert!(build_chain("[]").is_err());
+        assert!(build_chain(r#"["GoToTag x false"]"#).is_err());
+        assert_eq!(
+            build_chain(r#"["GoToTag 2 false", "Execute st -e htop"]"#).unwrap(),
+            Command::Chain(vec![
+                Command::GoToTag {
+                    tag: 2,
+                    swap: false
+                },
+                Command::Execute("st -e htop".to_string()),
+            ])
+        );
+    }
+
     #[test]
     fn build_set_layout_without_parameter() {
         assert!(build_set_layout("").is_err());
     }
 
+    #[test]
+    fn build_change_border_without_parameter() {
+        assert!(build_increase_border("").is_err());
+        assert!(build_decrease_border("").is_err());
+    }
+
     #[test]
     fn build_set_margin_multiplier_without_parameter() {
         assert!(build_set_margin_multiplier("").is_err());
     }
 
+    #[test]
+    fn build_focus_window_direction_without_parameter() {
+        assert!(build_focus_window_direction("").is_err());
+    }
+
+    #[test]
+    fn build_swap_window_direction_without_parameter() {
+        assert!(build_swap_window_direction("").is_err());
+    }
+
+    #[test]
+    fn build_split_focused_with_without_parameter() {
+        assert!(build_split_focused_with("").is_err());
+    }
+
+    #[test]
+    fn build_set_main_position_without_parameter() {
+        assert!(build_set_main_position("").is_err());
+        assert_eq!(
+            build_set_main_position("Bottom").unwrap(),
+            Command::SetMainPosition {
+                side: Some(Side::Bottom)
+            }
+        );
+        assert_eq!(
+            build_set_main_position("Reset").unwrap(),
+            Command::SetMainPosition { side: None }
+        );
+    }
+
+    #[test]
+    fn build_change_main_width_without_parameter() {
+        assert!(build_change_main_width("").is_err());
+        assert_eq!(
+            build_change_main_width("-5").unwrap(),
+            Command::ChangeMainWidth { delta: -5 }
+        );
+    }
+
+    #[test]
+    fn build_move_window_to_monitor_without_parameter() {
+        assert!(build_move_window_to_monitor("").is_err());
+    }
+
+    #[test]
+    fn build_swap_monitor_tags_without_parameter() {
+        assert!(build_swap_monitor_tags("").is_err());
+        assert!(build_swap_monitor_tags("0").is_err());
+        assert_eq!(
+            build_swap_monitor_tags("0 1").unwrap(),
+            Command::SwapMonitorTags { a: 0, b: 1 }
+        );
+    }
+
+    #[test]
+    fn build_move_tag_without_parameter() {
+        assert!(build_move_tag("").is_err());
+        assert!(build_move_tag("1").is_err());
+        assert_eq!(
+            build_move_tag("1 3").unwrap(),
+            Command::MoveTag { from: 1, to: 3 }
+        );
+    }
+
+    #[test]
+    fn build_move_window_to_tag_and_follow_without_parameter() {
+        assert!(build_move_window_to_tag_and_follow("").is_err());
+        assert_eq!(
+            build_move_window_to_tag_and_follow("3").unwrap(),
+            Command::MoveWindowToTagAndFollow {
+                tag: 3,
+                swap: false
+            }
+        );
+        assert_eq!(
+            build_move_window_to_tag_and_follow("3 true").unwrap(),
+            Command::MoveWindowToTagAndFollow { tag: 3, swap: true }
+        );
+    }
+
+    #[test]
+    fn build_swap_tag_contents_without_parameter() {
+        assert!(build_swap_tag_contents("").is_err());
+        assert!(build_swap_tag_contents("1").is_err());
+        assert_eq!(
+            build_swap_tag_contents("2 4").unwrap(),
+            Command::SwapTagContents { a: 2, b: 4 }
+        );
+    }
+
     #[test]
     fn build_move_window_top_without_parameter() {
         assert_eq!(
@@ -403,6 +879,14 @@ mod test {
         );
     }
 
+    #[test]
+    fn build_return_to_last_tag_without_parameter() {
+        assert_eq!(
+            build_return_to_last_tag("").unwrap(),
+            Command::ReturnToLastTag { swap: true }
+        );
+    }
+
     #[test]
     fn build_focus_window_top_without_parameter() {
         assert_eq!(
@@ -411,6 +895,15 @@ mod test {
         );
     }
 
+    #[test]
+    fn build_focus_by_size_without_parameter() {
+        assert!(build_focus_by_size("").is_err());
+        assert_eq!(
+            build_focus_by_size("false").unwrap(),
+            Command::FocusBySize { largest: false }
+        );
+    }
+
     #[test]
     fn build_move_window_to_next_tag_without_parameter() {
         assert_eq!(
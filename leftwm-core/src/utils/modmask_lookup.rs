@@ -35,3 +35,16 @@ pub fn into_mod(key: &str) -> ModMask {
         _ => 0,
     }
 }
+
+/// Returns the mouse button with the name, e.g. `Button1` or `ScrollUp`.
+#[must_use]
+pub fn into_button(name: &str) -> Option<Button> {
+    match name {
+        "Button1" | "Left" => Some(xlib::Button1),
+        "Button2" | "Middle" => Some(xlib::Button2),
+        "Button3" | "Right" => Some(xlib::Button3),
+        "Button4" | "ScrollUp" => Some(xlib::Button4),
+        "Button5" | "ScrollDown" => Some(xlib::Button5),
+        _ => None,
+    }
+}
@@ -8,6 +8,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
      * based on the new state of the WM
      */
     pub fn update_windows(&mut self) {
+        // Windows may have started or stopped floating.
+        self.state.update_borders(&self.config);
+
         // set all tagged windows as visible
         self.state
             .windows
@@ -21,5 +24,15 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 tag.update_windows(windows, ws);
             }
         }
+
+        // The overlay is placed on the focused workspace, above the tag it shows.
+        if self.state.overlay_shown {
+            let overlay = self.state.overlay_tag();
+            let tag = overlay.and_then(|id| self.state.tags.get(id));
+            let workspace = self.state.focus_manager.workspace(&self.state.workspaces);
+            if let (Some(tag), Some(workspace)) = (tag, workspace) {
+                tag.update_windows(&mut self.state.windows, workspace);
+            }
+        }
     }
 }
@@ -17,11 +17,15 @@ fn main() {
             let path = BaseDirectories::with_prefix("leftwm-lefthk")
                 .expect("ERROR: could not find base directory");
 
-            rt.block_on(Worker::new(config.mapped_bindings(), path).event_loop());
+            rt.block_on(Worker::new(config.mapped_bindings(), path).event_loop())
         });
 
         match exit_status {
-            Ok(_) => tracing::info!("Completed"),
+            Ok(false) => {
+                tracing::info!("Restarting to reload the keybinds");
+                std::process::exit(leftwm::utils::LEFTHK_RELOAD_EXIT_CODE);
+            }
+            Ok(true) => tracing::info!("Completed"),
             Err(err) => tracing::error!("Completed with error: {:?}", err),
         }
     }
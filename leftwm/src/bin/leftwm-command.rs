@@ -49,9 +49,12 @@ fn print_commandlist() {
         Commands without arguments:
 
         UnloadTheme
+        DumpWindowRuleMatches
+        ReloadKeybinds
         SoftReload
         ToggleFullScreen
         ToggleSticky
+        ToggleMaximize
         SwapScreens
         MoveWindowToNextTag
         MoveWindowToPreviousTag
@@ -61,21 +64,39 @@ fn print_commandlist() {
         FloatingToTile
         TileToFloating
         ToggleFloating
+        TileAllFloating
+        ToggleIgnoreInLayout
         MoveWindowUp
         MoveWindowDown
         MoveWindowTop
+        PromoteToMaster
         FocusWindowUp
         FocusWindowDown
         FocusWindowTop
         FocusNextTag
+        FocusUrgent
+        FocusNextOfClass
+        FocusNextTiled
+        FocusPrevTiled
         FocusPreviousTag
         FocusWorkspaceNext
         FocusWorkspacePrevious
         NextLayout
         PreviousLayout
+        ToggleStackVisibility
+        ToggleGaps
+        ResetMainWidth
+        IncreaseGridColumns
+        DecreaseGridColumns
+        ToggleOverview
+        CycleInsertBehavior
+        ToggleOverlay
+        ToggleWindowOverlay
+        QueryFocusedWindow
+        DumpLayout
         RotateTag
-        ReturnToLastTag
         CloseWindow
+        CloseAllOnTag
 
         Commands with arguments:
             Use quotations for the command and arguments, like this:
@@ -87,12 +108,35 @@ fn print_commandlist() {
         ReleaseScratchPad      Args: <tag_index> or <ScratchpadName>
         NextScratchPadWindow   Args: <ScratchpadName>
         PrevScratchPadWindow   Args: <ScratchpadName>
+        SaveScratchPadGeometry Args: <ScratchpadName>
         ToggleScratchPad       Args: <ScratchpadName>
         SendWorkspaceToTag     Args: <workspaxe_index> <tag_index> (int)
+        SwapMonitorTags        Args: <workspace_index> <workspace_index> (int)
+        MoveTag                Args: <tag_index> <tag_index> (int)
+        SwapTagContents        Args: <tag_index> <tag_index> (int)
         SendWindowToTag        Args: <tag_index> (int)
-        SetLayout              Args: <LayoutName>
+        MoveWindowToTagAndFollow Args: <tag_index> (int) [<swap> (bool)]
+        ToggleWindowTag        Args: <tag_index> (int)
+        SetWindowTags          Args: <tag_index> ... (int)
+        FocusBySize            Args: <largest> (bool)
+        ReturnToLastTag        Args: [swap] (bool)
+        MarkWindow             Args: <key> (char)
+        GotoMark               Args: <key> (char)
+        SetLayout              Args: <LayoutName> [columns of a Grid] (int)
+        SetGridColumns         Args: <columns> (int)
         SetMarginMultiplier    Args: <multiplier-value> (float)
+        ChangeMainWidth        Args: <percent> (int)
+        IncreaseBorder         Args: <pixels> (int)
+        DecreaseBorder         Args: <pixels> (int)
         FocusWindow            Args: <WindowClass> or <visible-window-index> (int)
+        FocusWindowDirection   Args: <Up|Down|Left|Right>
+        SwapWindowDirection    Args: <Up|Down|Left|Right>
+        SplitFocusedWith       Args: <Up|Down|Left|Right>
+        SetMainPosition        Args: <Top|Bottom|Left|Right|Reset>
+        MoveWindowToMonitor    Args: <Up|Down|Left|Right>
+        Execute                Args: <command>
+        Spawn                  Args: [tag_index] (int) <command>
+        Chain                  Args: <commands> (JSON list of commands)
 
         For more information please visit:
         https://github.com/leftwm/leftwm/wiki/External-Commands
@@ -1,6 +1,6 @@
 use leftwm_core::Manager;
 use std::panic;
-use xlib_display_server::XlibDisplayServer;
+use xlib_display_server::XlibDisplayServer as Server;
 
 fn main() {
     leftwm::utils::log::setup_logging();
@@ -21,7 +21,12 @@ fn main() {
         #[cfg(not(feature = "lefthk"))]
         let config = leftwm::load();
 
-        let manager = Manager::<leftwm::Config, XlibDisplayServer>::new(config);
+        #[cfg(feature = "config-watch")]
+        if let Ok(Some(path)) = leftwm::config_file_path() {
+            leftwm::watch_config(path);
+        }
+
+        let manager = Manager::<leftwm::Config, Server>::new(config);
         manager.register_child_hook();
         rt.block_on(manager.start_event_loop())
     });
@@ -154,6 +154,11 @@ fn start_leftwm() {
             // remove all child processes which finished
             children.remove_finished_children();
 
+            #[cfg(feature = "lefthk")]
+            if lefthk_reload_requested(&mut lefthk_session) {
+                lefthk_session = start_lefthk_session(&current_exe);
+            }
+
             while is_suspending(&flag) {
                 nix::unistd::pause();
             }
@@ -214,6 +219,15 @@ fn start_lefthk_session(current_exe: &Path) -> Child {
         .expect("failed to start lefthk")
 }
 
+/// Returns true if the lefthk session quit to reload the keybinds.
+#[cfg(feature = "lefthk")]
+fn lefthk_reload_requested(lefthk_session: &mut Child) -> bool {
+    matches!(
+        lefthk_session.try_wait(),
+        Ok(Some(status)) if status.code() == Some(leftwm::utils::LEFTHK_RELOAD_EXIT_CODE)
+    )
+}
+
 /// Kills the lefthk session
 #[cfg(feature = "lefthk")]
 fn kill_lefthk_session(lefthk_session: &mut Child) {
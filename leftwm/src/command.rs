@@ -13,9 +13,15 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum BaseCommand {
     Execute,
+    Spawn,
+    Chain,
     CloseWindow,
     CloseAllOtherWindows,
+    QueryFocusedWindow,
+    DumpLayout,
+    CloseAllOnTag,
     SwapTags,
+    SwapMonitorTags,
     SoftReload,
     HardReload,
     AttachScratchPad,
@@ -23,40 +29,76 @@ pub enum BaseCommand {
     NextScratchPadWindow,
     PrevScratchPadWindow,
     ToggleScratchPad,
+    SaveScratchPadGeometry,
     ToggleFullScreen,
     ToggleSticky,
+    ToggleMaximize,
     GotoTag,
     ReturnToLastTag,
+    MoveTag,
+    SwapTagContents,
     FloatingToTile,
     TileToFloating,
     ToggleFloating,
+    TileAllFloating,
+    ToggleIgnoreInLayout,
     MoveWindowUp,
     MoveWindowDown,
     MoveWindowTop,
+    PromoteToMaster,
+    SwapWindowDirection,
+    SplitFocusedWith,
     FocusNextTag,
     FocusPreviousTag,
     FocusWindow,
     FocusWindowUp,
     FocusWindowDown,
     FocusWindowTop,
+    FocusBySize,
+    FocusWindowDirection,
+    FocusUrgent,
+    FocusNextOfClass,
+    FocusNextTiled,
+    FocusPrevTiled,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
     MoveToTag,
+    MoveWindowToTagAndFollow,
+    ToggleWindowTag,
+    SetWindowTags,
+    MarkWindow,
+    GotoMark,
     MoveWindowToNextTag,
     MoveWindowToPreviousTag,
     MoveToLastWorkspace,
     MoveWindowToNextWorkspace,
     MoveWindowToPreviousWorkspace,
+    MoveWindowToMonitor,
     NextLayout,
     PreviousLayout,
     SetLayout,
     RotateTag,
+    ToggleStackVisibility,
+    SetMainPosition,
     IncreaseMainWidth,
     DecreaseMainWidth,
+    ChangeMainWidth,
+    ResetMainWidth,
+    IncreaseBorder,
+    DecreaseBorder,
+    IncreaseGridColumns,
+    DecreaseGridColumns,
+    ToggleOverview,
+    CycleInsertBehavior,
+    ToggleOverlay,
+    ToggleWindowOverlay,
     SetMarginMultiplier,
+    ToggleGaps,
     // Custom commands
     UnloadTheme,
     LoadTheme,
+    DumpWindowRuleMatches,
+    ReloadKeybinds,
 }
 
 impl std::convert::From<BaseCommand> for String {
@@ -1,4 +1,4 @@
-use super::Config;
+use super::{validation, Config, Warning};
 #[cfg(feature = "lefthk")]
 use lefthk_core::xkeysym_lookup;
 #[cfg(feature = "lefthk")]
@@ -23,21 +23,20 @@ impl Config {
         }
     }
 
-    /// Checks defined workspaces to ensure no ID collisions occur.
-    pub fn check_workspace_ids(&self, verbose: bool) {
-        if let Some(wss) = self.workspaces.as_ref() {
-            if verbose {
-                println!("Checking config for valid workspace definitions.");
-            }
-            let ids = crate::get_workspace_ids(wss);
-            if ids.iter().any(std::option::Option::is_some) {
-                if crate::all_ids_some(&ids) {
-                    if !crate::all_ids_unique(&ids) {
-                        println!("Your config file contains duplicate workspace IDs. Please assign unique IDs to workspaces. The default config will be used instead.");
-                    }
-                } else {
-                    println!("Your config file specifies an ID for some but not all workspaces. This can lead to ID collisions and is not allowed. The default config will be used instead.");
-                }
+    /// Reports the problems leftwm warns about when loading the config.
+    /// Keybinds are left to [`Config::check_keybinds`].
+    pub fn check_config(&self, verbose: bool) {
+        if verbose {
+            println!("Checking config for invalid options.");
+        }
+        for warning in validation::validate_config(self) {
+            match warning {
+                Warning::InvalidKeybind { .. } | Warning::ConflictingKeybinds { .. } => {}
+                _ if warning.is_error() => println!(
+                    "\x1b[1;91mERROR: {} The default config will be used instead.\x1b[0m",
+                    warning
+                ),
+                _ => println!("\x1b[1;93mWARN: {}\x1b[0m", warning),
             }
         }
     }
@@ -1,8 +1,11 @@
-use leftwm_core::models::{ScratchPad, Size};
+use leftwm_core::models::{ScratchPad, ScratchPadEdge, Size};
 
 #[cfg(feature = "lefthk")]
 use super::{default_terminal, exit_strategy, BaseCommand, Keybind};
-use super::{Config, Default, FocusBehaviour, LayoutMode, ThemeSetting, LAYOUTS};
+use super::{
+    Config, Default, EmptyTagFocus, FocusAfterLastClose, FocusBehaviour, LayoutMode, ThemeSetting,
+    LAYOUTS,
+};
 
 impl Default for Config {
     // We allow this because this function would be difficult to reduce. If someone would like to
@@ -18,6 +21,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::Execute,
                 value: "dmenu_run".to_owned(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "p".to_owned(),
             },
@@ -25,6 +29,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::Execute,
                 value: default_terminal().to_owned(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "Return".to_owned(),
             },
@@ -32,6 +37,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::CloseWindow,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "q".to_owned(),
             },
@@ -39,6 +45,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::SoftReload,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "r".to_owned(),
             },
@@ -46,6 +53,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::Execute,
                 value: exit_strategy().to_owned(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "x".to_owned(),
             },
@@ -53,6 +61,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::Execute,
                 value: "slock".to_owned(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Control".to_owned()].into()),
                 key: "l".to_owned(),
             },
@@ -60,6 +69,7 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::MoveToLastWorkspace,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "w".to_owned(),
             },
@@ -67,108 +77,126 @@ impl Default for Config {
             Keybind {
                 command: BaseCommand::SwapTags,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "w".to_owned(),
             },
             Keybind {
                 command: BaseCommand::MoveWindowUp,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "k".to_owned(),
             },
             Keybind {
                 command: BaseCommand::MoveWindowDown,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "j".to_owned(),
             },
             Keybind {
                 command: BaseCommand::MoveWindowTop,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "Return".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWindowUp,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "k".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWindowDown,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "j".to_owned(),
             },
             Keybind {
                 command: BaseCommand::NextLayout,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Control".to_owned()].into()),
                 key: "k".to_owned(),
             },
             Keybind {
                 command: BaseCommand::PreviousLayout,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Control".to_owned()].into()),
                 key: "j".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWorkspaceNext,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "l".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWorkspacePrevious,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "h".to_owned(),
             },
             Keybind {
                 command: BaseCommand::MoveWindowUp,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "Up".to_owned(),
             },
             Keybind {
                 command: BaseCommand::MoveWindowDown,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: "Down".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWindowUp,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "Up".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWindowDown,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "Down".to_owned(),
             },
             Keybind {
                 command: BaseCommand::NextLayout,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Control".to_owned()].into()),
                 key: "Up".to_owned(),
             },
             Keybind {
                 command: BaseCommand::PreviousLayout,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Control".to_owned()].into()),
                 key: "Down".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWorkspaceNext,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "Right".to_owned(),
             },
             Keybind {
                 command: BaseCommand::FocusWorkspacePrevious,
                 value: String::default(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: "Left".to_owned(),
             },
@@ -180,6 +208,7 @@ impl Default for Config {
             commands.push(Keybind {
                 command: BaseCommand::GotoTag,
                 value: i.to_string(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned()].into()),
                 key: i.to_string(),
             });
@@ -191,6 +220,7 @@ impl Default for Config {
             commands.push(Keybind {
                 command: BaseCommand::MoveToTag,
                 value: i.to_string(),
+                commands: vec![],
                 modifier: Some(vec!["modkey".to_owned(), "Shift".to_owned()].into()),
                 key: i.to_string(),
             });
@@ -208,6 +238,9 @@ impl Default for Config {
             y: Some(Size::Pixel(390)),
             height: Some(Size::Pixel(300)),
             width: Some(Size::Pixel(200)),
+            hide_on_focus_loss: false,
+            class: None,
+            position: ScratchPadEdge::Center,
         };
 
         Self {
@@ -215,17 +248,31 @@ impl Default for Config {
             tags: Some(tags),
             layouts: LAYOUTS.to_vec(),
             layout_mode: LayoutMode::Tag,
+            default_layouts: None,
+            max_tiled_windows: None,
+            on_tag_focus_cmd: None,
+            on_tag_unfocus_cmd: None,
+            remember_app_tags: None,
+            transient_float: None,
+            reset_layout_on_empty: false,
             // TODO: add sane default for scratchpad config.
             // Currently default values are set in sane_dimension fn.
             scratchpad: Some(vec![scratchpad]),
+            scratchpad_tag_label: "NSP".to_string(),
+            overlay_tag_label: "OVERLAY".to_string(),
             window_rules: Some(vec![]),
             disable_current_tag_swap: false,
-            disable_tile_drag: false,
+            disable_tile_drag: leftwm_core::config::DisableTileDrag::All(false),
             disable_window_snap: true,
+            constrain_floating_to_workspace: false,
             focus_behaviour: FocusBehaviour::Sloppy, // default behaviour: mouse move auto-focuses window
             focus_new_windows: true, // default behaviour: focuses windows on creation
+            prevent_focus_stealing: false,
+            focus_on_empty_tag: EmptyTagFocus::Clear,
+            focus_after_last_close: FocusAfterLastClose::FollowFocusBehaviour,
             single_window_border: true,
             insert_behavior: leftwm_core::config::InsertBehavior::Bottom,
+            floating_placement: leftwm_core::config::FloatingPlacement::Center,
             modkey: "Mod4".to_owned(),     //win key
             mousekey: Some("Mod4".into()), //win key
             #[cfg(feature = "lefthk")]
@@ -234,6 +281,13 @@ impl Default for Config {
             max_window_width: None,
             state_path: None,
             sloppy_mouse_follows_focus: true,
+            follow_modal_dialogs: false,
+            float_on_cursor_monitor: false,
+            inherit_floating_from_focused: false,
+            keep_fullscreen_on_new_window: false,
+            respect_dock_struts: true,
+            docks_on_top: false,
+            mousebind: vec![],
         }
     }
 }
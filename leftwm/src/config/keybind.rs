@@ -1,15 +1,10 @@
-#[cfg(feature = "lefthk")]
-use super::BaseCommand;
-#[cfg(feature = "lefthk")]
+use crate::BaseCommand;
 use crate::Config;
-#[cfg(feature = "lefthk")]
-use anyhow::{ensure, Context, Result};
-#[cfg(feature = "lefthk")]
+use anyhow::{bail, ensure, Context, Result};
 use leftwm_core::layouts::Layout;
+use leftwm_core::models::{FocusDirection, Side};
 use serde::{Deserialize, Serialize};
-#[cfg(feature = "lefthk")]
 use std::fmt::Write;
-#[cfg(feature = "lefthk")]
 use std::str::FromStr;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -18,79 +13,55 @@ pub struct Keybind {
     pub command: BaseCommand,
     #[serde(default)]
     pub value: String,
+    // The commands run in order by a `Chain` keybind.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<ChainedCommand>,
     pub modifier: Option<Modifier>,
     pub key: String,
 }
 
+/// One of the commands of a `Chain` keybind.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg(feature = "lefthk")]
+pub struct ChainedCommand {
+    pub command: BaseCommand,
+    #[serde(default)]
+    pub value: String,
+}
+
 #[cfg(feature = "lefthk")]
 impl Keybind {
     pub fn try_convert_to_lefthk_keybind(
         &self,
         config: &Config,
     ) -> Result<lefthk_core::config::Keybind> {
-        let value_is_some = !self.value.is_empty();
-        match &self.command {
-            BaseCommand::Execute | BaseCommand::LoadTheme => {
-                ensure!(value_is_some, "value must not be empty");
-            }
-            BaseCommand::ToggleScratchPad
-            | BaseCommand::AttachScratchPad
-            | BaseCommand::NextScratchPadWindow
-            | BaseCommand::PrevScratchPadWindow => {
+        if self.command == BaseCommand::Chain {
+            ensure!(!self.commands.is_empty(), "commands must not be empty");
+            for chained in &self.commands {
                 ensure!(
-                    is_valid_scratchpad_name(config, self.value.as_str()),
-                    "Value should be a correct scratchpad name"
+                    chained.command != BaseCommand::Chain,
+                    "a Chain can not contain another Chain"
                 );
+                validate_command(config, chained.command, &chained.value)
+                    .with_context(|| format!("invalid {:?} in Chain", chained.command))?;
             }
-            BaseCommand::ReleaseScratchPad => {
-                ensure!(
-                    self.value.is_empty()
-                        || usize::from_str(&self.value).is_ok()
-                        || is_valid_scratchpad_name(config, self.value.as_str()),
-                    "Value should be empty, a window number or a valid scratchpad name"
-                );
-            }
-            BaseCommand::GotoTag => {
-                usize::from_str(&self.value).context("invalid index value for GotoTag")?;
-            }
-            BaseCommand::FocusWindowTop if value_is_some => {
-                bool::from_str(&self.value).context("invalid boolean value for FocusWindowTop")?;
-            }
-            BaseCommand::MoveToTag => {
-                usize::from_str(&self.value).context("invalid index value for SendWindowToTag")?;
-            }
-            BaseCommand::SetLayout => {
-                Layout::from_str(&self.value)
-                    .context("could not parse layout for command SetLayout")?;
-            }
-            BaseCommand::IncreaseMainWidth => {
-                i8::from_str(&self.value).context("invalid width value for IncreaseMainWidth")?;
-            }
-            BaseCommand::DecreaseMainWidth => {
-                i8::from_str(&self.value).context("invalid width value for DecreaseMainWidth")?;
-            }
-            BaseCommand::SetMarginMultiplier => {
-                f32::from_str(&self.value)
-                    .context("invalid margin multiplier for SetMarginMultiplier")?;
-            }
-            _ => {}
+        } else {
+            validate_command(config, self.command, &self.value)?;
         }
 
-        let command: String = if self.command == BaseCommand::Execute {
-            self.value.clone()
-        } else {
-            let mut head = "leftwm-command ".to_owned();
-            let mut command_parts: String = self.command.into();
-            if !self.value.is_empty() {
-                let args = if self.command == BaseCommand::GotoTag {
-                    format!(" {} {}", self.value, !config.disable_current_tag_swap)
-                } else {
-                    format!(" {}", self.value)
-                };
-                command_parts.push_str(&args);
+        // Apps are run directly, `Spawn` launches them through leftwm instead so their windows
+        // open on the tag they were launched from.
+        let command = match self.command {
+            BaseCommand::Execute => self.value.clone(),
+            BaseCommand::Chain => {
+                let lines: Vec<String> = self
+                    .commands
+                    .iter()
+                    .map(|chained| pipe_command(config, chained.command, &chained.value))
+                    .collect();
+                leftwm_command(&format!("Chain {}", serde_json::to_string(&lines)?))
             }
-            let _ = writeln!(head, "'{}'", command_parts);
-            head
+            _ => leftwm_command(&pipe_command(config, self.command, &self.value)),
         };
         Ok(lefthk_core::config::Keybind {
             command: lefthk_core::config::Command::Execute(command),
@@ -105,6 +76,156 @@ impl Keybind {
     }
 }
 
+/// Ensures the value is valid for the command.
+pub(super) fn validate_command(config: &Config, command: BaseCommand, value: &str) -> Result<()> {
+    match command {
+        BaseCommand::Execute | BaseCommand::Spawn | BaseCommand::LoadTheme => {
+            ensure!(!value.is_empty(), "value must not be empty");
+        }
+        BaseCommand::ToggleScratchPad
+        | BaseCommand::AttachScratchPad
+        | BaseCommand::NextScratchPadWindow
+        | BaseCommand::PrevScratchPadWindow
+        | BaseCommand::SaveScratchPadGeometry => {
+            ensure!(
+                is_valid_scratchpad_name(config, value),
+                "Value should be a correct scratchpad name"
+            );
+        }
+        BaseCommand::ReleaseScratchPad => {
+            ensure!(
+                value.is_empty()
+                    || usize::from_str(value).is_ok()
+                    || is_valid_scratchpad_name(config, value),
+                "Value should be empty, a window number or a valid scratchpad name"
+            );
+        }
+        BaseCommand::GotoTag => {
+            ensure_valid_tag_index(config, value, "GotoTag")?;
+        }
+        BaseCommand::SwapMonitorTags => {
+            let (a, b) = two_values(value, "SwapMonitorTags")?;
+            for index in [a, b] {
+                usize::from_str(index).context("invalid workspace index for SwapMonitorTags")?;
+            }
+        }
+        BaseCommand::MoveTag => {
+            let (from, to) = two_values(value, "MoveTag")?;
+            ensure_valid_tag_index(config, from, "MoveTag")?;
+            ensure_valid_tag_index(config, to, "MoveTag")?;
+        }
+        BaseCommand::SwapTagContents => {
+            let (a, b) = two_values(value, "SwapTagContents")?;
+            ensure_valid_tag_index(config, a, "SwapTagContents")?;
+            ensure_valid_tag_index(config, b, "SwapTagContents")?;
+        }
+        BaseCommand::ReturnToLastTag if !value.is_empty() => {
+            bool::from_str(value).context("invalid boolean value for ReturnToLastTag")?;
+        }
+        BaseCommand::FocusWindowTop if !value.is_empty() => {
+            bool::from_str(value).context("invalid boolean value for FocusWindowTop")?;
+        }
+        BaseCommand::FocusBySize => {
+            bool::from_str(value).context("invalid boolean value for FocusBySize")?;
+        }
+        BaseCommand::FocusWindowDirection => {
+            FocusDirection::from_str(value)
+                .context("invalid direction for FocusWindowDirection")?;
+        }
+        BaseCommand::SwapWindowDirection => {
+            FocusDirection::from_str(value).context("invalid direction for SwapWindowDirection")?;
+        }
+        BaseCommand::SplitFocusedWith => {
+            FocusDirection::from_str(value).context("invalid direction for SplitFocusedWith")?;
+        }
+        BaseCommand::MoveWindowToMonitor => {
+            FocusDirection::from_str(value).context("invalid direction for MoveWindowToMonitor")?;
+        }
+        BaseCommand::MoveToTag => {
+            ensure_valid_tag_index(config, value, "SendWindowToTag")?;
+        }
+        BaseCommand::MoveWindowToTagAndFollow => {
+            ensure_valid_tag_index(config, value, "MoveWindowToTagAndFollow")?;
+        }
+        BaseCommand::ToggleWindowTag => {
+            ensure_valid_tag_index(config, value, "ToggleWindowTag")?;
+        }
+        BaseCommand::SetWindowTags => {
+            ensure!(!value.trim().is_empty(), "value must not be empty");
+            for tag in value.split_whitespace() {
+                ensure_valid_tag_index(config, tag, "SetWindowTags")?;
+            }
+        }
+        BaseCommand::MarkWindow | BaseCommand::GotoMark => {
+            char::from_str(value).context("value should be a single character")?;
+        }
+        BaseCommand::SetLayout => {
+            let mut parts = value.split_whitespace();
+            let layout = Layout::from_str(parts.next().unwrap_or_default())
+                .context("could not parse layout for command SetLayout")?;
+            if let Some(columns) = parts.next() {
+                ensure!(
+                    layout == Layout::Grid,
+                    "only the Grid layout takes a number of columns"
+                );
+                usize::from_str(columns).context("invalid number of columns for SetLayout")?;
+            }
+        }
+        BaseCommand::IncreaseMainWidth => {
+            i8::from_str(value).context("invalid width value for IncreaseMainWidth")?;
+        }
+        BaseCommand::DecreaseMainWidth => {
+            i8::from_str(value).context("invalid width value for DecreaseMainWidth")?;
+        }
+        BaseCommand::SetMainPosition if value != "Reset" => {
+            Side::from_str(value).context("invalid side for SetMainPosition")?;
+        }
+        BaseCommand::ChangeMainWidth => {
+            i32::from_str(value).context("invalid width value for ChangeMainWidth")?;
+        }
+        BaseCommand::IncreaseBorder => {
+            i32::from_str(value).context("invalid width value for IncreaseBorder")?;
+        }
+        BaseCommand::DecreaseBorder => {
+            i32::from_str(value).context("invalid width value for DecreaseBorder")?;
+        }
+        BaseCommand::SetMarginMultiplier => {
+            f32::from_str(value).context("invalid margin multiplier for SetMarginMultiplier")?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Builds the line sent to the command pipe for the command, e.g. `GoToTag 2 true`.
+pub(super) fn pipe_command(config: &Config, command: BaseCommand, value: &str) -> String {
+    let mut line: String = if command == BaseCommand::Execute {
+        "Execute".to_owned()
+    } else {
+        command.into()
+    };
+    let value = value.trim();
+    if command == BaseCommand::ReturnToLastTag {
+        // `true` makes it stay on the focused tag when the current tag swap is disabled.
+        if value == "true" {
+            let _ = write!(line, " {}", !config.disable_current_tag_swap);
+        }
+    } else if !value.is_empty() {
+        if command == BaseCommand::GotoTag || command == BaseCommand::MoveWindowToTagAndFollow {
+            let _ = write!(line, " {} {}", value, !config.disable_current_tag_swap);
+        } else {
+            let _ = write!(line, " {}", value);
+        }
+    }
+    line
+}
+
+/// Builds the shell line handing the command to `leftwm-command`, quoting it as a single word.
+#[cfg(feature = "lefthk")]
+fn leftwm_command(line: &str) -> String {
+    format!("leftwm-command '{}'\n", line.replace('\'', r"'\''"))
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, Eq, PartialEq)]
 #[serde(untagged)]
 pub enum Modifier {
@@ -173,7 +294,30 @@ impl Modifier {
     }
 }
 
-#[cfg(feature = "lefthk")]
+/// Ensures the value is the index of a configured tag, starting at 1.
+/// Splits the value of a command which takes two arguments.
+fn two_values<'a>(value: &'a str, command: &str) -> Result<(&'a str, &'a str)> {
+    let mut parts = value.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(a), Some(b), None) => Ok((a, b)),
+        _ => bail!("{} takes two values", command),
+    }
+}
+
+fn ensure_valid_tag_index(config: &Config, value: &str, command: &str) -> Result<()> {
+    let index = usize::from_str(value.trim())
+        .with_context(|| format!("invalid index value for {}", command))?;
+    let tag_count = leftwm_core::Config::create_list_of_tag_labels(config).len();
+    ensure!(
+        (1..=tag_count).contains(&index),
+        "tag {} for {} is out of range, there are only {} tags",
+        index,
+        command,
+        tag_count
+    );
+    Ok(())
+}
+
 fn is_valid_scratchpad_name(config: &Config, scratchpad_name: &str) -> bool {
     config
         .scratchpad
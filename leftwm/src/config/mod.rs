@@ -3,8 +3,19 @@
 mod checks;
 mod default;
 mod keybind;
+mod mousebind;
+mod validation;
+#[cfg(feature = "config-watch")]
+mod watch;
 
 use self::keybind::Modifier;
+use self::mousebind::Mousebind;
+pub use self::validation::{
+    config_file_path, parse_config_file, validate_config, validate_config_file,
+    validate_config_keys, Warning,
+};
+#[cfg(feature = "config-watch")]
+pub use self::watch::{watch_config, ConfigWatcher};
 
 #[cfg(feature = "lefthk")]
 use super::BaseCommand;
@@ -13,18 +24,21 @@ use super::ThemeSetting;
 use crate::config::keybind::Keybind;
 use anyhow::Result;
 use leftwm_core::{
-    config::{InsertBehavior, ScratchPad, Workspace},
+    config::{DisableTileDrag, FloatingPlacement, InsertBehavior, ScratchPad, Workspace},
     layouts::{Layout, LAYOUTS},
-    models::{FocusBehaviour, Gutter, LayoutMode, Margins, Size, Window, WindowState, WindowType},
+    models::{
+        EmptyTagFocus, FocusAfterLastClose, FocusBehaviour, Gutter, LayoutMode, Margins, Size,
+        Window, WindowState, WindowType,
+    },
     state::State,
     DisplayAction, DisplayServer, Manager,
 };
 use ron::{
     extensions::Extensions,
     ser::{to_string_pretty, PrettyConfig},
-    Options,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::default::Default;
 use std::env;
@@ -74,6 +88,8 @@ pub struct WindowHook {
     pub spawn_fullscreen: Option<bool>,
     /// Handle the window as if it was of this `_NET_WM_WINDOW_TYPE`
     pub spawn_as_type: Option<WindowType>,
+    /// `_NET_WM_WINDOW_OPACITY` to be applied by a compositor, from 0.0 to 1.0
+    pub spawn_opacity: Option<f32>,
 }
 
 impl WindowHook {
@@ -145,6 +161,10 @@ impl WindowHook {
         if let Some(w_type) = self.spawn_as_type.clone() {
             window.r#type = w_type;
         }
+        if let Some(opacity) = self.spawn_opacity.filter(|o| (0.0..=1.0).contains(o)) {
+            let act = DisplayAction::SetOpacity(window.handle, opacity);
+            state.actions.push_back(act);
+        }
     }
 }
 
@@ -157,20 +177,63 @@ pub struct Config {
     pub mousekey: Option<Modifier>,
     pub workspaces: Option<Vec<Workspace>>,
     pub tags: Option<Vec<String>>,
+    // Label of the hidden tag scratchpads are kept on, must differ from the tags
+    pub scratchpad_tag_label: String,
+    // Label of the hidden tag of the overlay, must differ from the tags
+    pub overlay_tag_label: String,
     pub max_window_width: Option<Size>,
     pub layouts: Vec<Layout>,
     pub layout_mode: LayoutMode,
+    // Layouts tags start with the first time they are shown, keyed by tag label
+    pub default_layouts: Option<HashMap<String, Layout>>,
+    // Number of tiled windows on a tag before new windows float, keyed by tag label
+    pub max_tiled_windows: Option<HashMap<String, usize>>,
+    // Shell commands run when a tag gets the focus, keyed by tag label
+    pub on_tag_focus_cmd: Option<HashMap<String, String>>,
+    // Shell commands run when a tag loses the focus, keyed by tag label
+    pub on_tag_unfocus_cmd: Option<HashMap<String, String>>,
+    // New windows open on the tag the last window of their class was on, for this many classes
+    pub remember_app_tags: Option<usize>,
+    // Whether child windows float or tile regardless of their type, keyed by WM_CLASS
+    pub transient_float: Option<HashMap<String, bool>>,
+    // Tags go back to their configured layout once their last window is closed
+    pub reset_layout_on_empty: bool,
     pub insert_behavior: InsertBehavior,
+    // Where new floating windows are placed: Center, Cascade or Cursor
+    pub floating_placement: FloatingPlacement,
     pub scratchpad: Option<Vec<ScratchPad>>,
     pub window_rules: Option<Vec<WindowHook>>,
     // If you are on tag "1" and you goto tag "1" this takes you to the previous tag
     pub disable_current_tag_swap: bool,
-    pub disable_tile_drag: bool,
+    // Either true/false, or the layouts in which tiled windows can't be dragged
+    pub disable_tile_drag: DisableTileDrag,
     pub disable_window_snap: bool,
+    // Floating windows can't be moved or resized beyond the usable area of their workspace
+    pub constrain_floating_to_workspace: bool,
     pub focus_behaviour: FocusBehaviour,
     pub focus_new_windows: bool,
+    // New windows don't take the focus from another window, unless they demand attention
+    pub prevent_focus_stealing: bool,
+    // Whether switching to a tag without windows unfocuses the last focused window
+    pub focus_on_empty_tag: EmptyTagFocus,
+    // What gets focused after the last window of a workspace has been closed
+    pub focus_after_last_close: FocusAfterLastClose,
     pub single_window_border: bool,
     pub sloppy_mouse_follows_focus: bool,
+    // Modal dialogs follow the user to other tags until they are closed
+    pub follow_modal_dialogs: bool,
+    // New floating windows are centered on the monitor under the cursor
+    pub float_on_cursor_monitor: bool,
+    // New windows float if the focused window does
+    pub inherit_floating_from_focused: bool,
+    // New windows open behind a fullscreen window instead of ending its fullscreen
+    pub keep_fullscreen_on_new_window: bool,
+    // Docks reserve their strut space, turn off for auto-hiding bars
+    pub respect_dock_struts: bool,
+    // Docks are stacked above dialogs and floating windows instead of below all windows
+    pub docks_on_top: bool,
+    // Mouse buttons pressed with modifiers which run a command
+    pub mousebind: Vec<Mousebind>,
     #[cfg(feature = "lefthk")]
     pub keybind: Vec<Keybind>,
     pub state_path: Option<PathBuf>,
@@ -202,42 +265,30 @@ pub fn load() -> Config {
 fn load_from_file() -> Result<Config> {
     tracing::debug!("Loading config file");
 
-    let path = BaseDirectories::with_prefix("leftwm")?;
-
     // the checks and fallback for `toml` can be removed when toml gets eventually deprecated
-    let config_file_ron = path.place_config_file("config.ron")?;
-    let config_file_toml = path.place_config_file("config.toml")?;
-
-    if Path::new(&config_file_ron).exists() {
-        tracing::debug!("Config file '{}' found.", config_file_ron.to_string_lossy());
-        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
-        let contents = fs::read_to_string(config_file_ron)?;
-        let config: Config = ron.from_str(&contents)?;
-
-        if check_workspace_ids(&config) {
-            Ok(config)
-        } else {
-            tracing::warn!("Invalid workspace ID configuration in config file. Falling back to default config.");
-            Ok(Config::default())
+    if let Some(config_file) = config_file_path()? {
+        tracing::debug!("Config file '{}' found.", config_file.to_string_lossy());
+        let config = parse_config_file(&config_file)?;
+        if config_file.extension() != Some(std::ffi::OsStr::new("ron")) {
+            tracing::info!("You are using TOML as config language which will be deprecated in the future.\nPlease consider migrating you config to RON. For further info visit the leftwm wiki.");
         }
-    } else if Path::new(&config_file_toml).exists() {
-        tracing::debug!(
-            "Config file '{}' found.",
-            config_file_toml.to_string_lossy()
-        );
-        let contents = fs::read_to_string(config_file_toml)?;
-        let config = toml::from_str(&contents)?;
-        tracing::info!("You are using TOML as config language which will be deprecated in the future.\nPlease consider migrating you config to RON. For further info visit the leftwm wiki.");
 
-        if check_workspace_ids(&config) {
-            Ok(config)
-        } else {
-            tracing::warn!("Invalid workspace ID configuration in config.toml. Falling back to default config.");
+        let mut warnings = validate_config_keys(&config_file).unwrap_or_default();
+        warnings.append(&mut validate_config(&config));
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
+        if warnings.iter().any(Warning::is_error) {
+            tracing::warn!("Invalid config file. Falling back to default config.");
             Ok(Config::default())
+        } else {
+            Ok(config)
         }
     } else {
         tracing::debug!("Config file not found. Using default config file.");
 
+        let path = BaseDirectories::with_prefix("leftwm")?;
+        let config_file_ron = path.place_config_file("config.ron")?;
         let config = Config::default();
         let ron_pretty_conf = PrettyConfig::new()
             .depth_limit(2)
@@ -264,18 +315,6 @@ fn load_from_file() -> Result<Config> {
     }
 }
 
-#[must_use]
-pub fn check_workspace_ids(config: &Config) -> bool {
-    config.workspaces.clone().map_or(true, |wss| {
-        let ids = get_workspace_ids(&wss);
-        if ids.iter().any(Option::is_some) {
-            all_ids_some(&ids) && all_ids_unique(&ids)
-        } else {
-            true
-        }
-    })
-}
-
 #[must_use]
 pub fn get_workspace_ids(wss: &[Workspace]) -> Vec<Option<i32>> {
     wss.iter().map(|ws| ws.id).collect()
@@ -352,6 +391,33 @@ fn exit_strategy<'s>() -> &'s str {
     "pkill leftwm"
 }
 
+/// Asks lefthk to register the keybinds of the config file again.
+/// Nothing else is reloaded, so workspaces, the theme and the windows are left alone.
+#[cfg(feature = "lefthk")]
+fn reload_keybinds() {
+    let pipe = BaseDirectories::with_prefix("leftwm-lefthk")
+        .ok()
+        .and_then(|dirs| dirs.find_runtime_file(lefthk_core::ipc::Pipe::pipe_name()));
+    let pipe = if let Some(pipe) = pipe {
+        pipe
+    } else {
+        tracing::error!("Could not find the lefthk pipe to reload the keybinds");
+        return;
+    };
+    let result = fs::OpenOptions::new()
+        .append(true)
+        .open(pipe)
+        .and_then(|mut file| writeln!(file, "Reload"));
+    if let Err(err) = result {
+        tracing::error!("Could not request a reload of the keybinds: {}", err);
+    }
+}
+
+#[cfg(not(feature = "lefthk"))]
+fn reload_keybinds() {
+    tracing::warn!("Keybinds are only handled with the lefthk feature, nothing to reload.");
+}
+
 fn absolute_path(path: &str) -> Option<PathBuf> {
     let exp_path = shellexpand::full(path).ok()?;
     std::fs::canonicalize(exp_path.as_ref()).ok()
@@ -360,37 +426,34 @@ fn absolute_path(path: &str) -> Option<PathBuf> {
 #[cfg(feature = "lefthk")]
 impl lefthk_core::config::Config for Config {
     fn mapped_bindings(&self) -> Vec<lefthk_core::config::Keybind> {
-        // copy keybinds substituting "modkey" modifier with a new "modkey".
-        self.keybind
-            .clone()
+        let (commands, mut bindings): (Vec<BaseCommand>, Vec<lefthk_core::config::Keybind>) = self
+            .substituted_keybinds()
             .into_iter()
-            .map(|mut keybind| {
-                if let Some(ref mut modifier) = keybind.modifier {
-                    match modifier {
-                        Modifier::Single(m) if m == "modkey" => *m = self.modkey.clone(),
-                        Modifier::List(ms) => {
-                            for m in ms {
-                                if m == "modkey" {
-                                    *m = self.modkey.clone();
-                                }
-                            }
-                        }
-                        Modifier::Single(_) => {}
-                    }
-                }
-
-                keybind
-            })
             .filter_map(
                 |keybind| match keybind.try_convert_to_lefthk_keybind(self) {
-                    Ok(lefthk_keybind) => Some(lefthk_keybind),
+                    Ok(lefthk_keybind) => Some((keybind.command, lefthk_keybind)),
                     Err(err) => {
                         tracing::error!("Invalid key binding: {}\n{:?}", err, keybind);
                         None
                     }
                 },
             )
-            .collect()
+            .unzip();
+
+        // Only the first of several keybinds on the same keys is used.
+        let conflicts = validation::keybind_conflicts(&bindings);
+        for &(kept, shadowed) in conflicts.iter().rev() {
+            let binding = bindings.remove(shadowed);
+            tracing::warn!(
+                "Keybind {}+{} is bound to both {:?} and {:?}, ignoring {:?}",
+                binding.modifier.join("+"),
+                binding.key,
+                commands[kept],
+                commands[shadowed],
+                commands[shadowed]
+            );
+        }
+        bindings
     }
 }
 
@@ -420,6 +483,21 @@ impl leftwm_core::Config for Config {
             .into()
     }
 
+    fn mousebinds(&self) -> Vec<leftwm_core::config::Mousebind> {
+        self.mousebind
+            .iter()
+            .filter_map(
+                |mousebind| match mousebind.try_convert_to_core_mousebind(self) {
+                    Ok(bind) => Some(bind),
+                    Err(err) => {
+                        tracing::error!("Invalid mouse binding: {}\n{:?}", err, mousebind);
+                        None
+                    }
+                },
+            )
+            .collect()
+    }
+
     fn create_list_of_scratchpads(&self) -> Vec<ScratchPad> {
         if let Some(scratchpads) = &self.scratchpad {
             return scratchpads.clone();
@@ -427,6 +505,14 @@ impl leftwm_core::Config for Config {
         vec![]
     }
 
+    fn scratchpad_tag_label(&self) -> String {
+        self.scratchpad_tag_label.clone()
+    }
+
+    fn overlay_tag_label(&self) -> String {
+        self.overlay_tag_label.clone()
+    }
+
     fn layouts(&self) -> Vec<Layout> {
         self.layouts.clone()
     }
@@ -435,10 +521,42 @@ impl leftwm_core::Config for Config {
         self.layout_mode
     }
 
+    fn default_layouts(&self) -> Option<HashMap<String, Layout>> {
+        self.default_layouts.clone()
+    }
+
+    fn max_tiled_windows(&self) -> Option<HashMap<String, usize>> {
+        self.max_tiled_windows.clone()
+    }
+
+    fn on_tag_focus_cmd(&self) -> Option<HashMap<String, String>> {
+        self.on_tag_focus_cmd.clone()
+    }
+
+    fn on_tag_unfocus_cmd(&self) -> Option<HashMap<String, String>> {
+        self.on_tag_unfocus_cmd.clone()
+    }
+
+    fn remember_app_tags(&self) -> Option<usize> {
+        self.remember_app_tags
+    }
+
+    fn transient_float(&self) -> Option<HashMap<String, bool>> {
+        self.transient_float.clone()
+    }
+
+    fn reset_layout_on_empty(&self) -> bool {
+        self.reset_layout_on_empty
+    }
+
     fn insert_behavior(&self) -> InsertBehavior {
         self.insert_behavior
     }
 
+    fn floating_placement(&self) -> FloatingPlacement {
+        self.floating_placement
+    }
+
     fn single_window_border(&self) -> bool {
         self.single_window_border
     }
@@ -447,37 +565,69 @@ impl leftwm_core::Config for Config {
         self.focus_new_windows
     }
 
+    fn prevent_focus_stealing(&self) -> bool {
+        self.prevent_focus_stealing
+    }
+
+    fn focus_on_empty_tag(&self) -> EmptyTagFocus {
+        self.focus_on_empty_tag
+    }
+
+    fn focus_after_last_close(&self) -> FocusAfterLastClose {
+        self.focus_after_last_close
+    }
+
     fn command_handler<SERVER: DisplayServer>(
         command: &str,
         manager: &mut Manager<Self, SERVER>,
     ) -> bool {
-        if let Some((command, value)) = command.split_once(' ') {
-            match command {
-                "LoadTheme" => {
-                    if let Some(absolute) = absolute_path(value.trim()) {
-                        manager.config.theme_setting.load(absolute);
-                    } else {
-                        tracing::warn!("Path submitted does not exist.");
-                    }
-                    return manager.reload_config();
-                }
-                "UnloadTheme" => {
-                    manager.config.theme_setting = ThemeSetting::default();
-                    return manager.reload_config();
+        let (command, value) = command.split_once(' ').unwrap_or((command, ""));
+        match command {
+            "LoadTheme" => {
+                if let Some(absolute) = absolute_path(value.trim()) {
+                    manager.config.theme_setting.load(absolute);
+                } else {
+                    tracing::warn!("Path submitted does not exist.");
                 }
-                _ => {
-                    tracing::warn!("Command not recognized: {}", command);
-                    return false;
+                manager.reload_config()
+            }
+            "UnloadTheme" => {
+                manager.config.theme_setting = ThemeSetting::default();
+                manager.reload_config()
+            }
+            "ReloadKeybinds" => {
+                reload_keybinds();
+                false
+            }
+            "DumpWindowRuleMatches" => {
+                for line in manager.config.window_rule_matches(&manager.state.windows) {
+                    tracing::info!("{}", line);
                 }
+                false
+            }
+            _ => {
+                tracing::warn!("Command not recognized: {}", command);
+                false
             }
         }
-        false
     }
 
     fn border_width(&self) -> i32 {
         self.theme_setting.border_width.unwrap_or(1)
     }
 
+    fn floating_border_width(&self) -> i32 {
+        self.theme_setting
+            .floating_border_width
+            .unwrap_or_else(|| self.border_width())
+    }
+
+    fn tiled_border_width(&self) -> i32 {
+        self.theme_setting
+            .tiled_border_width
+            .unwrap_or_else(|| self.border_width())
+    }
+
     fn margin(&self) -> Margins {
         match self
             .theme_setting
@@ -536,6 +686,10 @@ impl leftwm_core::Config for Config {
         self.disable_window_snap
     }
 
+    fn constrain_floating_to_workspace(&self) -> bool {
+        self.constrain_floating_to_workspace
+    }
+
     fn always_float(&self) -> bool {
         self.theme_setting.always_float.unwrap_or(false)
     }
@@ -559,6 +713,10 @@ impl leftwm_core::Config for Config {
         self.theme_setting.on_new_window_cmd.clone()
     }
 
+    fn on_reload_cmd(&self) -> Option<String> {
+        self.theme_setting.on_reload_cmd.clone()
+    }
+
     fn get_list_of_gutters(&self) -> Vec<Gutter> {
         self.theme_setting.gutter.clone().unwrap_or_default()
     }
@@ -567,8 +725,8 @@ impl leftwm_core::Config for Config {
         self.max_window_width
     }
 
-    fn disable_tile_drag(&self) -> bool {
-        self.disable_tile_drag
+    fn disable_tile_drag(&self) -> DisableTileDrag {
+        self.disable_tile_drag.clone()
     }
 
     fn save_state(&self, state: &State) {
@@ -585,20 +743,30 @@ impl leftwm_core::Config for Config {
         }
     }
 
-    fn load_state(&self, state: &mut State) {
+    fn load_state(&self, state: &mut State) -> bool {
         let path = self.state_file().to_owned();
         match File::open(&path) {
             Ok(file) => {
-                match serde_json::from_reader(file) {
-                    Ok(old_state) => state.restore_state(&old_state),
-                    Err(err) => tracing::error!("Cannot load old state: {}", err),
-                }
+                let restored = match serde_json::from_reader(file) {
+                    Ok(old_state) => {
+                        state.restore_state(&old_state);
+                        true
+                    }
+                    Err(err) => {
+                        tracing::error!("Cannot load old state: {}", err);
+                        false
+                    }
+                };
                 // Clean old state.
                 if let Err(err) = std::fs::remove_file(&path) {
                     tracing::error!("Cannot remove old state file: {}", err);
                 }
+                restored
+            }
+            Err(err) => {
+                tracing::error!("Cannot open old state: {}", err);
+                false
             }
-            Err(err) => tracing::error!("Cannot open old state: {}", err),
         }
     }
 
@@ -609,10 +777,12 @@ impl leftwm_core::Config for Config {
                 .iter()
                 // map first instead of using max_by_key directly...
                 .map(|wh| (wh, wh.score_window(window)))
-                // ...since this filter is required (0 := non-match)
-                .filter(|(_wh, score)| score != &0)
+                // ...since this filter is required (0 := non-match),
+                // a rule applied before is only replaced by a better match
+                .filter(|(_wh, score)| score > &window.rule_score)
                 .max_by_key(|(_wh, score)| *score);
-            if let Some((hook, _)) = best_match {
+            if let Some((hook, score)) = best_match {
+                window.rule_score = score;
                 hook.apply(state, window);
                 tracing::debug!(
                     "Window [[ TITLE={:?}, {:?}; WM_CLASS={:?}, {:?} ]] spawned in tag={:?} on workspace={:?} as type={:?} with floating={:?}, sticky={:?} and fullscreen={:?}",
@@ -637,9 +807,86 @@ impl leftwm_core::Config for Config {
     fn sloppy_mouse_follows_focus(&self) -> bool {
         self.sloppy_mouse_follows_focus
     }
+
+    fn follow_modal_dialogs(&self) -> bool {
+        self.follow_modal_dialogs
+    }
+
+    fn float_on_cursor_monitor(&self) -> bool {
+        self.float_on_cursor_monitor
+    }
+
+    fn inherit_floating_from_focused(&self) -> bool {
+        self.inherit_floating_from_focused
+    }
+
+    fn keep_fullscreen_on_new_window(&self) -> bool {
+        self.keep_fullscreen_on_new_window
+    }
+
+    fn respect_dock_struts(&self) -> bool {
+        self.respect_dock_struts
+    }
+
+    fn docks_on_top(&self) -> bool {
+        self.docks_on_top
+    }
 }
 
 impl Config {
+    /// Describes which of the windows each window rule matches, with the score of the match.
+    fn window_rule_matches(&self, windows: &[Window]) -> Vec<String> {
+        let mut report = vec![];
+        for (index, rule) in self.window_rules.iter().flatten().enumerate() {
+            report.push(format!(
+                "Window rule {} (WM_CLASS={:?}, TITLE={:?}):",
+                index, rule.window_class, rule.window_title
+            ));
+            let matches: Vec<String> = windows
+                .iter()
+                .map(|window| (window, rule.score_window(window)))
+                .filter(|(_, score)| *score > 0)
+                .map(|(window, score)| {
+                    format!(
+                        "    {:?} [[ TITLE={:?}; WM_CLASS={:?}, {:?} ]] with score {}",
+                        window.handle, window.name, window.res_name, window.res_class, score
+                    )
+                })
+                .collect();
+            if matches.is_empty() {
+                report.push("    matches no windows".to_string());
+            }
+            report.extend(matches);
+        }
+        report
+    }
+
+    /// Returns the keybinds with the "modkey" modifier substituted by the configured modkey.
+    #[cfg(feature = "lefthk")]
+    fn substituted_keybinds(&self) -> Vec<Keybind> {
+        self.keybind
+            .clone()
+            .into_iter()
+            .map(|mut keybind| {
+                if let Some(ref mut modifier) = keybind.modifier {
+                    match modifier {
+                        Modifier::Single(m) if m == "modkey" => *m = self.modkey.clone(),
+                        Modifier::List(ms) => {
+                            for m in ms {
+                                if m == "modkey" {
+                                    *m = self.modkey.clone();
+                                }
+                            }
+                        }
+                        Modifier::Single(_) => {}
+                    }
+                }
+
+                keybind
+            })
+            .collect()
+    }
+
     #[cfg(feature = "lefthk")]
     pub fn clear_keybinds(&mut self) {
         self.keybind.clear();
@@ -655,6 +902,8 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use leftwm_core::display_servers::HeadlessDisplayServer;
+    use leftwm_core::models::WindowHandle;
 
     #[test]
     fn config_serializes_to_valid_ron_test() {
@@ -670,4 +919,178 @@ mod tests {
         let ron_config = ron::from_str::<'_, Config>(ron.unwrap().as_str());
         assert!(ron_config.is_ok(), "Could not deserialize default config");
     }
+
+    #[test]
+    fn workspaces_mix_explicit_and_auto_geometry_test() {
+        let ron = ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        let wss: Vec<Workspace> = ron
+            .from_str(r#"[(x: "auto", width: 1720), (x: 1720, y: 0, height: "auto", width: 1720)]"#)
+            .unwrap();
+        assert_eq!(wss[0].x, None);
+        assert_eq!(wss[0].y, None);
+        assert_eq!(wss[0].width, Some(1720));
+        assert_eq!(wss[1].x, Some(1720));
+        assert_eq!(wss[1].y, Some(0));
+        assert_eq!(wss[1].height, None);
+    }
+
+    #[test]
+    fn matching_window_rule_sets_the_opacity() {
+        let config = Config {
+            window_rules: Some(vec![WindowHook {
+                window_class: Some("Alacritty".to_string()),
+                spawn_opacity: Some(0.9),
+                ..WindowHook::default()
+            }]),
+            ..Config::default()
+        };
+        let mut manager: Manager<Config, HeadlessDisplayServer> = Manager::new(config);
+        let handle = WindowHandle::MockHandle(1);
+        let mut window = Window::new(handle, None, None);
+        window.res_class = Some("Alacritty".to_string());
+
+        let config = &manager.config;
+        assert!(leftwm_core::Config::setup_predefined_window(
+            config,
+            &mut manager.state,
+            &mut window
+        ));
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetOpacity(h, o) if *h == handle && (*o - 0.9).abs() < f32::EPSILON
+        )));
+    }
+
+    #[test]
+    fn window_rule_matches_are_reported_with_their_score() {
+        let config = Config {
+            window_rules: Some(vec![
+                WindowHook {
+                    window_class: Some("Alacritty".to_string()),
+                    window_title: Some("htop".to_string()),
+                    ..WindowHook::default()
+                },
+                WindowHook {
+                    window_class: Some("krita".to_string()),
+                    ..WindowHook::default()
+                },
+            ]),
+            ..Config::default()
+        };
+        let mut terminal = Window::new(WindowHandle::MockHandle(1), None, None);
+        terminal.res_class = Some("Alacritty".to_string());
+        let mut htop = terminal.clone();
+        htop.handle = WindowHandle::MockHandle(2);
+        htop.name = Some("htop".to_string());
+
+        assert_eq!(
+            config.window_rule_matches(&[terminal, htop]),
+            vec![
+                r#"Window rule 0 (WM_CLASS=Some("Alacritty"), TITLE=Some("htop")):"#,
+                r#"    MockHandle(1) [[ TITLE=None; WM_CLASS=None, Some("Alacritty") ]] with score 1"#,
+                r#"    MockHandle(2) [[ TITLE=Some("htop"); WM_CLASS=None, Some("Alacritty") ]] with score 3"#,
+                r#"Window rule 1 (WM_CLASS=Some("krita"), TITLE=None):"#,
+                "    matches no windows",
+            ]
+        );
+    }
+
+    #[test]
+    fn window_rules_are_only_reapplied_for_a_better_match() {
+        let config = Config {
+            window_rules: Some(vec![
+                WindowHook {
+                    window_class: Some("Alacritty".to_string()),
+                    spawn_on_tag: Some(2),
+                    ..WindowHook::default()
+                },
+                WindowHook {
+                    window_title: Some("htop".to_string()),
+                    spawn_on_tag: Some(3),
+                    ..WindowHook::default()
+                },
+            ]),
+            ..Config::default()
+        };
+        let mut manager: Manager<Config, HeadlessDisplayServer> = Manager::new(config);
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.res_class = Some("Alacritty".to_string());
+
+        let config = &manager.config;
+        assert!(leftwm_core::Config::setup_predefined_window(
+            config,
+            &mut manager.state,
+            &mut window
+        ));
+        assert_eq!(window.tag, Some(2));
+        assert!(!leftwm_core::Config::setup_predefined_window(
+            config,
+            &mut manager.state,
+            &mut window
+        ));
+
+        window.name = Some("htop".to_string());
+        assert!(leftwm_core::Config::setup_predefined_window(
+            config,
+            &mut manager.state,
+            &mut window
+        ));
+        assert_eq!(window.tag, Some(3));
+    }
+
+    #[test]
+    fn reloading_the_keybinds_leaves_windows_and_workspaces_alone() {
+        let mut manager: Manager<Config, HeadlessDisplayServer> = Manager::new(Config::default());
+        manager.screen_create_handler(leftwm_core::models::Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.actions.clear();
+        let windows = serde_json::to_string(&manager.state.windows).unwrap();
+        let workspaces = serde_json::to_string(&manager.state.workspaces).unwrap();
+
+        assert!(!<Config as leftwm_core::Config>::command_handler(
+            "ReloadKeybinds",
+            &mut manager
+        ));
+        assert_eq!(
+            serde_json::to_string(&manager.state.windows).unwrap(),
+            windows
+        );
+        assert_eq!(
+            serde_json::to_string(&manager.state.workspaces).unwrap(),
+            workspaces
+        );
+        assert!(manager.state.actions.is_empty());
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn reloaded_keybinds_follow_the_config_file() {
+        use lefthk_core::config::Config as _;
+
+        let file = tempfile::Builder::new().suffix(".ron").tempfile().unwrap();
+        let keybind = |key: &str| {
+            format!(
+                r#"(keybind: [(command: Execute, value: "st", modifier: ["modkey"], key: "{}")])"#,
+                key
+            )
+        };
+        let keys = |path: &Path| -> Vec<String> {
+            let config = parse_config_file(path).unwrap();
+            config
+                .mapped_bindings()
+                .into_iter()
+                .map(|b| b.key)
+                .collect()
+        };
+
+        fs::write(file.path(), keybind("a")).unwrap();
+        assert_eq!(keys(file.path()), vec!["a"]);
+
+        fs::write(file.path(), keybind("b")).unwrap();
+        assert_eq!(keys(file.path()), vec!["b"]);
+    }
 }
@@ -0,0 +1,46 @@
+use super::keybind::{pipe_command, validate_command, Modifier};
+use crate::BaseCommand;
+use crate::Config;
+use anyhow::{Context, Result};
+use leftwm_core::utils::modmask_lookup;
+use serde::{Deserialize, Serialize};
+
+/// A mouse button bound to a command, e.g. scrolling up with the modkey to focus the next tag.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Mousebind {
+    pub command: BaseCommand,
+    #[serde(default)]
+    pub value: String,
+    pub modifier: Option<Modifier>,
+    pub button: String,
+}
+
+impl Mousebind {
+    pub fn try_convert_to_core_mousebind(
+        &self,
+        config: &Config,
+    ) -> Result<leftwm_core::config::Mousebind> {
+        validate_command(config, self.command, &self.value)?;
+        let button = modmask_lookup::into_button(&self.button)
+            .with_context(|| format!("unknown button `{}`", self.button))?;
+        let modifiers: Vec<String> = self
+            .modifier
+            .iter()
+            .flatten()
+            .map(|m| {
+                if m == "modkey" {
+                    config.modkey.clone()
+                } else {
+                    m
+                }
+            })
+            .collect();
+        let command =
+            leftwm_core::parse_external_command(&pipe_command(config, self.command, &self.value))?;
+        Ok(leftwm_core::config::Mousebind {
+            modmask: modmask_lookup::into_modmask(&modifiers),
+            button,
+            command,
+        })
+    }
+}
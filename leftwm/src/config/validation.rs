@@ -0,0 +1,875 @@
+//! Validation of the config without loading it into the running window manager.
+
+use super::{all_ids_some, all_ids_unique, get_workspace_ids, Config};
+use anyhow::{anyhow, Result};
+use leftwm_core::config::Workspace;
+use ron::{extensions::Extensions, Options};
+#[cfg(feature = "lefthk")]
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use xdg::BaseDirectories;
+
+/// A problem found while validating the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Some workspaces share the same ID.
+    DuplicateWorkspaceIds,
+    /// Only some of the workspaces have an ID.
+    PartialWorkspaceIds,
+    /// Two workspaces cover some of the same area, given by their position in the config.
+    OverlappingWorkspaces(usize, usize),
+    /// A workspace has a scale which isn't a positive number, given by its position in the config.
+    /// The scale `1.0` is used instead.
+    InvalidScale(usize, String),
+    /// A default layout is given for a tag which isn't configured.
+    UnknownDefaultLayoutTag(String),
+    /// The hidden scratchpad tag has the same label as a configured tag.
+    ScratchpadTagLabelClash(String),
+    /// The hidden overlay tag has the same label as a configured tag or the scratchpad tag.
+    OverlayTagLabelClash(String),
+    /// A window rule has an opacity outside of 0.0 to 1.0, the opacity is ignored.
+    InvalidOpacity(String),
+    /// A keybind can't be converted into a working keybind.
+    InvalidKeybind {
+        command: String,
+        key: String,
+        reason: String,
+    },
+    /// Several keybinds use the same keys, only the first one is used.
+    ConflictingKeybinds {
+        keys: String,
+        kept: String,
+        shadowed: String,
+    },
+    /// A mousebind can't be converted into a working mousebind.
+    InvalidMousebind {
+        command: String,
+        button: String,
+        reason: String,
+    },
+    /// A workspace has a default tag which isn't configured, given as its position in the config.
+    UnknownDefaultTag(usize, usize),
+    /// Several workspaces have the same default tag, only the first one starts on it.
+    DuplicateDefaultTag(usize),
+    /// A top-level key of the config file isn't a config option, it is ignored.
+    UnknownKey(String),
+}
+
+impl Warning {
+    /// Errors make the config unusable, the default config is used instead.
+    #[must_use]
+    pub const fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Warning::DuplicateWorkspaceIds | Warning::PartialWorkspaceIds
+        )
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::DuplicateWorkspaceIds => write!(
+                f,
+                "Your config file contains duplicate workspace IDs. Please assign unique IDs to workspaces."
+            ),
+            Warning::PartialWorkspaceIds => write!(
+                f,
+                "Your config file specifies an ID for some but not all workspaces. This can lead to ID collisions and is not allowed."
+            ),
+            Warning::OverlappingWorkspaces(a, b) => write!(
+                f,
+                "Your config file contains workspaces {} and {}, which overlap. Windows in the overlapping area may be placed on either of them.",
+                a, b
+            ),
+            Warning::InvalidScale(workspace, scale) => write!(
+                f,
+                "Your config file contains workspace {} with the scale {}, which is not a positive number. The scale 1.0 is used instead.",
+                workspace, scale
+            ),
+            Warning::UnknownDefaultLayoutTag(label) => write!(
+                f,
+                "Your config file contains a default layout for the tag `{}`, which is not a configured tag.",
+                label
+            ),
+            Warning::ScratchpadTagLabelClash(label) => write!(
+                f,
+                "Your config file uses `{}` as the label of a tag and of the hidden scratchpad tag. Please change `scratchpad_tag_label`.",
+                label
+            ),
+            Warning::OverlayTagLabelClash(label) => write!(
+                f,
+                "Your config file uses `{}` as the label of the hidden overlay tag and of another tag. Please change `overlay_tag_label`.",
+                label
+            ),
+            Warning::InvalidOpacity(opacity) => write!(
+                f,
+                "Your config file contains a window rule with the opacity {}, which is not between 0.0 and 1.0.",
+                opacity
+            ),
+            Warning::InvalidKeybind {
+                command,
+                key,
+                reason,
+            } => write!(f, "Keybind {} on key `{}` is invalid: {}", command, key, reason),
+            Warning::ConflictingKeybinds {
+                keys,
+                kept,
+                shadowed,
+            } => write!(
+                f,
+                "Keybind {} is bound to both {} and {}, {} is ignored.",
+                keys, kept, shadowed, shadowed
+            ),
+            Warning::InvalidMousebind {
+                command,
+                button,
+                reason,
+            } => write!(
+                f,
+                "Mousebind {} on button `{}` is invalid: {}",
+                command, button, reason
+            ),
+            Warning::UnknownDefaultTag(workspace, tag) => write!(
+                f,
+                "Your config file contains workspace {} with the default tag {}, which is not a configured tag.",
+                workspace, tag
+            ),
+            Warning::DuplicateDefaultTag(tag) => write!(
+                f,
+                "Your config file uses tag {} as the default tag of several workspaces, only the first one starts on it.",
+                tag
+            ),
+            Warning::UnknownKey(key) => write!(
+                f,
+                "Your config file contains the unknown option `{}`, which is ignored. Is it misspelled?",
+                key
+            ),
+        }
+    }
+}
+
+/// Returns the path of the config file, preferring `config.ron` over `config.toml`.
+/// Returns `None` if neither exists.
+///
+/// # Errors
+///
+/// Errors if the config directory can't be determined or created.
+pub fn config_file_path() -> Result<Option<PathBuf>> {
+    let path = BaseDirectories::with_prefix("leftwm")?;
+    let config_file_ron = path.place_config_file("config.ron")?;
+    let config_file_toml = path.place_config_file("config.toml")?;
+    if config_file_ron.exists() {
+        Ok(Some(config_file_ron))
+    } else if config_file_toml.exists() {
+        Ok(Some(config_file_toml))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parses the config file at `path`, as RON or TOML depending on its extension.
+///
+/// # Errors
+///
+/// Errors if the file can't be read or doesn't contain a valid config.
+pub fn parse_config_file(path: &Path) -> Result<Config> {
+    let contents = fs::read_to_string(path)?;
+    if path.extension() == Some(std::ffi::OsStr::new("ron")) {
+        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        Ok(ron.from_str(&contents)?)
+    } else {
+        Ok(toml::from_str(&contents)?)
+    }
+}
+
+/// Parses and validates the config file without loading it.
+///
+/// # Errors
+///
+/// Errors if there is no config file, or if it can't be parsed.
+pub fn validate_config_file() -> Result<Vec<Warning>> {
+    let path = config_file_path()?.ok_or_else(|| anyhow!("No config file found"))?;
+    let config = parse_config_file(&path)?;
+    let mut warnings = validate_config_keys(&path)?;
+    warnings.append(&mut validate_config(&config));
+    Ok(warnings)
+}
+
+/// Returns a warning for each top-level key of the config file which isn't a config option.
+/// Misspelled options would otherwise silently fall back to their default.
+///
+/// # Errors
+///
+/// Errors if the file can't be read or parsed.
+pub fn validate_config_keys(path: &Path) -> Result<Vec<Warning>> {
+    let contents = fs::read_to_string(path)?;
+    let keys: Vec<String> = if path.extension() == Some(std::ffi::OsStr::new("ron")) {
+        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        match ron.from_str(&contents)? {
+            ron::Value::Map(map) => map
+                .keys()
+                .filter_map(|key| match key {
+                    ron::Value::String(key) => Some(key.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    } else {
+        match toml::from_str(&contents)? {
+            toml::Value::Table(table) => table.keys().cloned().collect(),
+            _ => vec![],
+        }
+    };
+    let known = known_config_keys();
+    Ok(keys
+        .into_iter()
+        .filter(|key| !known.contains(key))
+        .map(Warning::UnknownKey)
+        .collect())
+}
+
+/// The names of all config options, taken from the serialized default config.
+fn known_config_keys() -> Vec<String> {
+    let mut keys: Vec<String> = match serde_json::to_value(Config::default()) {
+        Ok(serde_json::Value::Object(map)) => map.keys().cloned().collect(),
+        _ => vec![],
+    };
+    // Keybinds are valid even when built without them.
+    keys.push("keybind".to_owned());
+    keys
+}
+
+/// Returns all problems found in the config.
+#[must_use]
+pub fn validate_config(config: &Config) -> Vec<Warning> {
+    let mut warnings = vec![];
+
+    if let Some(wss) = &config.workspaces {
+        let ids = get_workspace_ids(wss);
+        if ids.iter().any(Option::is_some) {
+            if !all_ids_some(&ids) {
+                warnings.push(Warning::PartialWorkspaceIds);
+            } else if !all_ids_unique(&ids) {
+                warnings.push(Warning::DuplicateWorkspaceIds);
+            }
+        }
+        for (a, b) in overlapping_workspaces(wss) {
+            warnings.push(Warning::OverlappingWorkspaces(a + 1, b + 1));
+        }
+        for (i, ws) in wss.iter().enumerate() {
+            if let (Some(scale), None) = (ws.scale, ws.valid_scale()) {
+                warnings.push(Warning::InvalidScale(i + 1, scale.to_string()));
+            }
+        }
+        warnings.append(&mut validate_default_tags(config, wss));
+    }
+
+    if let Some(default_layouts) = &config.default_layouts {
+        let labels = leftwm_core::Config::create_list_of_tag_labels(config);
+        for label in default_layouts.keys() {
+            if !labels.contains(label) {
+                warnings.push(Warning::UnknownDefaultLayoutTag(label.clone()));
+            }
+        }
+    }
+
+    let labels = leftwm_core::Config::create_list_of_tag_labels(config);
+    if labels.contains(&config.scratchpad_tag_label) {
+        warnings.push(Warning::ScratchpadTagLabelClash(
+            config.scratchpad_tag_label.clone(),
+        ));
+    }
+    if labels.contains(&config.overlay_tag_label)
+        || config.overlay_tag_label == config.scratchpad_tag_label
+    {
+        warnings.push(Warning::OverlayTagLabelClash(
+            config.overlay_tag_label.clone(),
+        ));
+    }
+
+    for rule in config.window_rules.iter().flatten() {
+        if let Some(opacity) = rule.spawn_opacity {
+            if !(0.0..=1.0).contains(&opacity) {
+                warnings.push(Warning::InvalidOpacity(opacity.to_string()));
+            }
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    warnings.append(&mut validate_keybinds(config));
+
+    for mousebind in &config.mousebind {
+        if let Err(err) = mousebind.try_convert_to_core_mousebind(config) {
+            warnings.push(Warning::InvalidMousebind {
+                command: format!("{:?}", mousebind.command),
+                button: mousebind.button.clone(),
+                reason: err.to_string(),
+            });
+        }
+    }
+
+    warnings
+}
+
+/// Checks that the default tags of the workspaces exist and are used only once.
+fn validate_default_tags(config: &Config, wss: &[Workspace]) -> Vec<Warning> {
+    let tag_count = leftwm_core::Config::create_list_of_tag_labels(config).len();
+    let mut warnings = vec![];
+    let mut seen = vec![];
+    for (index, tag) in wss
+        .iter()
+        .enumerate()
+        .filter_map(|(index, ws)| Some((index, ws.default_tag?)))
+    {
+        if !(1..=tag_count).contains(&tag) {
+            warnings.push(Warning::UnknownDefaultTag(index + 1, tag));
+        } else if seen.contains(&tag) {
+            let duplicate = Warning::DuplicateDefaultTag(tag);
+            if !warnings.contains(&duplicate) {
+                warnings.push(duplicate);
+            }
+        } else {
+            seen.push(tag);
+        }
+    }
+    warnings
+}
+
+/// Finds the pairs of workspaces which cover some of the same area, by their index.
+/// Workspaces with an automatic geometry are skipped, as their area depends on the screens.
+fn overlapping_workspaces(wss: &[Workspace]) -> Vec<(usize, usize)> {
+    let areas: Vec<Option<(i32, i32, i32, i32)>> = wss
+        .iter()
+        .map(|ws| Some((ws.x?, ws.y?, ws.width?, ws.height?)))
+        .collect();
+    let mut overlaps = vec![];
+    for (a, &area_a) in areas.iter().enumerate() {
+        for (b, &area_b) in areas.iter().enumerate().skip(a + 1) {
+            if let (Some((ax, ay, aw, ah)), Some((bx, by, bw, bh))) = (area_a, area_b) {
+                if ax < bx + bw && bx < ax + aw && ay < by + bh && by < ay + ah {
+                    overlaps.push((a, b));
+                }
+            }
+        }
+    }
+    overlaps
+}
+
+#[cfg(feature = "lefthk")]
+fn validate_keybinds(config: &Config) -> Vec<Warning> {
+    let mut warnings = vec![];
+    let mut commands = vec![];
+    let mut bindings = vec![];
+    for keybind in config.substituted_keybinds() {
+        match keybind.try_convert_to_lefthk_keybind(config) {
+            Ok(binding) => {
+                commands.push(keybind.command);
+                bindings.push(binding);
+            }
+            Err(err) => warnings.push(Warning::InvalidKeybind {
+                command: format!("{:?}", keybind.command),
+                key: keybind.key.clone(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    for (kept, shadowed) in keybind_conflicts(&bindings) {
+        let binding = &bindings[shadowed];
+        warnings.push(Warning::ConflictingKeybinds {
+            keys: format!("{}+{}", binding.modifier.join("+"), binding.key),
+            kept: format!("{:?}", commands[kept]),
+            shadowed: format!("{:?}", commands[shadowed]),
+        });
+    }
+    warnings
+}
+
+/// Finds the keybinds which use the same modifiers and key as an earlier keybind.
+/// Returns the indices of the earlier and the shadowed keybind, ordered by the latter.
+#[cfg(feature = "lefthk")]
+pub(super) fn keybind_conflicts(bindings: &[lefthk_core::config::Keybind]) -> Vec<(usize, usize)> {
+    let mut seen: HashMap<(Vec<String>, &str), usize> = HashMap::new();
+    let mut conflicts = vec![];
+    for (index, binding) in bindings.iter().enumerate() {
+        let mut modifier = binding.modifier.clone();
+        modifier.sort_unstable();
+        match seen.entry((modifier, binding.key.as_str())) {
+            Entry::Occupied(kept) => conflicts.push((*kept.get(), index)),
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "lefthk")]
+    use crate::config::{
+        keybind::{ChainedCommand, Keybind},
+        Modifier,
+    };
+    #[cfg(feature = "lefthk")]
+    use crate::BaseCommand;
+    use std::io::Write;
+
+    fn config_file(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".ron").tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        assert!(validate_config(&Config::default()).is_empty());
+    }
+
+    #[test]
+    fn misspelled_keys_are_reported() {
+        let file = config_file(r#"(modekey: "Mod4", tags: ["1"], focus_new_windows: true)"#);
+        let warnings = validate_config_keys(file.path()).unwrap();
+        assert_eq!(warnings, vec![Warning::UnknownKey("modekey".to_string())]);
+        assert!(!warnings[0].is_error());
+        assert!(warnings[0].to_string().contains("`modekey`"));
+
+        let mut file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        file.write_all(b"modkey = \"Mod4\"\nmousekeys = \"Mod4\"\n")
+            .unwrap();
+        let warnings = validate_config_keys(file.path()).unwrap();
+        assert_eq!(warnings, vec![Warning::UnknownKey("mousekeys".to_string())]);
+    }
+
+    #[test]
+    fn duplicate_workspace_ids_are_errors() {
+        let config = Config {
+            workspaces: Some(vec![
+                Workspace {
+                    id: Some(0),
+                    ..Workspace::default()
+                },
+                Workspace {
+                    id: Some(0),
+                    ..Workspace::default()
+                },
+            ]),
+            ..Config::default()
+        };
+        let warnings = validate_config(&config);
+        assert_eq!(warnings, vec![Warning::DuplicateWorkspaceIds]);
+        assert!(warnings[0].is_error());
+    }
+
+    #[test]
+    fn overlapping_workspaces_are_reported() {
+        let workspace = |x, width| Workspace {
+            x: Some(x),
+            y: Some(0),
+            width: Some(width),
+            height: Some(1080),
+            ..Workspace::default()
+        };
+        let config = Config {
+            workspaces: Some(vec![
+                workspace(0, 1920),
+                workspace(1920, 1920),
+                workspace(1000, 1920),
+                Workspace::default(),
+            ]),
+            ..Config::default()
+        };
+        let warnings = validate_config(&config);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::OverlappingWorkspaces(1, 3),
+                Warning::OverlappingWorkspaces(2, 3),
+            ]
+        );
+        assert!(!warnings[0].is_error());
+    }
+
+    #[test]
+    fn workspaces_without_a_positive_scale_are_reported() {
+        let workspace = |scale| Workspace {
+            scale: Some(scale),
+            ..Workspace::default()
+        };
+        let config = Config {
+            workspaces: Some(vec![
+                workspace(1.5),
+                workspace(0.0),
+                workspace(-1.0),
+                workspace(f64::NAN),
+            ]),
+            ..Config::default()
+        };
+        let warnings = validate_config(&config);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::InvalidScale(2, "0".to_string()),
+                Warning::InvalidScale(3, "-1".to_string()),
+                Warning::InvalidScale(4, "NaN".to_string()),
+            ]
+        );
+        assert!(!warnings[0].is_error());
+    }
+
+    #[test]
+    fn invalid_default_tags_are_reported() {
+        let workspace = |default_tag| Workspace {
+            default_tag: Some(default_tag),
+            ..Workspace::default()
+        };
+        let config = Config {
+            workspaces: Some(vec![
+                workspace(1),
+                workspace(9),
+                workspace(10),
+                workspace(1),
+            ]),
+            ..Config::default()
+        };
+        let warnings = validate_config(&config);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::UnknownDefaultTag(3, 10),
+                Warning::DuplicateDefaultTag(1)
+            ]
+        );
+        assert!(warnings.iter().all(|warning| !warning.is_error()));
+    }
+
+    #[test]
+    fn out_of_range_window_rule_opacities_are_reported() {
+        let file = config_file(
+            r#"(
+                window_rules: [
+                    (window_class: "Alacritty", spawn_opacity: 0.9),
+                    (window_class: "mpv", spawn_opacity: 1.5),
+                ],
+            )"#,
+        );
+        let config = parse_config_file(file.path()).unwrap();
+        let warnings = validate_config(&config);
+        assert_eq!(warnings, vec![Warning::InvalidOpacity("1.5".to_string())]);
+        assert!(!warnings[0].is_error());
+    }
+
+    #[test]
+    fn scratchpad_tag_label_clashing_with_a_tag_is_reported() {
+        let clash = Warning::ScratchpadTagLabelClash("NSP".to_string());
+        let mut config = Config {
+            tags: Some(vec!["1".to_string(), "NSP".to_string()]),
+            ..Config::default()
+        };
+        assert!(validate_config(&config).contains(&clash));
+        assert!(!clash.is_error());
+
+        config.scratchpad_tag_label = "hidden".to_string();
+        assert!(!validate_config(&config).contains(&clash));
+    }
+
+    #[test]
+    fn overlay_tag_label_clashing_with_another_tag_is_reported() {
+        let clash = Warning::OverlayTagLabelClash("OVERLAY".to_string());
+        let mut config = Config {
+            tags: Some(vec!["1".to_string(), "OVERLAY".to_string()]),
+            ..Config::default()
+        };
+        assert!(validate_config(&config).contains(&clash));
+        assert!(!clash.is_error());
+
+        config.overlay_tag_label = "above".to_string();
+        assert!(!validate_config(&config).contains(&clash));
+
+        config.scratchpad_tag_label = "above".to_string();
+        let clash = Warning::OverlayTagLabelClash("above".to_string());
+        assert!(validate_config(&config).contains(&clash));
+    }
+
+    #[test]
+    fn mousebinds_in_a_file_are_parsed() {
+        use leftwm_core::config::Mousebind;
+        use leftwm_core::utils::modmask_lookup::{into_button, into_mod};
+        use leftwm_core::Command;
+
+        let file = config_file(
+            r#"(
+                modkey: "Mod4",
+                mousebind: [
+                    (command: FocusNextTag, modifier: ["modkey"], button: "ScrollUp"),
+                    (command: GotoTag, value: "2", modifier: ["modkey", "Shift"], button: "Button2"),
+                    (command: CloseWindow, modifier: ["modkey"], button: "Button9"),
+                ],
+            )"#,
+        );
+        let config = parse_config_file(file.path()).unwrap();
+
+        assert_eq!(
+            leftwm_core::Config::mousebinds(&config),
+            vec![
+                Mousebind {
+                    modmask: into_mod("Mod4"),
+                    button: into_button("ScrollUp").unwrap(),
+                    command: Command::FocusNextTag,
+                },
+                Mousebind {
+                    modmask: into_mod("Mod4") | into_mod("Shift"),
+                    button: into_button("Middle").unwrap(),
+                    command: Command::GoToTag { tag: 2, swap: true },
+                },
+            ]
+        );
+        assert!(matches!(
+            &validate_config(&config)[..],
+            [Warning::InvalidMousebind { command, button, .. }] if command == "CloseWindow" && button == "Button9"
+        ));
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn bad_keybinds_and_workspaces_in_a_file_are_reported() {
+        let file = config_file(
+            r#"(
+                workspaces: [(id: 0), (id: 0)],
+                keybind: [
+                    (command: GotoTag, value: "first", modifier: ["modkey"], key: "1"),
+                    (command: GotoTag, value: "1", modifier: ["modkey"], key: "1"),
+                ],
+            )"#,
+        );
+        let config = parse_config_file(file.path()).unwrap();
+        let warnings = validate_config(&config);
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0], Warning::DuplicateWorkspaceIds);
+        assert!(matches!(
+            &warnings[1],
+            Warning::InvalidKeybind { command, key, .. } if command == "GotoTag" && key == "1"
+        ));
+        assert!(!warnings[1].is_error());
+    }
+
+    #[cfg(feature = "lefthk")]
+    fn return_keybinds() -> Config {
+        let keybind = |command, value: &str| Keybind {
+            command,
+            value: value.to_string(),
+            commands: vec![],
+            modifier: Some(Modifier::List(vec!["modkey".to_string()])),
+            key: "Return".to_string(),
+        };
+        Config {
+            keybind: vec![
+                keybind(BaseCommand::Execute, "alacritty"),
+                keybind(BaseCommand::CloseWindow, ""),
+            ],
+            ..Config::default()
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn only_the_first_of_conflicting_keybinds_is_mapped() {
+        use lefthk_core::config::{Command, Config as _};
+
+        let bindings = return_keybinds().mapped_bindings();
+        assert_eq!(bindings.len(), 1);
+        assert!(matches!(&bindings[0].command, Command::Execute(cmd) if cmd.contains("alacritty")));
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn conflicting_keybinds_are_reported() {
+        let warnings = validate_config(&return_keybinds());
+        assert_eq!(
+            warnings,
+            vec![Warning::ConflictingKeybinds {
+                keys: "Mod4+Return".to_string(),
+                kept: "Execute".to_string(),
+                shadowed: "CloseWindow".to_string(),
+            }]
+        );
+    }
+
+    #[cfg(feature = "lefthk")]
+    fn tag_keybind(command: BaseCommand, value: &str) -> Config {
+        Config {
+            tags: Some(vec!["1".to_string(), "2".to_string(), "3".to_string()]),
+            keybind: vec![Keybind {
+                command,
+                value: value.to_string(),
+                commands: vec![],
+                modifier: Some(Modifier::List(vec!["modkey".to_string()])),
+                key: "1".to_string(),
+            }],
+            ..Config::default()
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn tag_indices_within_the_configured_tags_are_valid() {
+        assert!(validate_config(&tag_keybind(BaseCommand::GotoTag, "3")).is_empty());
+        assert!(validate_config(&tag_keybind(BaseCommand::MoveToTag, " 1 ")).is_empty());
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn out_of_range_tag_indices_are_reported() {
+        for (command, value) in [(BaseCommand::GotoTag, "4"), (BaseCommand::MoveToTag, "0")] {
+            let warnings = validate_config(&tag_keybind(command, value));
+            assert_eq!(warnings.len(), 1);
+            assert!(matches!(
+                &warnings[0],
+                Warning::InvalidKeybind { reason, .. } if reason.contains("out of range")
+            ));
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn swap_monitor_tags_keybinds_need_two_workspace_indices() {
+        let config = tag_keybind(BaseCommand::SwapMonitorTags, "0 1");
+        assert!(validate_config(&config).is_empty());
+        for value in ["", "0", "0 1 2", "0 left"] {
+            let warnings = validate_config(&tag_keybind(BaseCommand::SwapMonitorTags, value));
+            assert_eq!(warnings.len(), 1, "{value}");
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn tag_keybinds_need_two_configured_tags() {
+        for command in [BaseCommand::MoveTag, BaseCommand::SwapTagContents] {
+            assert!(validate_config(&tag_keybind(command, "1 3")).is_empty());
+            for value in ["", "1", "1 2 3", "1 4"] {
+                let warnings = validate_config(&tag_keybind(command, value));
+                assert_eq!(warnings.len(), 1, "{command:?} {value}");
+            }
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn chained_keybinds_send_all_commands_at_once() {
+        use lefthk_core::config::Command;
+
+        let keybind: Keybind = toml::from_str(
+            r#"
+            command = "Chain"
+            commands = [
+                { command = "GotoTag", value = "2" },
+                { command = "Execute", value = "alacritty" },
+            ]
+            modifier = ["modkey"]
+            key = "t"
+            "#,
+        )
+        .unwrap();
+        let binding = keybind
+            .try_convert_to_lefthk_keybind(&Config::default())
+            .unwrap();
+        assert!(matches!(
+            &binding.command,
+            Command::Execute(cmd)
+                if cmd == "leftwm-command 'Chain [\"GoToTag 2 true\",\"Execute alacritty\"]'\n"
+        ));
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn return_to_last_tag_keybinds_may_follow_the_current_tag_swap() {
+        use lefthk_core::config::Command;
+
+        for (value, disable_current_tag_swap, line) in [
+            ("", true, "ReturnToLastTag"),
+            ("false", true, "ReturnToLastTag"),
+            ("true", false, "ReturnToLastTag true"),
+            ("true", true, "ReturnToLastTag false"),
+        ] {
+            let config = Config {
+                disable_current_tag_swap,
+                ..tag_keybind(BaseCommand::ReturnToLastTag, value)
+            };
+            let binding = config.keybind[0]
+                .try_convert_to_lefthk_keybind(&config)
+                .unwrap();
+            let expected = format!("leftwm-command '{line}'\n");
+            assert!(matches!(&binding.command, Command::Execute(cmd) if cmd == &expected));
+        }
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn only_spawned_keybinds_are_launched_by_leftwm() {
+        use lefthk_core::config::Command;
+
+        let config = tag_keybind(BaseCommand::Execute, "alacritty -e htop");
+        let binding = config.keybind[0]
+            .try_convert_to_lefthk_keybind(&config)
+            .unwrap();
+        assert!(matches!(
+            &binding.command,
+            Command::Execute(cmd) if cmd == "alacritty -e htop"
+        ));
+
+        let config = tag_keybind(BaseCommand::Spawn, "alacritty -e htop");
+        let binding = config.keybind[0]
+            .try_convert_to_lefthk_keybind(&config)
+            .unwrap();
+        assert!(matches!(
+            &binding.command,
+            Command::Execute(cmd) if cmd == "leftwm-command 'Spawn alacritty -e htop'\n"
+        ));
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn quotes_in_chained_commands_are_escaped_for_the_shell() {
+        use lefthk_core::config::Command;
+
+        let keybind: Keybind = toml::from_str(
+            r#"
+            command = "Chain"
+            commands = [{ command = "Execute", value = "notify-send 'it works'" }]
+            modifier = ["modkey"]
+            key = "t"
+            "#,
+        )
+        .unwrap();
+        let binding = keybind
+            .try_convert_to_lefthk_keybind(&Config::default())
+            .unwrap();
+        assert!(matches!(
+            &binding.command,
+            Command::Execute(cmd)
+                if cmd == "leftwm-command 'Chain [\"Execute notify-send '\\''it works'\\''\"]'\n"
+        ));
+    }
+
+    #[cfg(feature = "lefthk")]
+    #[test]
+    fn invalid_chained_commands_are_reported() {
+        let mut config = tag_keybind(BaseCommand::Chain, "");
+        assert_eq!(validate_config(&config).len(), 1);
+
+        config.keybind[0].commands = vec![ChainedCommand {
+            command: BaseCommand::GotoTag,
+            value: "4".to_string(),
+        }];
+        assert!(matches!(
+            &validate_config(&config)[..],
+            [Warning::InvalidKeybind { reason, .. }] if reason.contains("GotoTag")
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+//! Reloads the config when its file is saved.
+
+use super::validation::{parse_config_file, validate_config, validate_config_keys, Warning};
+use leftwm_core::CommandPipe;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use xdg::BaseDirectories;
+
+/// How often the config file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Notices changes to the config file.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    #[must_use]
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified(&path);
+        Self { path, modified }
+    }
+
+    /// Returns true if the file was saved since the last call.
+    pub fn poll(&mut self) -> bool {
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        true
+    }
+
+    /// Returns true if the changed config can be applied.
+    /// A config which can't be parsed or contains errors is rejected, keeping the running one.
+    #[must_use]
+    pub fn accept_change(&self) -> bool {
+        let config = match parse_config_file(&self.path) {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::warn!("Not reloading the changed config: {}", err);
+                return false;
+            }
+        };
+        let mut warnings = validate_config_keys(&self.path).unwrap_or_default();
+        warnings.append(&mut validate_config(&config));
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
+        if warnings.iter().any(Warning::is_error) {
+            tracing::warn!("Not reloading the changed config, it is invalid.");
+            return false;
+        }
+        true
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Watches the config file in the background, soft reloading once a valid config is saved.
+pub fn watch_config(path: PathBuf) {
+    let mut watcher = ConfigWatcher::new(path);
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+        if watcher.poll() && watcher.accept_change() {
+            request_soft_reload();
+        }
+    });
+}
+
+/// Sends `SoftReload` through the command pipe, like `leftwm-command` would.
+fn request_soft_reload() {
+    let pipe = BaseDirectories::with_prefix("leftwm")
+        .ok()
+        .and_then(|dirs| dirs.find_runtime_file(CommandPipe::pipe_name()));
+    let pipe = if let Some(pipe) = pipe {
+        pipe
+    } else {
+        tracing::error!("Could not find the command pipe to reload the config");
+        return;
+    };
+    let result = OpenOptions::new()
+        .append(true)
+        .open(pipe)
+        .and_then(|mut file| writeln!(file, "SoftReload"));
+    if let Err(err) = result {
+        tracing::error!("Could not request a reload of the config: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watched_file(contents: &str) -> (tempfile::NamedTempFile, ConfigWatcher) {
+        let mut file = tempfile::Builder::new().suffix(".ron").tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        let watcher = ConfigWatcher::new(file.path().to_path_buf());
+        (file, watcher)
+    }
+
+    #[test]
+    fn valid_changes_are_accepted() {
+        let (_file, watcher) = watched_file(r#"(modkey: "Mod4", tags: ["1", "2"])"#);
+        assert!(watcher.accept_change());
+    }
+
+    #[test]
+    fn invalid_changes_are_rejected() {
+        let (_file, watcher) = watched_file(r#"(modkey: "Mod4", tags: ["1", "2""#);
+        assert!(!watcher.accept_change());
+
+        let (_file, watcher) = watched_file(r#"(workspaces: [(id: Some(0)), (id: Some(0))])"#);
+        assert!(!watcher.accept_change());
+    }
+
+    #[test]
+    fn an_unchanged_file_is_not_reported() {
+        let (_file, mut watcher) = watched_file(r#"(modkey: "Mod4")"#);
+        assert!(!watcher.poll());
+        // A change is injected by forgetting when the file was saved.
+        watcher.modified = None;
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+    }
+}
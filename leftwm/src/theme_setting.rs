@@ -8,6 +8,8 @@ use std::path::Path;
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ThemeSetting {
     pub border_width: Option<i32>,
+    pub floating_border_width: Option<i32>,
+    pub tiled_border_width: Option<i32>,
     pub margin: Option<CustomMargins>,
     pub workspace_margin: Option<CustomMargins>,
     pub default_width: Option<i32>,
@@ -20,6 +22,8 @@ pub struct ThemeSetting {
     pub background_color: Option<String>,
     #[serde(rename = "on_new_window")]
     pub on_new_window_cmd: Option<String>,
+    #[serde(rename = "on_reload")]
+    pub on_reload_cmd: Option<String>,
 }
 
 impl ThemeSetting {
@@ -38,6 +42,8 @@ impl Default for ThemeSetting {
     fn default() -> Self {
         Self {
             border_width: Some(1),
+            floating_border_width: None,
+            tiled_border_width: None,
             margin: Some(CustomMargins::Int(10)),
             workspace_margin: Some(CustomMargins::Int(10)),
             default_width: Some(1000),
@@ -49,6 +55,7 @@ impl Default for ThemeSetting {
             focused_border_color: Some("#FF0000".to_owned()),
             background_color: Some("#333333".to_owned()),
             on_new_window_cmd: None,
+            on_reload_cmd: None,
         }
     }
 }
@@ -105,6 +112,7 @@ mod tests {
     fn deserialize_custom_theme_config_toml() {
         let config = r#"
 border_width = 0
+floating_border_width = 3
 default_width = 400
 default_height = 400
 always_float = true
@@ -115,6 +123,7 @@ floating_border_color = '#005500'
 focused_border_color = '#FFB53A'
 background_color = '#333333'
 on_new_window = 'echo Hello World'
+on_reload = 'echo Reloaded'
 
 [[gutter]]
 side = "Top"
@@ -126,6 +135,8 @@ value = 0
             config,
             ThemeSetting {
                 border_width: Some(0),
+                floating_border_width: Some(3),
+                tiled_border_width: None,
                 margin: Some(CustomMargins::Int(5)),
                 workspace_margin: Some(CustomMargins::Int(5)),
                 default_width: Some(400),
@@ -141,6 +152,7 @@ value = 0
                 focused_border_color: Some("#FFB53A".to_string()),
                 background_color: Some("#333333".to_owned()),
                 on_new_window_cmd: Some("echo Hello World".to_string()),
+                on_reload_cmd: Some("echo Reloaded".to_string()),
             }
         );
     }
@@ -150,6 +162,7 @@ value = 0
         let config = r##"
 (
     border_width: Some(0),
+    tiled_border_width: Some(2),
     default_width: Some(400),
     default_height: Some(400),
     always_float: Some(true),
@@ -173,6 +186,8 @@ value = 0
             config,
             ThemeSetting {
                 border_width: Some(0),
+                floating_border_width: None,
+                tiled_border_width: Some(2),
                 margin: Some(CustomMargins::Int(5)),
                 workspace_margin: Some(CustomMargins::Int(5)),
                 default_width: Some(400),
@@ -188,6 +203,7 @@ value = 0
                 focused_border_color: Some("#FFB53A".to_string()),
                 background_color: Some("#333333".to_owned()),
                 on_new_window_cmd: Some("echo Hello World".to_string()),
+                on_reload_cmd: None,
             }
         );
     }
@@ -1,5 +1,9 @@
 pub mod log;
 
+/// Exit code of `lefthk-worker` when it quits to reload the keybinds, so it gets restarted.
+#[cfg(feature = "lefthk")]
+pub const LEFTHK_RELOAD_EXIT_CODE: i32 = 3;
+
 pub const fn get_help_template() -> &'static str {
     "\
 {name} {version}